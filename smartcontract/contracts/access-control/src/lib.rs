@@ -1,8 +1,29 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, Address, Env, Vec,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, Address, BytesN, Env,
+    Symbol, Vec,
 };
+use stellar_guard_pausable::PausableError;
+
+/// Numeric level of each step in the built-in role hierarchy, leaving room
+/// for custom roles in between (Member is 200, Admin is 300). The owner can
+/// remap these with `set_role_level`.
+pub const ROLE_LEVEL_STEP: u32 = 100;
+
+/// Maximum number of assignments in one `assign_roles_batch` call.
+pub const MAX_BATCH_SIZE: u32 = 25;
+
+/// Seconds guardians have to reach the recovery threshold once the first
+/// of them asks to recover the owner.
+pub const RECOVERY_WINDOW: u64 = 3 * 24 * 60 * 60;
+
+/// Version of this contract's code, stored by `initialize`.
+pub const VERSION: u32 = 1;
+
+/// Schema version of the events this contract publishes, carried as the last
+/// topic of every event. Bump it whenever an event's payload layout changes.
+pub const EVENT_VERSION: u32 = 1;
 
 // ============================================================================
 // Error Codes
@@ -29,6 +50,30 @@ pub enum Error {
     CannotRemoveOwner = 7,
     /// Action requires higher privilege level.
     InsufficientPrivilege = 8,
+    /// A custom role with this name already exists.
+    RoleAlreadyDefined = 9,
+    /// Role assignment has not expired yet.
+    RoleNotExpired = 10,
+    /// Duration must be greater than zero.
+    InvalidDuration = 11,
+    /// Too many entries passed to a batch call.
+    BatchTooLarge = 12,
+    /// Role changes are paused by the owner.
+    Paused = 13,
+    /// Caller is not a guardian.
+    NotAGuardian = 14,
+    /// Guardian has already backed this recovery.
+    AlreadyApproved = 15,
+    /// Guardian threshold must be > 0 and <= guardian count.
+    InvalidThreshold = 16,
+    /// Audit log entry was not found.
+    AuditEntryNotFound = 17,
+    /// Not enough owners have approved this action.
+    InsufficientApprovals = 18,
+}
+
+impl PausableError for Error {
+    const PAUSED: Self = Error::Paused;
 }
 
 // ============================================================================
@@ -65,6 +110,84 @@ pub enum DataKey {
     AllMembers,
     /// Total number of each role type.
     RoleCount(u32),
+    /// Numeric level of a custom role.
+    CustomRole(Symbol),
+    /// Addresses that can jointly recover ownership.
+    Guardians,
+    /// Number of guardians needed to recover ownership.
+    GuardianThreshold,
+    /// Ownership recovery in progress.
+    Recovery,
+    /// Owner proposed by the current owner, awaiting acceptance.
+    PendingOwner,
+    /// Capability granted directly to an address.
+    Capability(Address, Symbol),
+    /// Default capabilities of every holder of a role.
+    RoleCapabilities(u32),
+    /// Audit log entry by ID.
+    AuditLog(u64),
+    /// Number of audit log entries.
+    AuditCount,
+    /// Number of owners that must approve sensitive actions.
+    OwnerThreshold,
+    /// Owners that have approved a sensitive action.
+    OwnerApprovals(OwnerAction),
+    /// Governance contract allowed to upgrade this contract.
+    Governance,
+    /// Numeric level configured for a built-in role.
+    RoleLevel(u32),
+    /// Contract version that last wrote the stored data.
+    Version,
+    /// Highest role a trusted contract may assign without holding a role.
+    TrustedAssignor(Address),
+}
+
+/// Sensitive actions that need approval from `owner_threshold` owners.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OwnerAction {
+    /// Upgrade to the given WASM hash.
+    Upgrade(BytesN<32>),
+    /// Transfer ownership to the given address.
+    TransferOwnership(Address),
+    /// Change the owner threshold.
+    SetOwnerThreshold(u32),
+    /// Trust the given governance contract to upgrade.
+    SetGovernance(Address),
+    /// Grant the Owner role to the given address.
+    AssignOwner(Address),
+}
+
+/// A single role change in the audit log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    /// Sequential identifier, starting at 1.
+    pub id: u64,
+    /// Kind of change: `assign`, `revoke` or `owner`.
+    pub action: Symbol,
+    /// Address that made the change.
+    pub actor: Address,
+    /// Address whose role changed.
+    pub subject: Address,
+    /// Role before the change as its `Role` value; zero if none.
+    pub old_role: u32,
+    /// Role after the change as its `Role` value; zero if none.
+    pub new_role: u32,
+    /// When the change was made.
+    pub timestamp: u64,
+}
+
+/// An ownership recovery being backed by guardians.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    /// Proposed new owner.
+    pub new_owner: Address,
+    /// When the first guardian asked for this recovery.
+    pub started_at: u64,
+    /// Guardians that have backed this recovery.
+    pub approvals: Vec<Address>,
 }
 
 /// Role assignment record.
@@ -73,12 +196,31 @@ pub enum DataKey {
 pub struct RoleAssignment {
     /// The address holding the role.
     pub address: Address,
-    /// The assigned role.
+    /// The assigned role. For a custom role, the highest built-in role at
+    /// or below its level.
     pub role: Role,
+    /// The custom role, if one was assigned instead of a built-in role.
+    pub custom_role: Option<Symbol>,
     /// When the role was assigned.
     pub assigned_at: u64,
     /// Who assigned this role.
     pub assigned_by: Address,
+    /// When the role lapses; zero for a permanent assignment.
+    pub expires_at: u64,
+    /// Whether the account is suspended pending review.
+    pub suspended: bool,
+}
+
+/// A page of role assignments returned by `list_members_by_role`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleAssignmentPage {
+    /// Assignments on this page.
+    pub items: Vec<RoleAssignment>,
+    /// `start` for the next page, if there is one.
+    pub next_cursor: Option<u64>,
+    /// Whether more matching members follow this page.
+    pub has_more: bool,
 }
 
 /// Access control summary.
@@ -91,6 +233,8 @@ pub struct AccessSummary {
     pub admin_count: u32,
     pub member_count: u32,
     pub viewer_count: u32,
+    pub audit_count: u64,
+    pub owner_threshold: u32,
 }
 
 // ============================================================================
@@ -118,14 +262,18 @@ impl AccessControlContract {
         owner.require_auth();
 
         env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Version, &VERSION);
         env.storage().instance().set(&DataKey::Owner, &owner);
 
         // Assign Owner role
         let assignment = RoleAssignment {
             address: owner.clone(),
             role: Role::Owner,
+            custom_role: None,
             assigned_at: env.ledger().timestamp(),
             assigned_by: owner.clone(),
+            expires_at: 0,
+            suspended: false,
         };
         env.storage()
             .persistent()
@@ -150,8 +298,10 @@ impl AccessControlContract {
             .instance()
             .set(&DataKey::RoleCount(Role::Viewer as u32), &0_u32);
 
-        env.events()
-            .publish((symbol_short!("acl"), symbol_short!("init")), owner.clone());
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("init"), EVENT_VERSION),
+            owner.clone(),
+        );
 
         log!(&env, "Access control initialized with owner {:?}", owner);
         Ok(())
@@ -164,7 +314,9 @@ impl AccessControlContract {
     /// Assign a role to an address.
     /// Only admins and owners can assign roles.
     /// Admins can only assign Member and Viewer roles.
-    /// Only owners can assign Admin roles.
+    /// Only owners can assign Admin roles, and granting Owner needs approval
+    /// from `owner_threshold` owners. An existing owner's role cannot be
+    /// reassigned; owners step down through `transfer_ownership`.
     ///
     /// # Arguments
     /// * `assignor` - The address assigning the role (must be Admin or Owner).
@@ -177,90 +329,205 @@ impl AccessControlContract {
         role: Role,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         assignor.require_auth();
 
-        // Get assignor's role
-        let assignor_role = Self::internal_get_role(&env, &assignor)?;
+        Self::internal_assign(&env, &assignor, &target, role, None, 0)?;
 
-        // Only Owner can assign Owner or Admin roles
-        match role {
-            Role::Owner | Role::Admin => {
-                if assignor_role != Role::Owner {
-                    return Err(Error::InsufficientPrivilege);
-                }
-            }
-            Role::Member | Role::Viewer => {
-                // Admin or Owner can assign these
-                if assignor_role != Role::Owner && assignor_role != Role::Admin {
-                    return Err(Error::InsufficientPrivilege);
-                }
-            }
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("assign"), EVENT_VERSION),
+            (target.clone(), role, assignor.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Assign roles to several addresses at once, applying the same
+    /// privilege rules as `assign_role` to each entry. The batch is
+    /// all-or-nothing: if any entry fails, no role is assigned.
+    ///
+    /// # Errors
+    /// * `Error::BatchTooLarge` - If more than `MAX_BATCH_SIZE` entries are given.
+    /// * `Error::InsufficientPrivilege` - If the assignor cannot grant any entry.
+    pub fn assign_roles_batch(
+        env: Env,
+        assignor: Address,
+        assignments: Vec<(Address, Role)>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        assignor.require_auth();
+
+        if assignments.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
         }
 
-        // Get current role count for the new role
-        let role_val = role.clone() as u32;
-        let mut count: u32 = env
+        for (target, role) in assignments.iter() {
+            Self::internal_assign(&env, &assignor, &target, role, None, 0)?;
+
+            env.events().publish(
+                (symbol_short!("acl"), symbol_short!("assign"), EVENT_VERSION),
+                (target, role, assignor.clone()),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Assign a role that lapses `duration` seconds from now, e.g. for a
+    /// contractor. Same privilege rules as `assign_role`. Once expired the
+    /// address is treated as having no role. Ownership cannot be temporary.
+    ///
+    /// # Errors
+    /// * `Error::InvalidRole` - If role is Owner.
+    /// * `Error::InvalidDuration` - If duration is zero.
+    /// * `Error::InsufficientPrivilege` - If the assignor cannot grant the role.
+    pub fn assign_role_temp(
+        env: Env,
+        assignor: Address,
+        target: Address,
+        role: Role,
+        duration: u64,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        assignor.require_auth();
+
+        if role == Role::Owner {
+            return Err(Error::InvalidRole);
+        }
+        if duration == 0 {
+            return Err(Error::InvalidDuration);
+        }
+        let expires_at = env.ledger().timestamp().saturating_add(duration);
+        Self::internal_assign(&env, &assignor, &target, role, None, expires_at)?;
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("assign_t"),
+                EVENT_VERSION,
+            ),
+            (target.clone(), role, expires_at, assignor.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Remove an expired role assignment to reclaim its storage and update
+    /// the role counts. Anyone can call this.
+    ///
+    /// # Errors
+    /// * `Error::RoleNotFound` - If the address has no assignment.
+    /// * `Error::RoleNotExpired` - If the assignment is permanent or still active.
+    pub fn cleanup_expired(env: Env, target: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        let assignment: RoleAssignment = env
             .storage()
-            .instance()
-            .get(&DataKey::RoleCount(role_val))
-            .unwrap_or(0);
+            .persistent()
+            .get(&DataKey::Role(target.clone()))
+            .ok_or(Error::RoleNotFound)?;
+        if !Self::is_expired(&env, &assignment) {
+            return Err(Error::RoleNotExpired);
+        }
 
-        // If target already has a role, decrement old role count
+        Self::remove_assignment(&env, &target, assignment.role);
+        Self::require_owner_remains(&env)?;
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("expired"),
+                EVENT_VERSION,
+            ),
+            target,
+        );
+
+        Ok(())
+    }
+
+    /// Define a custom role with an explicit numeric level, e.g. a
+    /// moderator between Member (200) and Admin (300).
+    /// Only the owner can define roles.
+    ///
+    /// # Errors
+    /// * `Error::InvalidRole` - If the level is below Viewer or not below Owner.
+    /// * `Error::RoleAlreadyDefined` - If a role with this name exists.
+    pub fn define_role(env: Env, owner: Address, name: Symbol, level: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        if level < Self::role_level(&env, Role::Viewer)
+            || level >= Self::role_level(&env, Role::Owner)
+        {
+            return Err(Error::InvalidRole);
+        }
         if env
             .storage()
-            .persistent()
-            .has(&DataKey::Role(target.clone()))
+            .instance()
+            .has(&DataKey::CustomRole(name.clone()))
         {
-            let old_assignment: RoleAssignment = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Role(target.clone()))
-                .unwrap();
-            let old_role_val = old_assignment.role as u32;
-            let mut old_count: u32 = env
-                .storage()
-                .instance()
-                .get(&DataKey::RoleCount(old_role_val))
-                .unwrap_or(1);
-            if old_count > 0 {
-                old_count -= 1;
-            }
-            env.storage()
-                .instance()
-                .set(&DataKey::RoleCount(old_role_val), &old_count);
-        } else {
-            // New member — add to the list
-            let mut members: Vec<Address> = env
-                .storage()
-                .instance()
-                .get(&DataKey::AllMembers)
-                .unwrap_or(Vec::new(&env));
-            members.push_back(target.clone());
-            env.storage().instance().set(&DataKey::AllMembers, &members);
+            return Err(Error::RoleAlreadyDefined);
         }
 
-        // Create assignment
-        let assignment = RoleAssignment {
-            address: target.clone(),
-            role: role.clone(),
-            assigned_at: env.ledger().timestamp(),
-            assigned_by: assignor.clone(),
-        };
-
         env.storage()
-            .persistent()
-            .set(&DataKey::Role(target.clone()), &assignment);
+            .instance()
+            .set(&DataKey::CustomRole(name.clone()), &level);
 
-        // Update role count
-        count += 1;
-        env.storage()
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("def_role"),
+                EVENT_VERSION,
+            ),
+            (name, level),
+        );
+
+        Ok(())
+    }
+
+    /// Assign a custom role to an address.
+    /// Custom roles at or above Admin level can only be assigned by owners;
+    /// lower ones by admins and owners.
+    ///
+    /// # Errors
+    /// * `Error::RoleNotFound` - If the custom role is not defined.
+    /// * `Error::InsufficientPrivilege` - If the assignor cannot grant it.
+    pub fn assign_custom_role(
+        env: Env,
+        assignor: Address,
+        target: Address,
+        name: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        assignor.require_auth();
+
+        let level: u32 = env
+            .storage()
             .instance()
-            .set(&DataKey::RoleCount(role_val), &count);
+            .get(&DataKey::CustomRole(name.clone()))
+            .ok_or(Error::RoleNotFound)?;
+        let role = Self::base_role(&env, level);
+        Self::internal_assign(&env, &assignor, &target, role, Some(name.clone()), 0)?;
 
         env.events().publish(
-            (symbol_short!("acl"), symbol_short!("assign")),
-            (target.clone(), role, assignor.clone()),
+            (
+                symbol_short!("acl"),
+                symbol_short!("assign_c"),
+                EVENT_VERSION,
+            ),
+            (target.clone(), name, level, assignor.clone()),
         );
 
         Ok(())
@@ -270,6 +537,7 @@ impl AccessControlContract {
     /// Owners cannot be removed. Only owners can revoke admin roles.
     pub fn revoke_role(env: Env, revoker: Address, target: Address) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         revoker.require_auth();
 
@@ -297,111 +565,484 @@ impl AccessControlContract {
             return Err(Error::InsufficientPrivilege);
         }
 
-        // Decrement role count
-        let role_val = target_assignment.role as u32;
-        let mut count: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::RoleCount(role_val))
-            .unwrap_or(1);
-        if count > 0 {
-            count -= 1;
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey::RoleCount(role_val), &count);
+        Self::remove_assignment(&env, &target, target_assignment.role);
+        Self::record_audit(
+            &env,
+            symbol_short!("revoke"),
+            &revoker,
+            &target,
+            Some(target_assignment.role),
+            None,
+        );
 
-        // Remove role assignment
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Role(target.clone()));
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("revoke"), EVENT_VERSION),
+            (target.clone(), revoker.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Revoke every role granted by `assignor`, e.g. after its key was
+    /// compromised. Owner roles are kept. Owner only.
+    ///
+    /// # Returns
+    /// The number of roles revoked.
+    pub fn revoke_by_assignor(env: Env, owner: Address, assignor: Address) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
 
-        // Remove from members list
         let members: Vec<Address> = env
             .storage()
             .instance()
             .get(&DataKey::AllMembers)
             .unwrap_or(Vec::new(&env));
-        let mut new_members = Vec::new(&env);
-        for i in 0..members.len() {
-            let m = members.get(i).unwrap();
-            if m != target {
-                new_members.push_back(m);
+        let mut revoked = 0_u32;
+        for member in members.iter() {
+            let assignment: Option<RoleAssignment> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Role(member.clone()));
+            let Some(assignment) = assignment else {
+                continue;
+            };
+            if assignment.assigned_by != assignor || assignment.role == Role::Owner {
+                continue;
             }
+
+            Self::remove_assignment(&env, &member, assignment.role);
+            Self::record_audit(
+                &env,
+                symbol_short!("revoke"),
+                &owner,
+                &member,
+                Some(assignment.role),
+                None,
+            );
+            revoked += 1;
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::AllMembers, &new_members);
 
         env.events().publish(
-            (symbol_short!("acl"), symbol_short!("revoke")),
-            (target.clone(), revoker.clone()),
+            (
+                symbol_short!("acl"),
+                symbol_short!("revoke_by"),
+                EVENT_VERSION,
+            ),
+            (assignor, revoked, owner),
         );
 
-        Ok(())
-    }
-
-    // ========================================================================
-    // Permission Checks (for cross-contract use)
-    // ========================================================================
-
-    /// Check if an address has at least the specified role level.
-    /// Returns true if the address's role >= required role.
-    pub fn has_permission(env: Env, address: Address, required_role: Role) -> bool {
-        let role_result = Self::internal_get_role(&env, &address);
-        match role_result {
-            Ok(role) => role >= required_role,
-            Err(_) => false,
-        }
-    }
-
-    /// Check if an address is the contract owner.
-    pub fn is_owner(env: Env, address: Address) -> bool {
-        let role_result = Self::internal_get_role(&env, &address);
-        match role_result {
-            Ok(role) => role == Role::Owner,
-            Err(_) => false,
-        }
+        Ok(revoked)
     }
 
-    /// Check if an address is an admin or owner.
-    pub fn is_admin_or_above(env: Env, address: Address) -> bool {
-        let role_result = Self::internal_get_role(&env, &address);
-        match role_result {
-            Ok(role) => role >= Role::Admin,
-            Err(_) => false,
-        }
+    /// Suspend an account pending review. Its permission checks fail as if
+    /// it had no role, but the assignment is kept. Same privilege rules as
+    /// `revoke_role`.
+    pub fn suspend(env: Env, admin: Address, target: Address) -> Result<(), Error> {
+        Self::set_suspended(&env, &admin, &target, true)
     }
 
-    /// Check if an address is a member or above.
-    pub fn is_member_or_above(env: Env, address: Address) -> bool {
-        let role_result = Self::internal_get_role(&env, &address);
-        match role_result {
-            Ok(role) => role >= Role::Member,
-            Err(_) => false,
-        }
+    /// Lift a suspension, restoring the account's permissions.
+    pub fn unsuspend(env: Env, admin: Address, target: Address) -> Result<(), Error> {
+        Self::set_suspended(&env, &admin, &target, false)
     }
 
     // ========================================================================
-    // Query Functions
+    // Capabilities
     // ========================================================================
 
-    /// Get the role of an address.
-    pub fn get_role(env: Env, address: Address) -> Result<RoleAssignment, Error> {
+    /// Grant a single capability to an address, independent of any role.
+    /// Owner only.
+    pub fn grant_capability(
+        env: Env,
+        owner: Address,
+        target: Address,
+        cap: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
         env.storage()
             .persistent()
-            .get(&DataKey::Role(address))
-            .ok_or(Error::RoleNotFound)
+            .set(&DataKey::Capability(target.clone(), cap.clone()), &true);
+
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("grant"), EVENT_VERSION),
+            (target, cap),
+        );
+
+        Ok(())
     }
 
-    /// Get all members with roles.
-    pub fn get_all_members(env: Env) -> Vec<Address> {
+    /// Revoke a capability granted directly to an address. Capabilities the
+    /// address gets from its role are unaffected. Owner only.
+    pub fn revoke_capability(
+        env: Env,
+        owner: Address,
+        target: Address,
+        cap: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Capability(target.clone(), cap.clone()));
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("ungrant"),
+                EVENT_VERSION,
+            ),
+            (target, cap),
+        );
+
+        Ok(())
+    }
+
+    /// Set the default capabilities of every holder of `role`, replacing the
+    /// previous bundle. Owner only.
+    pub fn set_role_capabilities(
+        env: Env,
+        owner: Address,
+        role: Role,
+        caps: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCapabilities(role as u32), &caps);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("role_caps"),
+                EVENT_VERSION,
+            ),
+            (role, caps),
+        );
+
+        Ok(())
+    }
+
+    /// Set the numeric level a built-in role ranks at, replacing the
+    /// default of `ROLE_LEVEL_STEP` per step. Permission checks compare these
+    /// levels, so this can reorder the hierarchy or make room for a new tier.
+    /// Owner only.
+    ///
+    /// # Errors
+    /// * `Error::InvalidRole` - If the level is zero.
+    pub fn set_role_level(env: Env, owner: Address, role: Role, level: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        if level == 0 {
+            return Err(Error::InvalidRole);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleLevel(role as u32), &level);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("role_lvl"),
+                EVENT_VERSION,
+            ),
+            (role, level),
+        );
+
+        Ok(())
+    }
+
+    /// Trust a contract to assign roles up to `max_role` without holding a
+    /// role itself, so governance can manage membership without being made
+    /// an Admin. It can only change addresses whose current role is also
+    /// within `max_role`. Owner only.
+    ///
+    /// # Errors
+    /// * `Error::InvalidRole` - If `max_role` is Owner.
+    pub fn set_trusted_assignor(
+        env: Env,
+        owner: Address,
+        contract_addr: Address,
+        max_role: Role,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        if max_role == Role::Owner {
+            return Err(Error::InvalidRole);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TrustedAssignor(contract_addr.clone()), &max_role);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("trusted"),
+                EVENT_VERSION,
+            ),
+            (contract_addr, max_role),
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the trust granted by `set_trusted_assignor`. Owner only.
+    pub fn remove_trusted_assignor(
+        env: Env,
+        owner: Address,
+        contract_addr: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TrustedAssignor(contract_addr.clone()));
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("untrusted"),
+                EVENT_VERSION,
+            ),
+            contract_addr,
+        );
+
+        Ok(())
+    }
+
+    /// Get the highest role a trusted assignor may assign, if any.
+    pub fn get_trusted_assignor(env: Env, contract_addr: Address) -> Option<Role> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TrustedAssignor(contract_addr))
+    }
+
+    /// Get the numeric level a built-in role currently ranks at.
+    pub fn get_builtin_level(env: Env, role: Role) -> u32 {
+        Self::role_level(&env, role)
+    }
+
+    /// Check if an address holds a capability, either granted directly or
+    /// through the default bundle of its role. Suspended accounts hold none.
+    pub fn has_capability(env: Env, address: Address, cap: Symbol) -> bool {
+        let assignment: Option<RoleAssignment> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Role(address.clone()));
+        if assignment.is_some_and(|assignment| assignment.suspended) {
+            return false;
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Capability(address.clone(), cap.clone()))
+        {
+            return true;
+        }
+        match Self::internal_get_role(&env, &address) {
+            Ok(role) => Self::role_capabilities(env.clone(), role).contains(&cap),
+            Err(_) => false,
+        }
+    }
+
+    /// Get the default capabilities of a role.
+    pub fn role_capabilities(env: Env, role: Role) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoleCapabilities(role as u32))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ========================================================================
+    // Permission Checks (for cross-contract use)
+    // ========================================================================
+
+    /// Check if an address has at least the specified role level.
+    /// Returns true if the address's role >= required role.
+    pub fn has_permission(env: Env, address: Address, required_role: Role) -> bool {
+        let level = Self::role_level(&env, required_role);
+        Self::has_level(env, address, level)
+    }
+
+    /// Check `has_permission` for several addresses in one call, returning
+    /// the results in the same order as `addresses`.
+    ///
+    /// # Errors
+    /// * `Error::BatchTooLarge` - If more than `MAX_BATCH_SIZE` addresses are given.
+    pub fn has_permission_batch(
+        env: Env,
+        addresses: Vec<Address>,
+        required_role: Role,
+    ) -> Result<Vec<bool>, Error> {
+        if addresses.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let level = Self::role_level(&env, required_role);
+        let mut results = Vec::new(&env);
+        for address in addresses.iter() {
+            results.push_back(Self::has_level(env.clone(), address, level));
+        }
+        Ok(results)
+    }
+
+    /// Check if an address's role is at least the given numeric level,
+    /// counting custom roles at their defined level.
+    pub fn has_level(env: Env, address: Address, level: u32) -> bool {
+        match Self::internal_get_level(&env, &address) {
+            Ok(address_level) => address_level >= level,
+            Err(_) => false,
+        }
+    }
+
+    /// Check if an address's role is at least the level of a custom role.
+    pub fn has_custom_permission(env: Env, address: Address, name: Symbol) -> bool {
+        let level: Option<u32> = env.storage().instance().get(&DataKey::CustomRole(name));
+        match level {
+            Some(level) => Self::has_level(env, address, level),
+            None => false,
+        }
+    }
+
+    /// Get the level of a custom role.
+    pub fn get_role_level(env: Env, name: Symbol) -> Result<u32, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::CustomRole(name))
+            .ok_or(Error::RoleNotFound)
+    }
+
+    /// Check if an address is the contract owner.
+    pub fn is_owner(env: Env, address: Address) -> bool {
+        let role_result = Self::internal_get_role(&env, &address);
+        match role_result {
+            Ok(role) => role == Role::Owner,
+            Err(_) => false,
+        }
+    }
+
+    /// Check if an address is an admin or owner.
+    pub fn is_admin_or_above(env: Env, address: Address) -> bool {
+        Self::has_permission(env, address, Role::Admin)
+    }
+
+    /// Check if an address is a member or above.
+    pub fn is_member_or_above(env: Env, address: Address) -> bool {
+        Self::has_permission(env, address, Role::Member)
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get the role of an address.
+    pub fn get_role(env: Env, address: Address) -> Result<RoleAssignment, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Role(address))
+            .ok_or(Error::RoleNotFound)
+    }
+
+    /// Get all members with roles.
+    pub fn get_all_members(env: Env) -> Vec<Address> {
         env.storage()
             .instance()
             .get(&DataKey::AllMembers)
             .unwrap_or(Vec::new(&env))
     }
 
+    /// List the assignments of members holding `role`, in member-list order.
+    ///
+    /// Skips the first `start` matching members and returns at most `limit`
+    /// assignments. To fetch the next page, pass `next_cursor` as `start`.
+    /// Expired assignments are left out.
+    pub fn list_members_by_role(
+        env: Env,
+        role: Role,
+        start: u32,
+        limit: u32,
+    ) -> RoleAssignmentPage {
+        let mut page = RoleAssignmentPage {
+            items: Vec::new(&env),
+            next_cursor: None,
+            has_more: false,
+        };
+        if limit == 0 {
+            return page;
+        }
+
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllMembers)
+            .unwrap_or(Vec::new(&env));
+
+        let mut skipped = 0_u32;
+        for member in members.iter() {
+            let assignment = match Self::active_assignment(&env, &member) {
+                Ok(assignment) if assignment.role == role => assignment,
+                _ => continue,
+            };
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+            // One match past the limit means another page exists
+            if page.items.len() >= limit {
+                page.next_cursor = Some(start as u64 + limit as u64);
+                page.has_more = true;
+                break;
+            }
+            page.items.push_back(assignment);
+        }
+
+        page
+    }
+
+    /// Get an audit log entry by ID (1 to `audit_count` in the summary).
+    pub fn get_audit_entry(env: Env, id: u64) -> Result<AuditEntry, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuditLog(id))
+            .ok_or(Error::AuditEntryNotFound)
+    }
+
     /// Get the access control summary.
     pub fn get_summary(env: Env) -> Result<AccessSummary, Error> {
         Self::require_initialized(&env)?;
@@ -444,6 +1085,12 @@ impl AccessControlContract {
             admin_count,
             member_count,
             viewer_count,
+            audit_count: env
+                .storage()
+                .instance()
+                .get(&DataKey::AuditCount)
+                .unwrap_or(0),
+            owner_threshold: Self::owner_threshold(&env),
         })
     }
 
@@ -451,14 +1098,132 @@ impl AccessControlContract {
     // Admin Functions
     // ========================================================================
 
+    /// Approve a sensitive action ahead of the owner who carries it out.
+    /// `upgrade`, `transfer_ownership`, `propose_ownership`,
+    /// `set_owner_threshold`, `set_governance` and granting the Owner role
+    /// need `owner_threshold` owners in total, counting the caller.
+    ///
+    /// # Returns
+    /// The number of owners that have approved the action.
+    ///
+    /// # Errors
+    /// * `Error::Unauthorized` - If the caller is not an owner.
+    /// * `Error::AlreadyApproved` - If the owner already approved the action.
+    pub fn approve_owner_action(
+        env: Env,
+        owner: Address,
+        action: OwnerAction,
+    ) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        let key = DataKey::OwnerApprovals(action.clone());
+        let mut approvals: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if approvals.contains(&owner) {
+            return Err(Error::AlreadyApproved);
+        }
+        approvals.push_back(owner.clone());
+        env.storage().persistent().set(&key, &approvals);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("own_appr"),
+                EVENT_VERSION,
+            ),
+            (owner, action, approvals.len()),
+        );
+
+        Ok(approvals.len())
+    }
+
+    /// Set how many owners must approve sensitive actions. Changing it is
+    /// itself a sensitive action approved under the current threshold.
+    ///
+    /// # Errors
+    /// * `Error::InvalidThreshold` - If threshold is 0 or exceeds the number
+    ///   of active owners.
+    /// * `Error::InsufficientApprovals` - If too few owners approved the change.
+    pub fn set_owner_threshold(env: Env, owner: Address, threshold: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+
+        if threshold == 0 || threshold > Self::active_owner_count(&env) {
+            return Err(Error::InvalidThreshold);
+        }
+        Self::consume_owner_approvals(&env, &owner, OwnerAction::SetOwnerThreshold(threshold))?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::OwnerThreshold, &threshold);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("own_thr"),
+                EVENT_VERSION,
+            ),
+            threshold,
+        );
+
+        Ok(())
+    }
+
+    /// Set the governance contract allowed to call `upgrade`. Because it
+    /// bypasses owner approvals afterwards, setting it is itself a sensitive
+    /// action approved under the owner threshold.
+    ///
+    /// # Errors
+    /// * `Error::InsufficientApprovals` - If too few owners approved the change.
+    pub fn set_governance(env: Env, owner: Address, governance: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(&env, &owner)?;
+        Self::consume_owner_approvals(
+            &env,
+            &owner,
+            OwnerAction::SetGovernance(governance.clone()),
+        )?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("gov"), EVENT_VERSION),
+            governance,
+        );
+
+        Ok(())
+    }
+
+    /// Get the governance contract allowed to call `upgrade`, if any.
+    pub fn get_governance(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Governance)
+    }
+
     /// Transfer ownership to a new address.
-    /// Only the current owner can transfer ownership.
+    /// Only the current owner can transfer ownership, with approval from
+    /// `owner_threshold` owners in total.
     pub fn transfer_ownership(
         env: Env,
         current_owner: Address,
         new_owner: Address,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         current_owner.require_auth();
 
@@ -471,149 +1236,308 @@ impl AccessControlContract {
         if current_owner != stored_owner {
             return Err(Error::Unauthorized);
         }
+        Self::consume_owner_approvals(
+            &env,
+            &current_owner,
+            OwnerAction::TransferOwnership(new_owner.clone()),
+        )?;
 
-        // Check if new owner already has a role before we change anything
-        let new_owner_had_role = env
-            .storage()
-            .persistent()
-            .has(&DataKey::Role(new_owner.clone()));
-        let old_new_owner_role = if new_owner_had_role {
-            let assignment: RoleAssignment = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Role(new_owner.clone()))
-                .unwrap();
-            Some(assignment.role)
-        } else {
-            None
-        };
-
-        // Update owner
-        env.storage().instance().set(&DataKey::Owner, &new_owner);
+        Self::internal_transfer_ownership(&env, &current_owner, &current_owner, &new_owner)?;
 
-        // Update role assignments
-        let new_owner_assignment = RoleAssignment {
-            address: new_owner.clone(),
-            role: Role::Owner,
-            assigned_at: env.ledger().timestamp(),
-            assigned_by: current_owner.clone(),
-        };
-        env.storage()
-            .persistent()
-            .set(&DataKey::Role(new_owner.clone()), &new_owner_assignment);
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("owner"), EVENT_VERSION),
+            (current_owner, new_owner.clone()),
+        );
 
-        // Demote old owner to admin
-        let old_owner_assignment = RoleAssignment {
-            address: current_owner.clone(),
-            role: Role::Admin,
-            assigned_at: env.ledger().timestamp(),
-            assigned_by: current_owner.clone(),
-        };
-        env.storage()
-            .persistent()
-            .set(&DataKey::Role(current_owner.clone()), &old_owner_assignment);
+        Ok(())
+    }
 
-        // Ensure new owner is in members list
-        let mut members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::AllMembers)
-            .unwrap_or(Vec::new(&env));
-        let mut found = false;
-        for i in 0..members.len() {
-            if members.get(i).unwrap() == new_owner {
-                found = true;
-                break;
-            }
-        }
-        if !found {
-            members.push_back(new_owner.clone());
-            env.storage().instance().set(&DataKey::AllMembers, &members);
-        }
+    /// Propose a new owner, who must call `accept_ownership` to take over.
+    /// The current owner keeps full control until then, and can replace the
+    /// pending owner by proposing again.
+    pub fn propose_ownership(
+        env: Env,
+        current_owner: Address,
+        new_owner: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
-        // Update role counts
-        // If new owner had a previous role, decrement that role's count
-        if let Some(old_role) = old_new_owner_role {
-            let old_role_val = old_role as u32;
-            let mut old_count: u32 = env
-                .storage()
-                .instance()
-                .get(&DataKey::RoleCount(old_role_val))
-                .unwrap_or(1);
-            if old_count > 0 {
-                old_count -= 1;
-            }
-            env.storage()
-                .instance()
-                .set(&DataKey::RoleCount(old_role_val), &old_count);
-        }
+        current_owner.require_auth();
 
-        // Decrement owner_count (old owner is no longer owner)
-        let mut owner_count: u32 = env
+        let stored_owner: Address = env
             .storage()
             .instance()
-            .get(&DataKey::RoleCount(Role::Owner as u32))
-            .unwrap_or(1);
-        if owner_count > 0 {
-            owner_count -= 1;
+            .get(&DataKey::Owner)
+            .ok_or(Error::NotInitialized)?;
+        if current_owner != stored_owner {
+            return Err(Error::Unauthorized);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::RoleCount(Role::Owner as u32), &owner_count);
+        Self::consume_owner_approvals(
+            &env,
+            &current_owner,
+            OwnerAction::TransferOwnership(new_owner.clone()),
+        )?;
 
-        // Increment owner_count (new owner is now owner)
-        let mut owner_count: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::RoleCount(Role::Owner as u32))
-            .unwrap_or(0);
-        owner_count += 1;
         env.storage()
             .instance()
-            .set(&DataKey::RoleCount(Role::Owner as u32), &owner_count);
+            .set(&DataKey::PendingOwner, &new_owner);
 
-        // Increment admin_count (old owner is now admin)
-        let mut admin_count: u32 = env
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("own_prop"),
+                EVENT_VERSION,
+            ),
+            (current_owner, new_owner),
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending ownership proposal. The caller becomes the owner and
+    /// the previous owner is demoted to Admin.
+    ///
+    /// # Errors
+    /// * `Error::Unauthorized` - If `new_owner` is not the pending owner.
+    pub fn accept_ownership(env: Env, new_owner: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+
+        let pending: Option<Address> = env.storage().instance().get(&DataKey::PendingOwner);
+        if pending != Some(new_owner.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        new_owner.require_auth();
+
+        let current_owner: Address = env
             .storage()
             .instance()
-            .get(&DataKey::RoleCount(Role::Admin as u32))
-            .unwrap_or(0);
-        admin_count += 1;
-        env.storage()
-            .instance()
-            .set(&DataKey::RoleCount(Role::Admin as u32), &admin_count);
+            .get(&DataKey::Owner)
+            .ok_or(Error::NotInitialized)?;
+        env.storage().instance().remove(&DataKey::PendingOwner);
+        Self::internal_transfer_ownership(&env, &new_owner, &current_owner, &new_owner)?;
 
         env.events().publish(
-            (symbol_short!("acl"), symbol_short!("owner")),
-            (current_owner, new_owner.clone()),
+            (symbol_short!("acl"), symbol_short!("owner"), EVENT_VERSION),
+            (current_owner, new_owner),
         );
 
         Ok(())
     }
 
-    /// Upgrade contract WASM. Owner only.
-    pub fn upgrade(
+    /// Get the owner proposed by `propose_ownership`, if any.
+    pub fn get_pending_owner(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingOwner)
+    }
+
+    // ========================================================================
+    // Owner Recovery
+    // ========================================================================
+
+    /// Set the guardians that can jointly recover ownership if the owner's
+    /// key is lost, and how many of them must agree. Clears any recovery in
+    /// progress. Owner only.
+    ///
+    /// # Errors
+    /// * `Error::InvalidThreshold` - If threshold is 0 or exceeds the guardian count.
+    pub fn set_guardians(
         env: Env,
         owner: Address,
-        new_wasm_hash: soroban_sdk::BytesN<32>,
+        guardians: Vec<Address>,
+        threshold: u32,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         owner.require_auth();
 
-        let stored_owner: Address = env
+        Self::require_owner(&env, &owner)?;
+
+        if threshold == 0 || threshold > guardians.len() {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Guardians, &guardians);
+        env.storage()
+            .instance()
+            .set(&DataKey::GuardianThreshold, &threshold);
+        env.storage().instance().remove(&DataKey::Recovery);
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("guardian"),
+                EVENT_VERSION,
+            ),
+            (guardians.len(), threshold),
+        );
+
+        Ok(())
+    }
+
+    /// Back recovering ownership to `new_owner`. Once the guardian threshold
+    /// of distinct guardians back the same `new_owner` within
+    /// `RECOVERY_WINDOW` seconds, ownership moves to `new_owner` and the old
+    /// owner loses its role. Backing a different address, or backing after
+    /// the window has passed, starts a new recovery. Works while paused.
+    ///
+    /// # Returns
+    /// `true` if ownership was transferred by this call.
+    ///
+    /// # Errors
+    /// * `Error::NotAGuardian` - If the caller is not a guardian.
+    /// * `Error::AlreadyApproved` - If the guardian already backed this recovery.
+    pub fn recover_owner(env: Env, guardian: Address, new_owner: Address) -> Result<bool, Error> {
+        Self::require_initialized(&env)?;
+
+        guardian.require_auth();
+
+        let guardians: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Guardians)
+            .unwrap_or(Vec::new(&env));
+        if !guardians.contains(&guardian) {
+            return Err(Error::NotAGuardian);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut request = match env
+            .storage()
+            .instance()
+            .get::<DataKey, RecoveryRequest>(&DataKey::Recovery)
+        {
+            Some(request)
+                if request.new_owner == new_owner
+                    && now < request.started_at.saturating_add(RECOVERY_WINDOW) =>
+            {
+                request
+            }
+            _ => RecoveryRequest {
+                new_owner: new_owner.clone(),
+                started_at: now,
+                approvals: Vec::new(&env),
+            },
+        };
+        if request.approvals.contains(&guardian) {
+            return Err(Error::AlreadyApproved);
+        }
+        request.approvals.push_back(guardian.clone());
+
+        env.events().publish(
+            (
+                symbol_short!("acl"),
+                symbol_short!("recover"),
+                EVENT_VERSION,
+            ),
+            (guardian.clone(), new_owner.clone(), request.approvals.len()),
+        );
+
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GuardianThreshold)
+            .unwrap_or(u32::MAX);
+        if request.approvals.len() < threshold {
+            env.storage().instance().set(&DataKey::Recovery, &request);
+            return Ok(false);
+        }
+
+        env.storage().instance().remove(&DataKey::Recovery);
+        let old_owner: Address = env
             .storage()
             .instance()
             .get(&DataKey::Owner)
             .ok_or(Error::NotInitialized)?;
-        if owner != stored_owner {
-            return Err(Error::Unauthorized);
+        Self::internal_transfer_ownership(&env, &guardian, &old_owner, &new_owner)?;
+        // The lost key should not keep the Admin role it was demoted to
+        if old_owner != new_owner {
+            Self::remove_assignment(&env, &old_owner, Role::Admin);
+            Self::record_audit(
+                &env,
+                symbol_short!("revoke"),
+                &guardian,
+                &old_owner,
+                Some(Role::Admin),
+                None,
+            );
+        }
+
+        env.events().publish(
+            (symbol_short!("acl"), symbol_short!("owner"), EVENT_VERSION),
+            (old_owner, new_owner),
+        );
+
+        Ok(true)
+    }
+
+    /// Get the guardians and the number needed to recover ownership.
+    pub fn get_guardians(env: Env) -> (Vec<Address>, u32) {
+        let guardians: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Guardians)
+            .unwrap_or(Vec::new(&env));
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GuardianThreshold)
+            .unwrap_or(0);
+        (guardians, threshold)
+    }
+
+    // ========================================================================
+    // Emergency Pause
+    // ========================================================================
+
+    /// Pause every role change, e.g. while a suspected key compromise is
+    /// investigated. Permission checks keep working. Owner only.
+    pub fn pause(env: Env, owner: Address) -> Result<(), Error> {
+        Self::set_paused(&env, &owner, true)
+    }
+
+    /// Resume role changes after a pause. Owner only.
+    pub fn unpause(env: Env, owner: Address) -> Result<(), Error> {
+        Self::set_paused(&env, &owner, false)
+    }
+
+    /// Check whether role changes are paused.
+    pub fn is_paused(env: Env) -> bool {
+        stellar_guard_pausable::is_paused(&env)
+    }
+
+    /// Upgrade contract WASM. Needs approval from `owner_threshold`
+    /// owners in total, counting the caller, unless the caller is the
+    /// governance contract set via `set_governance`.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        caller.require_auth();
+
+        // A passed governance proposal stands in for the owners' approval
+        let governance: Option<Address> = env.storage().instance().get(&DataKey::Governance);
+        if governance.as_ref() != Some(&caller) {
+            Self::require_owner(&env, &caller)?;
+            Self::consume_owner_approvals(
+                &env,
+                &caller,
+                OwnerAction::Upgrade(new_wasm_hash.clone()),
+            )?;
         }
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
 
+    /// Get the version of the deployed contract code.
+    pub fn get_version(_env: Env) -> u32 {
+        VERSION
+    }
+
     // ========================================================================
     // Internal Helpers
     // ========================================================================
@@ -625,326 +1549,1814 @@ impl AccessControlContract {
         Ok(())
     }
 
-    fn internal_get_role(env: &Env, address: &Address) -> Result<Role, Error> {
-        let assignment: RoleAssignment = env
+    fn internal_assign(
+        env: &Env,
+        assignor: &Address,
+        target: &Address,
+        role: Role,
+        custom_role: Option<Symbol>,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        let current: Option<RoleAssignment> = env
             .storage()
             .persistent()
-            .get(&DataKey::Role(address.clone()))
-            .ok_or(Error::RoleNotFound)?;
-        Ok(assignment.role)
-    }
-}
+            .get(&DataKey::Role(target.clone()));
+
+        let trusted_cap: Option<Role> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TrustedAssignor(assignor.clone()));
+        if let Some(max_role) = trusted_cap {
+            // Trusted assignors are bounded by their cap alone, for both the
+            // role granted and the role it replaces
+            let max_level = Self::role_level(env, max_role);
+            if Self::role_level(env, role) > max_level {
+                return Err(Error::InsufficientPrivilege);
+            }
+            if let Some(assignment) = &current {
+                if Self::role_level(env, assignment.role) > max_level {
+                    return Err(Error::InsufficientPrivilege);
+                }
+            }
+        } else {
+            Self::check_assignor_role(env, assignor, role)?;
+        }
+
+        if current.is_some_and(|assignment| assignment.role == Role::Owner) {
+            return Err(Error::CannotRemoveOwner);
+        }
+        if role == Role::Owner {
+            Self::consume_owner_approvals(env, assignor, OwnerAction::AssignOwner(target.clone()))?;
+        }
+
+        // Get current role count for the new role
+        let role_val = role as u32;
+        let mut count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoleCount(role_val))
+            .unwrap_or(0);
+
+        // If target already has a role, decrement old role count
+        let mut old_role = None;
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Role(target.clone()))
+        {
+            let old_assignment: RoleAssignment = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Role(target.clone()))
+                .unwrap();
+            let old_role_val = old_assignment.role as u32;
+            let old_count: u32 = env
+                .storage()
+                .instance()
+                .get::<DataKey, u32>(&DataKey::RoleCount(old_role_val))
+                .unwrap_or(1)
+                .saturating_sub(1);
+            env.storage()
+                .instance()
+                .set(&DataKey::RoleCount(old_role_val), &old_count);
+            old_role = Some(old_assignment.role);
+        } else {
+            // New member — add to the list
+            let mut members: Vec<Address> = env
+                .storage()
+                .instance()
+                .get(&DataKey::AllMembers)
+                .unwrap_or(Vec::new(env));
+            members.push_back(target.clone());
+            env.storage().instance().set(&DataKey::AllMembers, &members);
+        }
+
+        // Create assignment
+        let assignment = RoleAssignment {
+            address: target.clone(),
+            role,
+            custom_role,
+            assigned_at: env.ledger().timestamp(),
+            assigned_by: assignor.clone(),
+            expires_at,
+            suspended: false,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(target.clone()), &assignment);
+
+        // Update role count
+        count += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCount(role_val), &count);
+
+        Self::record_audit(
+            env,
+            symbol_short!("assign"),
+            assignor,
+            target,
+            old_role,
+            Some(role),
+        );
+
+        Self::require_owner_remains(env)
+    }
+
+    /// Require that an address holds the Owner role. Any owner qualifies,
+    /// not only the one recorded by `DataKey::Owner`.
+    fn require_owner(env: &Env, address: &Address) -> Result<(), Error> {
+        if Self::internal_get_role(env, address) != Ok(Role::Owner) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn owner_threshold(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OwnerThreshold)
+            .unwrap_or(1)
+    }
+
+    /// Number of owners whose assignment has not expired. `RoleCount` keeps
+    /// counting an expired owner until it is cleaned up.
+    fn active_owner_count(env: &Env) -> u32 {
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllMembers)
+            .unwrap_or(Vec::new(env));
+        let mut count = 0_u32;
+        for member in members.iter() {
+            if Self::require_owner(env, &member).is_ok() {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Check that `caller` plus the owners that approved `action` meet the
+    /// owner threshold, then clear the approvals. Approvals from addresses
+    /// that are no longer owners do not count, and the threshold is capped
+    /// at the number of active owners so expired ones cannot lock it.
+    fn consume_owner_approvals(
+        env: &Env,
+        caller: &Address,
+        action: OwnerAction,
+    ) -> Result<(), Error> {
+        let key = DataKey::OwnerApprovals(action);
+        let approvals: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        let mut count = 1_u32;
+        for approver in approvals.iter() {
+            if approver != *caller && Self::require_owner(env, &approver).is_ok() {
+                count += 1;
+            }
+        }
+        let required = Self::owner_threshold(env).min(Self::active_owner_count(env));
+        if count < required {
+            return Err(Error::InsufficientApprovals);
+        }
+
+        env.storage().persistent().remove(&key);
+        Ok(())
+    }
+
+    fn require_not_paused(env: &Env) -> Result<(), Error> {
+        stellar_guard_pausable::require_not_paused(env)
+    }
+
+    fn set_paused(env: &Env, owner: &Address, paused: bool) -> Result<(), Error> {
+        Self::require_initialized(env)?;
+
+        owner.require_auth();
+
+        Self::require_owner(env, owner)?;
+
+        stellar_guard_pausable::set_paused(env, symbol_short!("acl"), EVENT_VERSION, owner, paused);
+
+        Ok(())
+    }
+
+    /// Make `new_owner` the owner and demote `current_owner` to Admin,
+    /// keeping the member list and role counts in step.
+    fn internal_transfer_ownership(
+        env: &Env,
+        actor: &Address,
+        current_owner: &Address,
+        new_owner: &Address,
+    ) -> Result<(), Error> {
+        // Check if new owner already has a role before we change anything
+        let new_owner_had_role = env
+            .storage()
+            .persistent()
+            .has(&DataKey::Role(new_owner.clone()));
+        let old_new_owner_role = if new_owner_had_role {
+            let assignment: RoleAssignment = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Role(new_owner.clone()))
+                .unwrap();
+            Some(assignment.role)
+        } else {
+            None
+        };
+
+        // Update owner
+        env.storage().instance().set(&DataKey::Owner, new_owner);
+        env.storage().instance().remove(&DataKey::PendingOwner);
+
+        // Update role assignments
+        let new_owner_assignment = RoleAssignment {
+            address: new_owner.clone(),
+            role: Role::Owner,
+            custom_role: None,
+            assigned_at: env.ledger().timestamp(),
+            assigned_by: current_owner.clone(),
+            expires_at: 0,
+            suspended: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(new_owner.clone()), &new_owner_assignment);
+
+        // Demote old owner to admin
+        let old_owner_assignment = RoleAssignment {
+            address: current_owner.clone(),
+            role: Role::Admin,
+            custom_role: None,
+            assigned_at: env.ledger().timestamp(),
+            assigned_by: current_owner.clone(),
+            expires_at: 0,
+            suspended: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(current_owner.clone()), &old_owner_assignment);
+
+        // Ensure new owner is in members list
+        let mut members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllMembers)
+            .unwrap_or(Vec::new(env));
+        let mut found = false;
+        for i in 0..members.len() {
+            if members.get(i).unwrap() == *new_owner {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            members.push_back(new_owner.clone());
+            env.storage().instance().set(&DataKey::AllMembers, &members);
+        }
+
+        // Update role counts
+        // If new owner had a previous role, decrement that role's count
+        if let Some(old_role) = old_new_owner_role {
+            let old_role_val = old_role as u32;
+            let old_count: u32 = env
+                .storage()
+                .instance()
+                .get::<DataKey, u32>(&DataKey::RoleCount(old_role_val))
+                .unwrap_or(1)
+                .saturating_sub(1);
+            env.storage()
+                .instance()
+                .set(&DataKey::RoleCount(old_role_val), &old_count);
+        }
+
+        // Decrement owner_count (old owner is no longer owner)
+        let owner_count: u32 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u32>(&DataKey::RoleCount(Role::Owner as u32))
+            .unwrap_or(1)
+            .saturating_sub(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCount(Role::Owner as u32), &owner_count);
+
+        // Increment owner_count (new owner is now owner)
+        let mut owner_count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoleCount(Role::Owner as u32))
+            .unwrap_or(0);
+        owner_count += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCount(Role::Owner as u32), &owner_count);
+
+        // Increment admin_count (old owner is now admin)
+        let mut admin_count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoleCount(Role::Admin as u32))
+            .unwrap_or(0);
+        admin_count += 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCount(Role::Admin as u32), &admin_count);
+
+        Self::record_audit(
+            env,
+            symbol_short!("owner"),
+            actor,
+            new_owner,
+            old_new_owner_role,
+            Some(Role::Owner),
+        );
+        Self::record_audit(
+            env,
+            symbol_short!("owner"),
+            actor,
+            current_owner,
+            Some(Role::Owner),
+            Some(Role::Admin),
+        );
+
+        Self::require_owner_remains(env)
+    }
+
+    /// Invariant checked after every role change: at least one active Owner
+    /// exists, enough to meet the owner threshold, and the recorded owner still
+    /// holds the Owner role, so the contract can never be orphaned.
+    /// Returning an error reverts the change.
+    fn require_owner_remains(env: &Env) -> Result<(), Error> {
+        let owner_count = Self::active_owner_count(env);
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Owner)
+            .ok_or(Error::NotInitialized)?;
+        let owner_role = env
+            .storage()
+            .persistent()
+            .get::<DataKey, RoleAssignment>(&DataKey::Role(owner))
+            .map(|assignment| assignment.role);
+        if owner_count == 0
+            || owner_count < Self::owner_threshold(env)
+            || owner_role != Some(Role::Owner)
+        {
+            return Err(Error::CannotRemoveOwner);
+        }
+        Ok(())
+    }
+
+    /// Append an entry to the audit log.
+    fn record_audit(
+        env: &Env,
+        action: Symbol,
+        actor: &Address,
+        subject: &Address,
+        old_role: Option<Role>,
+        new_role: Option<Role>,
+    ) {
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::AuditCount)
+            .unwrap_or(0)
+            + 1;
+        let entry = AuditEntry {
+            id,
+            action,
+            actor: actor.clone(),
+            subject: subject.clone(),
+            old_role: old_role.map_or(0, |role| role as u32),
+            new_role: new_role.map_or(0, |role| role as u32),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::AuditLog(id), &entry);
+        env.storage().instance().set(&DataKey::AuditCount, &id);
+    }
+
+    fn set_suspended(
+        env: &Env,
+        admin: &Address,
+        target: &Address,
+        suspended: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(env)?;
+        Self::require_not_paused(env)?;
+
+        admin.require_auth();
+
+        let admin_role = Self::internal_get_role(env, admin)?;
+        if admin_role != Role::Owner && admin_role != Role::Admin {
+            return Err(Error::InsufficientPrivilege);
+        }
+
+        let mut assignment: RoleAssignment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Role(target.clone()))
+            .ok_or(Error::RoleNotFound)?;
+        if assignment.role == Role::Owner {
+            return Err(Error::CannotRemoveOwner);
+        }
+        if assignment.role == Role::Admin && admin_role != Role::Owner {
+            return Err(Error::InsufficientPrivilege);
+        }
+
+        assignment.suspended = suspended;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(target.clone()), &assignment);
+
+        let action = if suspended {
+            symbol_short!("suspend")
+        } else {
+            symbol_short!("unsuspend")
+        };
+        env.events().publish(
+            (symbol_short!("acl"), action, EVENT_VERSION),
+            (target.clone(), admin.clone()),
+        );
+
+        Ok(())
+    }
+
+    fn remove_assignment(env: &Env, target: &Address, role: Role) {
+        // Decrement role count
+        let role_val = role as u32;
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u32>(&DataKey::RoleCount(role_val))
+            .unwrap_or(1)
+            .saturating_sub(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoleCount(role_val), &count);
+
+        // Remove role assignment
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Role(target.clone()));
+
+        // Remove from members list
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllMembers)
+            .unwrap_or(Vec::new(env));
+        let mut new_members = Vec::new(env);
+        for i in 0..members.len() {
+            let m = members.get(i).unwrap();
+            if m != *target {
+                new_members.push_back(m);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AllMembers, &new_members);
+    }
+
+    fn is_expired(env: &Env, assignment: &RoleAssignment) -> bool {
+        assignment.expires_at != 0 && env.ledger().timestamp() >= assignment.expires_at
+    }
+
+    /// The address's role assignment, treating an expired or suspended one
+    /// as absent.
+    fn active_assignment(env: &Env, address: &Address) -> Result<RoleAssignment, Error> {
+        let assignment: RoleAssignment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Role(address.clone()))
+            .ok_or(Error::RoleNotFound)?;
+        if assignment.suspended || Self::is_expired(env, &assignment) {
+            return Err(Error::RoleNotFound);
+        }
+        Ok(assignment)
+    }
+
+    /// Check that a role holder may grant `role`.
+    fn check_assignor_role(env: &Env, assignor: &Address, role: Role) -> Result<(), Error> {
+        // Get assignor's role
+        let assignor_role = Self::internal_get_role(env, assignor)?;
+
+        // Only Owner can assign Owner or Admin roles
+        match role {
+            Role::Owner | Role::Admin => {
+                if assignor_role != Role::Owner {
+                    return Err(Error::InsufficientPrivilege);
+                }
+            }
+            Role::Member | Role::Viewer => {
+                // Admin or Owner can assign these
+                if assignor_role != Role::Owner && assignor_role != Role::Admin {
+                    return Err(Error::InsufficientPrivilege);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn internal_get_role(env: &Env, address: &Address) -> Result<Role, Error> {
+        Ok(Self::active_assignment(env, address)?.role)
+    }
+
+    /// Numeric level of an address's role, honouring custom roles.
+    fn internal_get_level(env: &Env, address: &Address) -> Result<u32, Error> {
+        let assignment = Self::active_assignment(env, address)?;
+        match assignment.custom_role {
+            Some(name) => env
+                .storage()
+                .instance()
+                .get(&DataKey::CustomRole(name))
+                .ok_or(Error::RoleNotFound),
+            None => Ok(Self::role_level(env, assignment.role)),
+        }
+    }
+
+    /// Level of a built-in role, as set by `set_role_level` or the default.
+    fn role_level(env: &Env, role: Role) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoleLevel(role as u32))
+            .unwrap_or(role as u32 * ROLE_LEVEL_STEP)
+    }
+
+    /// The highest-ranked built-in role at or below a level, or Viewer if
+    /// none is.
+    fn base_role(env: &Env, level: u32) -> Role {
+        let mut base = Role::Viewer;
+        let mut base_level = 0;
+        for role in [Role::Viewer, Role::Member, Role::Admin, Role::Owner] {
+            let role_level = Self::role_level(env, role);
+            if role_level <= level && role_level >= base_level {
+                base = role;
+                base_level = role_level;
+            }
+        }
+        base
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+    use soroban_sdk::{vec, Env, IntoVal, TryFromVal};
+
+    fn setup_contract() -> (Env, Address, AccessControlContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, AccessControlContract);
+        let client = AccessControlContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        (env, owner, client)
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (_env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let summary = client.get_summary();
+        assert_eq!(summary.owner, owner);
+        assert_eq!(summary.total_members, 1);
+        assert_eq!(summary.owner_count, 1);
+    }
+
+    #[test]
+    fn test_assign_roles() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        let viewer = Address::generate(&env);
+
+        // Owner assigns Admin
+        client.assign_role(&owner, &admin, &Role::Admin);
+        // Owner assigns Member
+        client.assign_role(&owner, &member, &Role::Member);
+        // Admin assigns Viewer
+        client.assign_role(&admin, &viewer, &Role::Viewer);
+
+        let summary = client.get_summary();
+        assert_eq!(summary.total_members, 4);
+        assert_eq!(summary.admin_count, 1);
+        assert_eq!(summary.member_count, 1);
+        assert_eq!(summary.viewer_count, 1);
+    }
+
+    #[test]
+    fn test_permission_checks() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let member = Address::generate(&env);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        assert_eq!(client.is_owner(&owner), true);
+        assert_eq!(client.is_owner(&member), false);
+        assert_eq!(client.is_member_or_above(&member), true);
+        assert_eq!(client.is_admin_or_above(&member), false);
+        assert_eq!(client.has_permission(&member, &Role::Viewer), true);
+        assert_eq!(client.has_permission(&member, &Role::Admin), false);
+    }
+
+    #[test]
+    fn test_revoke_role() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let member = Address::generate(&env);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        assert_eq!(client.is_member_or_above(&member), true);
+
+        client.revoke_role(&owner, &member);
+
+        assert_eq!(client.is_member_or_above(&member), false);
+    }
+
+    #[test]
+    fn test_assign_role_privilege_escalation() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        let target = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        let result = client.try_assign_role(&admin, &target, &Role::Admin);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+
+        let result = client.try_assign_role(&admin, &target, &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+
+        let result = client.try_assign_role(&member, &target, &Role::Viewer);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+    }
+
+    #[test]
+    fn test_revoke_role_cannot_remove_owner() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let result = client.try_revoke_role(&owner, &owner);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+
+        let admin = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+
+        let result = client.try_revoke_role(&admin, &owner);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+    }
+
+    #[test]
+    fn test_revoke_admin_requires_owner() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin1 = Address::generate(&env);
+        let admin2 = Address::generate(&env);
+
+        client.assign_role(&owner, &admin1, &Role::Admin);
+        client.assign_role(&owner, &admin2, &Role::Admin);
+
+        let result = client.try_revoke_role(&admin1, &admin2);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+    }
+
+    #[test]
+    fn test_has_permission() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let viewer = Address::generate(&env);
+        let member = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let random_addr = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+        client.assign_role(&admin, &viewer, &Role::Viewer);
+
+        assert_eq!(client.has_permission(&owner, &Role::Owner), true);
+        assert_eq!(client.has_permission(&owner, &Role::Admin), true);
+        assert_eq!(client.has_permission(&owner, &Role::Member), true);
+        assert_eq!(client.has_permission(&owner, &Role::Viewer), true);
+
+        assert_eq!(client.has_permission(&admin, &Role::Owner), false);
+        assert_eq!(client.has_permission(&admin, &Role::Admin), true);
+        assert_eq!(client.has_permission(&admin, &Role::Member), true);
+
+        assert_eq!(client.has_permission(&member, &Role::Admin), false);
+        assert_eq!(client.has_permission(&member, &Role::Member), true);
+        assert_eq!(client.has_permission(&member, &Role::Viewer), true);
+
+        assert_eq!(client.has_permission(&viewer, &Role::Member), false);
+        assert_eq!(client.has_permission(&viewer, &Role::Viewer), true);
+
+        assert_eq!(client.has_permission(&random_addr, &Role::Viewer), false);
+    }
+
+    #[test]
+    fn test_is_owner() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        let random_addr = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        assert_eq!(client.is_owner(&owner), true);
+        assert_eq!(client.is_owner(&admin), false);
+        assert_eq!(client.is_owner(&member), false);
+        assert_eq!(client.is_owner(&random_addr), false);
+    }
+
+    #[test]
+    fn test_is_admin_or_above() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        let viewer = Address::generate(&env);
+        let random_addr = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+        client.assign_role(&admin, &viewer, &Role::Viewer);
+
+        assert_eq!(client.is_admin_or_above(&owner), true);
+        assert_eq!(client.is_admin_or_above(&admin), true);
+        assert_eq!(client.is_admin_or_above(&member), false);
+        assert_eq!(client.is_admin_or_above(&viewer), false);
+        assert_eq!(client.is_admin_or_above(&random_addr), false);
+    }
+
+    #[test]
+    fn test_is_member_or_above() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        let viewer = Address::generate(&env);
+        let random_addr = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+        client.assign_role(&admin, &viewer, &Role::Viewer);
+
+        assert_eq!(client.is_member_or_above(&owner), true);
+        assert_eq!(client.is_member_or_above(&admin), true);
+        assert_eq!(client.is_member_or_above(&member), true);
+        assert_eq!(client.is_member_or_above(&viewer), false);
+        assert_eq!(client.is_member_or_above(&random_addr), false);
+    }
+
+    #[test]
+    fn test_transfer_ownership() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let new_owner = Address::generate(&env);
+
+        client.transfer_ownership(&owner, &new_owner);
+
+        let summary = client.get_summary();
+        assert_eq!(summary.owner, new_owner);
+
+        let old_owner_role = client.get_role(&owner);
+        assert_eq!(old_owner_role.role, Role::Admin);
+
+        let new_owner_role = client.get_role(&new_owner);
+        assert_eq!(new_owner_role.role, Role::Owner);
+
+        assert_eq!(client.is_owner(&new_owner), true);
+        assert_eq!(client.is_owner(&owner), false);
+        assert_eq!(client.is_admin_or_above(&owner), true);
+    }
+
+    #[test]
+    fn test_transfer_ownership_role_counts() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        // Before transfer: owner_count = 1, admin_count = 0
+        let summary_before = client.get_summary();
+        assert_eq!(summary_before.owner_count, 1);
+        assert_eq!(summary_before.admin_count, 0);
+
+        let new_owner = Address::generate(&env);
+
+        client.transfer_ownership(&owner, &new_owner);
+
+        // After transfer: owner_count should be 1 (new owner), admin_count should be 1 (old owner)
+        let summary_after = client.get_summary();
+        assert_eq!(
+            summary_after.owner_count, 1,
+            "owner_count should be 1 after transfer"
+        );
+        assert_eq!(
+            summary_after.admin_count, 1,
+            "admin_count should be 1 after transfer"
+        );
+        assert_eq!(summary_after.total_members, 2, "total_members should be 2");
+    }
+
+    #[test]
+    fn test_transfer_ownership_unauthorized() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+
+        client.assign_role(&owner, &admin, &Role::Admin);
+
+        let result = client.try_transfer_ownership(&admin, &new_owner);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_initialize_sets_owner_role() {
+        let (_env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let role_assignment = client.get_role(&owner);
+        assert_eq!(role_assignment.role, Role::Owner);
+        assert_eq!(role_assignment.address, owner);
+        assert_eq!(role_assignment.assigned_by, owner);
+    }
+
+    #[test]
+    fn test_custom_role_between_member_and_admin() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let moderator_role = Symbol::new(&env, "moderator");
+        client.define_role(&owner, &moderator_role, &250);
+        assert_eq!(client.get_role_level(&moderator_role), 250);
+
+        let moderator = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.assign_custom_role(&owner, &moderator, &moderator_role);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        assert!(client.has_permission(&moderator, &Role::Member));
+        assert!(!client.has_permission(&moderator, &Role::Admin));
+        assert!(client.has_custom_permission(&moderator, &moderator_role));
+        assert!(!client.has_custom_permission(&member, &moderator_role));
+        assert!(client.has_custom_permission(&owner, &moderator_role));
+        assert!(client.has_level(&moderator, &250));
+        assert!(!client.has_level(&moderator, &251));
+
+        let assignment = client.get_role(&moderator);
+        assert_eq!(assignment.role, Role::Member);
+        assert_eq!(assignment.custom_role, Some(moderator_role));
+        assert_eq!(client.get_summary().member_count, 2);
+    }
+
+    #[test]
+    fn test_define_role_validation() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+
+        let auditor_role = Symbol::new(&env, "auditor");
+        let result = client.try_define_role(&admin, &auditor_role, &150);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_define_role(&owner, &auditor_role, &400);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+        let result = client.try_define_role(&owner, &auditor_role, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+
+        client.define_role(&owner, &auditor_role, &350);
+        let result = client.try_define_role(&owner, &auditor_role, &150);
+        assert_eq!(result, Err(Ok(Error::RoleAlreadyDefined)));
+
+        // Only the owner can grant a custom role at or above Admin level
+        let auditor = Address::generate(&env);
+        let result = client.try_assign_custom_role(&admin, &auditor, &auditor_role);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        let result = client.try_assign_custom_role(&owner, &auditor, &Symbol::new(&env, "ghost"));
+        assert_eq!(result, Err(Ok(Error::RoleNotFound)));
+    }
+
+    #[test]
+    fn test_temp_admin_expires() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        env.ledger().set_timestamp(1_000);
+        let contractor = Address::generate(&env);
+        client.assign_role_temp(&owner, &contractor, &Role::Admin, &3_600);
+        assert_eq!(client.get_role(&contractor).expires_at, 4_600);
+
+        assert!(client.is_admin_or_above(&contractor));
+        assert!(client.has_permission(&contractor, &Role::Admin));
+        let result = client.try_cleanup_expired(&contractor);
+        assert_eq!(result, Err(Ok(Error::RoleNotExpired)));
+
+        env.ledger().set_timestamp(4_600);
+        assert!(!client.is_admin_or_above(&contractor));
+        assert!(!client.has_permission(&contractor, &Role::Viewer));
+
+        // An expired admin can no longer assign roles
+        let result = client.try_assign_role(&contractor, &Address::generate(&env), &Role::Viewer);
+        assert_eq!(result, Err(Ok(Error::RoleNotFound)));
+
+        client.cleanup_expired(&contractor);
+        assert_eq!(
+            client.try_get_role(&contractor),
+            Err(Ok(Error::RoleNotFound))
+        );
+        let summary = client.get_summary();
+        assert_eq!(summary.admin_count, 0);
+        assert_eq!(summary.total_members, 1);
+    }
+
+    #[test]
+    fn test_assign_role_temp_validation() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let target = Address::generate(&env);
+        let result = client.try_assign_role_temp(&owner, &target, &Role::Member, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidDuration)));
+
+        // Permanent assignments never expire
+        client.assign_role(&owner, &target, &Role::Member);
+        let result = client.try_cleanup_expired(&target);
+        assert_eq!(result, Err(Ok(Error::RoleNotExpired)));
+    }
+
+    #[test]
+    fn test_assign_roles_batch() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let viewer = Address::generate(&env);
+        client.assign_roles_batch(
+            &owner,
+            &vec![
+                &env,
+                (admin.clone(), Role::Admin),
+                (member1.clone(), Role::Member),
+                (member2.clone(), Role::Member),
+                (viewer.clone(), Role::Viewer),
+            ],
+        );
+
+        let summary = client.get_summary();
+        assert_eq!(summary.total_members, 5);
+        assert_eq!(summary.owner_count, 1);
+        assert_eq!(summary.admin_count, 1);
+        assert_eq!(summary.member_count, 2);
+        assert_eq!(summary.viewer_count, 1);
+        assert_eq!(
+            client.get_all_members(),
+            vec![&env, owner, admin, member1, member2, viewer]
+        );
+    }
+
+    #[test]
+    fn test_assign_roles_batch_is_all_or_nothing() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+
+        // An admin cannot grant Admin, so the whole batch is rejected
+        let member = Address::generate(&env);
+        let result = client.try_assign_roles_batch(
+            &admin,
+            &vec![
+                &env,
+                (member.clone(), Role::Member),
+                (Address::generate(&env), Role::Admin),
+            ],
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        assert!(!client.is_member_or_above(&member));
+        assert_eq!(client.get_summary().total_members, 2);
+
+        let mut oversized = Vec::new(&env);
+        for _ in 0..=MAX_BATCH_SIZE {
+            oversized.push_back((Address::generate(&env), Role::Viewer));
+        }
+        let result = client.try_assign_roles_batch(&owner, &oversized);
+        assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
+    }
+
+    #[test]
+    fn test_list_members_by_role() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member1 = Address::generate(&env);
+        let viewer = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member1, &Role::Member);
+        client.assign_role(&owner, &viewer, &Role::Viewer);
+        client.assign_role(&owner, &member2, &Role::Member);
+        client.assign_role(&owner, &member3, &Role::Member);
+
+        let admins = client.list_members_by_role(&Role::Admin, &0, &10);
+        assert_eq!(admins.items.len(), 1);
+        assert_eq!(admins.items.get(0).unwrap().address, admin);
+        assert!(!admins.has_more);
+
+        let page = client.list_members_by_role(&Role::Member, &0, &2);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items.get(0).unwrap().address, member1);
+        assert_eq!(page.items.get(1).unwrap().address, member2);
+        assert!(page.has_more);
+        assert_eq!(page.next_cursor, Some(2));
+
+        let page = client.list_members_by_role(&Role::Member, &2, &2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items.get(0).unwrap().address, member3);
+        assert!(!page.has_more);
+        assert_eq!(page.next_cursor, None);
+
+        // A page that exactly fills the limit is the last one
+        assert!(!client.list_members_by_role(&Role::Member, &0, &3).has_more);
+
+        let page = client.list_members_by_role(&Role::Member, &3, &2);
+        assert_eq!(page.items.len(), 0);
+        let page = client.list_members_by_role(&Role::Member, &0, &0);
+        assert_eq!(page.items.len(), 0);
+        let owners = client.list_members_by_role(&Role::Owner, &0, &10);
+        assert_eq!(owners.items.len(), 1);
+    }
+
+    #[test]
+    fn test_pause_blocks_role_changes() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        let result = client.try_pause(&admin);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.pause(&owner);
+        assert!(client.is_paused());
+
+        let result = client.try_assign_role(&owner, &Address::generate(&env), &Role::Viewer);
+        assert_eq!(result, Err(Ok(Error::Paused)));
+        let result = client.try_revoke_role(&owner, &member);
+        assert_eq!(result, Err(Ok(Error::Paused)));
+        let result = client.try_transfer_ownership(&owner, &admin);
+        assert_eq!(result, Err(Ok(Error::Paused)));
+
+        // Reads keep working for dependent contracts
+        assert!(client.has_permission(&admin, &Role::Admin));
+        assert!(client.is_member_or_above(&member));
+        assert!(client.is_owner(&owner));
+        assert_eq!(client.get_summary().total_members, 3);
+
+        client.unpause(&owner);
+        assert!(!client.is_paused());
+        client.revoke_role(&owner, &member);
+        assert!(!client.is_member_or_above(&member));
+    }
+
+    #[test]
+    fn test_guardian_recovery() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        let guardian1 = Address::generate(&env);
+        let guardian2 = Address::generate(&env);
+        let guardian3 = Address::generate(&env);
+        client.set_guardians(
+            &owner,
+            &vec![
+                &env,
+                guardian1.clone(),
+                guardian2.clone(),
+                guardian3.clone(),
+            ],
+            &2,
+        );
+
+        let new_owner = Address::generate(&env);
+        let result = client.try_recover_owner(&new_owner, &new_owner);
+        assert_eq!(result, Err(Ok(Error::NotAGuardian)));
+
+        // One guardian is not enough
+        assert!(!client.recover_owner(&guardian1, &new_owner));
+        let result = client.try_recover_owner(&guardian1, &new_owner);
+        assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+        assert!(client.is_owner(&owner));
+
+        assert!(client.recover_owner(&guardian2, &new_owner));
+        assert!(client.is_owner(&new_owner));
+        assert!(!client.has_permission(&owner, &Role::Viewer));
+        let summary = client.get_summary();
+        assert_eq!(summary.owner, new_owner);
+        assert_eq!(summary.owner_count, 1);
+        assert_eq!(summary.admin_count, 0);
+        assert_eq!(summary.total_members, 1);
+    }
+
+    #[test]
+    fn test_guardian_recovery_resets() {
+        let (env, owner, client) = setup_contract();
 
-// ============================================================================
-// Tests
-// ============================================================================
+        client.initialize(&owner);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+        let guardian1 = Address::generate(&env);
+        let guardian2 = Address::generate(&env);
+        let result = client.try_set_guardians(&owner, &vec![&env, guardian1.clone()], &2);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+        client.set_guardians(
+            &owner,
+            &vec![&env, guardian1.clone(), guardian2.clone()],
+            &2,
+        );
 
-    fn setup_contract() -> (Env, Address, AccessControlContractClient<'static>) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, AccessControlContract);
-        let client = AccessControlContractClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-        (env, owner, client)
+        // Guardians backing different addresses do not add up
+        let candidate1 = Address::generate(&env);
+        let candidate2 = Address::generate(&env);
+        client.recover_owner(&guardian1, &candidate1);
+        assert!(!client.recover_owner(&guardian2, &candidate2));
+        assert!(client.is_owner(&owner));
+
+        // Backing that arrives after the window starts over
+        env.ledger().set_timestamp(RECOVERY_WINDOW);
+        assert!(!client.recover_owner(&guardian1, &candidate2));
+        assert!(client.is_owner(&owner));
+        assert!(client.recover_owner(&guardian2, &candidate2));
+        assert!(client.is_owner(&candidate2));
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_two_step_ownership_transfer() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
+        let new_owner = Address::generate(&env);
+        client.propose_ownership(&owner, &new_owner);
+        assert_eq!(client.get_pending_owner(), Some(new_owner.clone()));
+
+        // The current owner keeps full control until the handoff is accepted
+        assert!(client.is_owner(&owner));
+        assert!(!client.has_permission(&new_owner, &Role::Viewer));
+        client.assign_role(&owner, &Address::generate(&env), &Role::Admin);
+
+        client.accept_ownership(&new_owner);
+        assert!(client.is_owner(&new_owner));
+        assert!(!client.is_owner(&owner));
+        assert!(client.is_admin_or_above(&owner));
+        assert_eq!(client.get_pending_owner(), None);
         let summary = client.get_summary();
-        assert_eq!(summary.owner, owner);
-        assert_eq!(summary.total_members, 1);
+        assert_eq!(summary.owner, new_owner);
         assert_eq!(summary.owner_count, 1);
+        assert_eq!(summary.admin_count, 2);
     }
 
     #[test]
-    fn test_assign_roles() {
+    fn test_accept_ownership_requires_pending_owner() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let admin = Address::generate(&env);
-        let member = Address::generate(&env);
-        let viewer = Address::generate(&env);
-
-        // Owner assigns Admin
-        client.assign_role(&owner, &admin, &Role::Admin);
-        // Owner assigns Member
-        client.assign_role(&owner, &member, &Role::Member);
-        // Admin assigns Viewer
-        client.assign_role(&admin, &viewer, &Role::Viewer);
+        let intended = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let result = client.try_accept_ownership(&intended);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        let summary = client.get_summary();
-        assert_eq!(summary.total_members, 4);
-        assert_eq!(summary.admin_count, 1);
-        assert_eq!(summary.member_count, 1);
-        assert_eq!(summary.viewer_count, 1);
+        client.propose_ownership(&owner, &intended);
+        let result = client.try_accept_ownership(&stranger);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_propose_ownership(&stranger, &stranger);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert!(client.is_owner(&owner));
     }
 
     #[test]
-    fn test_permission_checks() {
+    fn test_capability_without_role() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let member = Address::generate(&env);
-        client.assign_role(&owner, &member, &Role::Member);
+        let auditor = Address::generate(&env);
+        let read_all = Symbol::new(&env, "read_all");
+        let write = Symbol::new(&env, "write");
+        client.grant_capability(&owner, &auditor, &read_all);
 
-        assert_eq!(client.is_owner(&owner), true);
-        assert_eq!(client.is_owner(&member), false);
-        assert_eq!(client.is_member_or_above(&member), true);
-        assert_eq!(client.is_admin_or_above(&member), false);
-        assert_eq!(client.has_permission(&member, &Role::Viewer), true);
-        assert_eq!(client.has_permission(&member, &Role::Admin), false);
+        assert!(client.has_capability(&auditor, &read_all));
+        assert!(!client.has_capability(&auditor, &write));
+        assert!(!client.has_permission(&auditor, &Role::Viewer));
+        assert_eq!(client.try_get_role(&auditor), Err(Ok(Error::RoleNotFound)));
+
+        client.revoke_capability(&owner, &auditor, &read_all);
+        assert!(!client.has_capability(&auditor, &read_all));
     }
 
     #[test]
-    fn test_revoke_role() {
+    fn test_role_capability_bundles() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
+        let admin = Address::generate(&env);
         let member = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
         client.assign_role(&owner, &member, &Role::Member);
 
-        assert_eq!(client.is_member_or_above(&member), true);
+        let write = Symbol::new(&env, "write");
+        let vote = Symbol::new(&env, "vote");
+        client.set_role_capabilities(&owner, &Role::Member, &vec![&env, vote.clone()]);
 
-        client.revoke_role(&owner, &member);
+        assert!(client.has_capability(&member, &vote));
+        assert!(!client.has_capability(&member, &write));
+        assert!(!client.has_capability(&admin, &vote));
 
-        assert_eq!(client.is_member_or_above(&member), false);
+        // Only the owner manages capabilities
+        let result = client.try_grant_capability(&admin, &member, &write);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.revoke_role(&owner, &member);
+        assert!(!client.has_capability(&member, &vote));
     }
 
     #[test]
-    fn test_assign_role_privilege_escalation() {
+    fn test_suspend_and_unsuspend() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
         let admin = Address::generate(&env);
         let member = Address::generate(&env);
-        let target = Address::generate(&env);
-
         client.assign_role(&owner, &admin, &Role::Admin);
         client.assign_role(&owner, &member, &Role::Member);
 
-        let result = client.try_assign_role(&admin, &target, &Role::Admin);
-        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        client.suspend(&admin, &member);
+        assert!(!client.has_permission(&member, &Role::Viewer));
+        assert!(!client.is_member_or_above(&member));
+        let assignment = client.get_role(&member);
+        assert!(assignment.suspended);
+        assert_eq!(assignment.role, Role::Member);
+        assert_eq!(client.get_summary().member_count, 1);
+
+        client.unsuspend(&admin, &member);
+        assert!(client.has_permission(&member, &Role::Member));
+        assert!(!client.get_role(&member).suspended);
+    }
 
-        let result = client.try_assign_role(&admin, &target, &Role::Owner);
-        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+    #[test]
+    fn test_suspend_privileges() {
+        let (env, owner, client) = setup_contract();
 
-        let result = client.try_assign_role(&member, &target, &Role::Viewer);
+        client.initialize(&owner);
+
+        let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.assign_role(&owner, &admin, &Role::Admin);
+        client.assign_role(&owner, &member, &Role::Member);
+
+        let result = client.try_suspend(&member, &admin);
         assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        let result = client.try_suspend(&admin, &owner);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+
+        // A suspended admin loses its own privileges
+        client.suspend(&owner, &admin);
+        assert!(!client.is_admin_or_above(&admin));
+        let result = client.try_suspend(&admin, &member);
+        assert_eq!(result, Err(Ok(Error::RoleNotFound)));
+        let cap = Symbol::new(&env, "read_all");
+        client.grant_capability(&owner, &admin, &cap);
+        assert!(!client.has_capability(&admin, &cap));
     }
 
     #[test]
-    fn test_revoke_role_cannot_remove_owner() {
+    fn test_audit_log_records_role_changes() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let result = client.try_revoke_role(&owner, &owner);
-        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
-
         let admin = Address::generate(&env);
+        let member = Address::generate(&env);
+        env.ledger().set_timestamp(100);
         client.assign_role(&owner, &admin, &Role::Admin);
+        env.ledger().set_timestamp(200);
+        client.assign_role(&admin, &member, &Role::Viewer);
+        client.assign_role(&admin, &member, &Role::Member);
+        env.ledger().set_timestamp(300);
+        client.revoke_role(&admin, &member);
+        client.transfer_ownership(&owner, &admin);
+
+        assert_eq!(client.get_summary().audit_count, 6);
+        let expected = [
+            ("assign", &owner, &admin, None, Some(Role::Admin), 100),
+            ("assign", &admin, &member, None, Some(Role::Viewer), 200),
+            (
+                "assign",
+                &admin,
+                &member,
+                Some(Role::Viewer),
+                Some(Role::Member),
+                200,
+            ),
+            ("revoke", &admin, &member, Some(Role::Member), None, 300),
+            (
+                "owner",
+                &owner,
+                &admin,
+                Some(Role::Admin),
+                Some(Role::Owner),
+                300,
+            ),
+            (
+                "owner",
+                &owner,
+                &owner,
+                Some(Role::Owner),
+                Some(Role::Admin),
+                300,
+            ),
+        ];
+        for (i, (action, actor, subject, old_role, new_role, timestamp)) in
+            expected.into_iter().enumerate()
+        {
+            let id = i as u64 + 1;
+            assert_eq!(
+                client.get_audit_entry(&id),
+                AuditEntry {
+                    id,
+                    action: Symbol::new(&env, action),
+                    actor: actor.clone(),
+                    subject: subject.clone(),
+                    old_role: old_role.map_or(0, |role: Role| role as u32),
+                    new_role: new_role.map_or(0, |role: Role| role as u32),
+                    timestamp,
+                }
+            );
+        }
+        assert_eq!(
+            client.try_get_audit_entry(&7),
+            Err(Ok(Error::AuditEntryNotFound))
+        );
+    }
 
-        let result = client.try_revoke_role(&admin, &owner);
+    #[test]
+    fn test_cannot_leave_zero_owners() {
+        let (env, owner, client) = setup_contract();
+
+        client.initialize(&owner);
+
+        // The sole owner demoting itself would orphan the contract
+        let result = client.try_assign_role(&owner, &owner, &Role::Admin);
         assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+        let result =
+            client.try_assign_roles_batch(&owner, &vec![&env, (owner.clone(), Role::Member)]);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+
+        // Transferring to itself would leave the owner demoted to Admin
+        let result = client.try_transfer_ownership(&owner, &owner);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+
+        assert!(client.is_owner(&owner));
+        assert_eq!(client.get_summary().owner_count, 1);
     }
 
     #[test]
-    fn test_revoke_admin_requires_owner() {
+    fn test_multi_owner_transfer_needs_threshold() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let admin1 = Address::generate(&env);
-        let admin2 = Address::generate(&env);
+        let owner2 = Address::generate(&env);
+        let owner3 = Address::generate(&env);
+        client.assign_role(&owner, &owner2, &Role::Owner);
+        client.assign_role(&owner, &owner3, &Role::Owner);
+        assert!(client.is_owner(&owner2));
+        assert!(client.is_owner(&owner3));
 
-        client.assign_role(&owner, &admin1, &Role::Admin);
-        client.assign_role(&owner, &admin2, &Role::Admin);
+        let result = client.try_set_owner_threshold(&owner, &4);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+        client.set_owner_threshold(&owner, &2);
+        assert_eq!(client.get_summary().owner_threshold, 2);
 
-        let result = client.try_revoke_role(&admin1, &admin2);
-        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        // One owner cannot transfer ownership alone
+        let new_owner = Address::generate(&env);
+        let result = client.try_transfer_ownership(&owner, &new_owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
+
+        let action = OwnerAction::TransferOwnership(new_owner.clone());
+        assert_eq!(client.approve_owner_action(&owner2, &action), 1);
+        let result = client.try_approve_owner_action(&owner2, &action);
+        assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+        let result = client.try_approve_owner_action(&new_owner, &action);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.transfer_ownership(&owner, &new_owner);
+        assert_eq!(client.get_summary().owner, new_owner);
+        assert!(client.is_admin_or_above(&owner));
+        assert!(!client.is_owner(&owner));
+
+        // Approvals are used up by the transfer
+        let result = client.try_transfer_ownership(&new_owner, &owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
     }
 
     #[test]
-    fn test_has_permission() {
+    fn test_multi_owner_upgrade_needs_threshold() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let viewer = Address::generate(&env);
-        let member = Address::generate(&env);
-        let admin = Address::generate(&env);
-        let random_addr = Address::generate(&env);
+        let owner2 = Address::generate(&env);
+        client.assign_role(&owner, &owner2, &Role::Owner);
+        client.set_owner_threshold(&owner, &2);
 
-        client.assign_role(&owner, &admin, &Role::Admin);
-        client.assign_role(&owner, &member, &Role::Member);
-        client.assign_role(&admin, &viewer, &Role::Viewer);
+        // Owners cannot drop below the threshold
+        let result = client.try_assign_role(&owner, &owner2, &Role::Admin);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
 
-        assert_eq!(client.has_permission(&owner, &Role::Owner), true);
-        assert_eq!(client.has_permission(&owner, &Role::Admin), true);
-        assert_eq!(client.has_permission(&owner, &Role::Member), true);
-        assert_eq!(client.has_permission(&owner, &Role::Viewer), true);
+        let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+        let result = client.try_upgrade(&owner2, &wasm_hash);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
 
-        assert_eq!(client.has_permission(&admin, &Role::Owner), false);
-        assert_eq!(client.has_permission(&admin, &Role::Admin), true);
-        assert_eq!(client.has_permission(&admin, &Role::Member), true);
+        // With the threshold met the call gets past the approval check and
+        // reaches the host, which rejects the hash as no such WASM exists.
+        client.approve_owner_action(&owner, &OwnerAction::Upgrade(wasm_hash.clone()));
+        let result = client.try_upgrade(&owner2, &wasm_hash);
+        assert!(matches!(result, Err(Err(_))));
+    }
 
-        assert_eq!(client.has_permission(&member, &Role::Admin), false);
-        assert_eq!(client.has_permission(&member, &Role::Member), true);
-        assert_eq!(client.has_permission(&member, &Role::Viewer), true);
+    #[test]
+    fn test_pause_emits_shared_event() {
+        let (env, owner, client) = setup_contract();
 
-        assert_eq!(client.has_permission(&viewer, &Role::Member), false);
-        assert_eq!(client.has_permission(&viewer, &Role::Viewer), true);
+        client.initialize(&owner);
+        client.pause(&owner);
 
-        assert_eq!(client.has_permission(&random_addr, &Role::Viewer), false);
+        let event = env.events().all().last().unwrap();
+        assert_eq!(event.0, client.address);
+        assert_eq!(
+            event.1,
+            (symbol_short!("acl"), symbol_short!("pause"), EVENT_VERSION).into_val(&env)
+        );
+        let payload: (Address, bool) = <_>::try_from_val(&env, &event.2).unwrap();
+        assert_eq!(payload, (owner, true));
     }
 
     #[test]
-    fn test_is_owner() {
+    fn test_governance_can_upgrade_without_owner_approvals() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        let admin = Address::generate(&env);
-        let member = Address::generate(&env);
-        let random_addr = Address::generate(&env);
+        let owner2 = Address::generate(&env);
+        let governance = Address::generate(&env);
+        client.assign_role(&owner, &owner2, &Role::Owner);
+        client.set_owner_threshold(&owner, &2);
+
+        // Trusting governance is itself a sensitive action
+        let result = client.try_set_governance(&owner, &governance);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
+        client.approve_owner_action(&owner2, &OwnerAction::SetGovernance(governance.clone()));
+        client.set_governance(&owner, &governance);
+        assert_eq!(client.get_governance(), Some(governance.clone()));
+
+        let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+        let stranger = Address::generate(&env);
+        let result = client.try_upgrade(&stranger, &wasm_hash);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        client.assign_role(&owner, &admin, &Role::Admin);
-        client.assign_role(&owner, &member, &Role::Member);
+        // Governance skips the owner approvals and reaches the host, which
+        // rejects the hash as no such WASM exists.
+        let result = client.try_upgrade(&governance, &wasm_hash);
+        assert!(matches!(result, Err(Err(_))));
+    }
 
-        assert_eq!(client.is_owner(&owner), true);
-        assert_eq!(client.is_owner(&admin), false);
-        assert_eq!(client.is_owner(&member), false);
-        assert_eq!(client.is_owner(&random_addr), false);
+    #[test]
+    fn test_set_role_level_reorders_hierarchy() {
+        let (env, owner, client) = setup_contract();
+        client.initialize(&owner);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.assign_role(&owner, &alice, &Role::Member);
+        client.assign_role(&owner, &bob, &Role::Admin);
+
+        assert!(!client.has_permission(&alice, &Role::Admin));
+        assert!(client.is_member_or_above(&bob));
+
+        // Only the owner can remap levels, and never to zero
+        let result = client.try_set_role_level(&bob, &Role::Member, &350);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_set_role_level(&owner, &Role::Member, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+
+        // Rank Member above Admin
+        client.set_role_level(&owner, &Role::Member, &350);
+        assert_eq!(client.get_builtin_level(&Role::Member), 350);
+        assert_eq!(client.get_builtin_level(&Role::Admin), 300);
+        assert!(client.has_permission(&alice, &Role::Admin));
+        assert!(client.is_admin_or_above(&alice));
+        assert!(!client.has_permission(&bob, &Role::Member));
+        assert!(!client.is_member_or_above(&bob));
+        assert!(client.has_level(&alice, &350));
+        assert!(!client.has_level(&bob, &350));
+
+        // Custom roles rank against the new levels
+        let moderator = Symbol::new(&env, "moderator");
+        client.define_role(&owner, &moderator, &320);
+        assert!(client.has_custom_permission(&alice, &moderator));
+        assert!(!client.has_custom_permission(&bob, &moderator));
+        assert!(client.has_custom_permission(&owner, &moderator));
     }
 
     #[test]
-    fn test_is_admin_or_above() {
+    fn test_revoke_by_assignor() {
+        let (env, owner, client) = setup_contract();
+        client.initialize(&owner);
+
+        let rogue_admin = Address::generate(&env);
+        let other_admin = Address::generate(&env);
+        client.assign_role(&owner, &rogue_admin, &Role::Admin);
+        client.assign_role(&owner, &other_admin, &Role::Admin);
+
+        let rogue1 = Address::generate(&env);
+        let rogue2 = Address::generate(&env);
+        let rogue3 = Address::generate(&env);
+        let legit = Address::generate(&env);
+        client.assign_role(&rogue_admin, &rogue1, &Role::Member);
+        client.assign_role(&rogue_admin, &rogue2, &Role::Viewer);
+        client.assign_role(&rogue_admin, &rogue3, &Role::Member);
+        client.assign_role(&other_admin, &legit, &Role::Member);
+
+        let result = client.try_revoke_by_assignor(&other_admin, &rogue_admin);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(client.revoke_by_assignor(&owner, &rogue_admin), 3);
+        for rogue in [&rogue1, &rogue2, &rogue3] {
+            assert_eq!(client.try_get_role(rogue), Err(Ok(Error::RoleNotFound)));
+        }
+        assert!(client.is_member_or_above(&legit));
+        assert!(client.is_admin_or_above(&rogue_admin));
+
+        let summary = client.get_summary();
+        assert_eq!(summary.member_count, 1);
+        assert_eq!(summary.viewer_count, 0);
+
+        // Nothing left to revoke
+        assert_eq!(client.revoke_by_assignor(&owner, &rogue_admin), 0);
+    }
+
+    #[test]
+    fn test_get_version() {
         let (env, owner, client) = setup_contract();
+        client.initialize(&owner);
 
+        assert_eq!(client.get_version(), VERSION);
+
+        // Swap the code in place, as an upgrade would; the data keeps the
+        // version it was written by.
+        env.register_contract(Some(&client.address), AccessControlContract);
+        assert_eq!(client.get_version(), VERSION);
+        let stored: Option<u32> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&DataKey::Version)
+        });
+        assert_eq!(stored, Some(VERSION));
+    }
+
+    #[test]
+    fn test_trusted_assignor_capped_by_max_role() {
+        let (env, owner, client) = setup_contract();
         client.initialize(&owner);
 
-        let admin = Address::generate(&env);
+        let governance = env.register_contract(None, AccessControlContract);
         let member = Address::generate(&env);
-        let viewer = Address::generate(&env);
-        let random_addr = Address::generate(&env);
 
+        // Without trust the contract holds no role to assign from
+        let result = client.try_assign_role(&governance, &member, &Role::Member);
+        assert!(result.is_err());
+
+        let result = client.try_set_trusted_assignor(&owner, &governance, &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+
+        client.set_trusted_assignor(&owner, &governance, &Role::Member);
+        assert_eq!(client.get_trusted_assignor(&governance), Some(Role::Member));
+
+        client.assign_role(&governance, &member, &Role::Member);
+        assert_eq!(client.get_role(&member).role, Role::Member);
+
+        let result = client.try_assign_role(&governance, &member, &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        let result = client.try_assign_role(&governance, &member, &Role::Admin);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        assert!(!client.has_permission(&governance, &Role::Viewer));
+
+        // Roles above the cap cannot be demoted either
+        let admin = Address::generate(&env);
         client.assign_role(&owner, &admin, &Role::Admin);
-        client.assign_role(&owner, &member, &Role::Member);
-        client.assign_role(&admin, &viewer, &Role::Viewer);
+        let result = client.try_assign_role(&governance, &admin, &Role::Member);
+        assert_eq!(result, Err(Ok(Error::InsufficientPrivilege)));
+        assert_eq!(client.get_role(&admin).role, Role::Admin);
 
-        assert_eq!(client.is_admin_or_above(&owner), true);
-        assert_eq!(client.is_admin_or_above(&admin), true);
-        assert_eq!(client.is_admin_or_above(&member), false);
-        assert_eq!(client.is_admin_or_above(&viewer), false);
-        assert_eq!(client.is_admin_or_above(&random_addr), false);
+        client.remove_trusted_assignor(&owner, &governance);
+        assert_eq!(client.get_trusted_assignor(&governance), None);
+        let result = client.try_assign_role(&governance, &Address::generate(&env), &Role::Viewer);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_is_member_or_above() {
+    fn test_has_permission_batch() {
         let (env, owner, client) = setup_contract();
-
         client.initialize(&owner);
 
         let admin = Address::generate(&env);
         let member = Address::generate(&env);
         let viewer = Address::generate(&env);
-        let random_addr = Address::generate(&env);
-
+        let outsider = Address::generate(&env);
         client.assign_role(&owner, &admin, &Role::Admin);
         client.assign_role(&owner, &member, &Role::Member);
-        client.assign_role(&admin, &viewer, &Role::Viewer);
+        client.assign_role(&owner, &viewer, &Role::Viewer);
+
+        let addresses = vec![
+            &env,
+            viewer.clone(),
+            owner.clone(),
+            outsider.clone(),
+            member.clone(),
+            admin.clone(),
+        ];
+        let results = client.has_permission_batch(&addresses, &Role::Member);
+        assert_eq!(results, vec![&env, false, true, false, true, true]);
+
+        assert_eq!(
+            client.has_permission_batch(&Vec::new(&env), &Role::Viewer),
+            Vec::new(&env)
+        );
 
-        assert_eq!(client.is_member_or_above(&owner), true);
-        assert_eq!(client.is_member_or_above(&admin), true);
-        assert_eq!(client.is_member_or_above(&member), true);
-        assert_eq!(client.is_member_or_above(&viewer), false);
-        assert_eq!(client.is_member_or_above(&random_addr), false);
+        let mut oversized = Vec::new(&env);
+        for _ in 0..=MAX_BATCH_SIZE {
+            oversized.push_back(member.clone());
+        }
+        let result = client.try_has_permission_batch(&oversized, &Role::Viewer);
+        assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
     }
 
     #[test]
-    fn test_transfer_ownership() {
+    fn test_events_carry_schema_version() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
+        let member = Address::generate(&env);
+        client.assign_role(&owner, &member, &Role::Member);
+        client.revoke_role(&owner, &member);
 
-        let new_owner = Address::generate(&env);
-
-        client.transfer_ownership(&owner, &new_owner);
-
-        let summary = client.get_summary();
-        assert_eq!(summary.owner, new_owner);
-
-        let old_owner_role = client.get_role(&owner);
-        assert_eq!(old_owner_role.role, Role::Admin);
-
-        let new_owner_role = client.get_role(&new_owner);
-        assert_eq!(new_owner_role.role, Role::Owner);
-
-        assert_eq!(client.is_owner(&new_owner), true);
-        assert_eq!(client.is_owner(&owner), false);
-        assert_eq!(client.is_admin_or_above(&owner), true);
+        let events = env.events().all();
+        assert!(events.len() >= 3);
+        for (_, topics, _) in events.iter() {
+            let version = u32::try_from_val(&env, &topics.last().unwrap()).unwrap();
+            assert_eq!(version, EVENT_VERSION);
+        }
     }
 
     #[test]
-    fn test_transfer_ownership_role_counts() {
+    fn test_granting_owner_needs_owner_approvals() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
-        // Before transfer: owner_count = 1, admin_count = 0
-        let summary_before = client.get_summary();
-        assert_eq!(summary_before.owner_count, 1);
-        assert_eq!(summary_before.admin_count, 0);
-
-        let new_owner = Address::generate(&env);
-
-        client.transfer_ownership(&owner, &new_owner);
-
-        // After transfer: owner_count should be 1 (new owner), admin_count should be 1 (old owner)
-        let summary_after = client.get_summary();
-        assert_eq!(summary_after.owner_count, 1, "owner_count should be 1 after transfer");
-        assert_eq!(summary_after.admin_count, 1, "admin_count should be 1 after transfer");
-        assert_eq!(summary_after.total_members, 2, "total_members should be 2");
+        let owner2 = Address::generate(&env);
+        client.assign_role(&owner, &owner2, &Role::Owner);
+        client.set_owner_threshold(&owner, &2);
+
+        // A single owner cannot mint a co-owner to approve on their behalf
+        let puppet = Address::generate(&env);
+        let result = client.try_assign_role(&owner, &puppet, &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
+        let result = client.try_assign_role_temp(&owner, &puppet, &Role::Owner, &100);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+        assert!(!client.is_owner(&puppet));
+
+        client.approve_owner_action(&owner2, &OwnerAction::AssignOwner(puppet.clone()));
+        client.assign_role(&owner, &puppet, &Role::Owner);
+        assert!(client.is_owner(&puppet));
+
+        // The approval was used up
+        let other = Address::generate(&env);
+        client.approve_owner_action(&owner2, &OwnerAction::AssignOwner(other.clone()));
+        client.assign_role(&owner, &other, &Role::Owner);
+        let result = client.try_assign_role(&owner, &Address::generate(&env), &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::InsufficientApprovals)));
     }
 
     #[test]
-    fn test_transfer_ownership_unauthorized() {
+    fn test_existing_owner_cannot_be_reassigned() {
         let (env, owner, client) = setup_contract();
 
         client.initialize(&owner);
 
+        let owner2 = Address::generate(&env);
         let admin = Address::generate(&env);
-        let new_owner = Address::generate(&env);
-
+        client.assign_role(&owner, &owner2, &Role::Owner);
         client.assign_role(&owner, &admin, &Role::Admin);
 
-        let result = client.try_transfer_ownership(&admin, &new_owner);
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        // An admin cannot demote a co-owner that revoke_role protects
+        let result = client.try_assign_role(&admin, &owner2, &Role::Viewer);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+        let result = client.try_assign_role(&owner, &owner2, &Role::Admin);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+        let result = client.try_assign_role(&owner, &owner2, &Role::Owner);
+        assert_eq!(result, Err(Ok(Error::CannotRemoveOwner)));
+        assert!(client.is_owner(&owner2));
+        assert_eq!(client.get_summary().owner_count, 2);
     }
 
     #[test]
-    fn test_initialize_sets_owner_role() {
+    fn test_expired_owner_cannot_lock_owner_threshold() {
         let (env, owner, client) = setup_contract();
-
         client.initialize(&owner);
 
-        let role_assignment = client.get_role(&owner);
-        assert_eq!(role_assignment.role, Role::Owner);
-        assert_eq!(role_assignment.address, owner);
-        assert_eq!(role_assignment.assigned_by, owner);
+        env.ledger().set_timestamp(1_000);
+        let owner2 = Address::generate(&env);
+        let result = client.try_assign_role_temp(&owner, &owner2, &Role::Owner, &3_600);
+        assert_eq!(result, Err(Ok(Error::InvalidRole)));
+
+        // An owner that expires anyway, as a temporary owner granted before
+        // this was refused would
+        client.assign_role(&owner, &owner2, &Role::Owner);
+        client.set_owner_threshold(&owner, &2);
+        env.as_contract(&client.address, || {
+            let key = DataKey::Role(owner2.clone());
+            let mut assignment: RoleAssignment = env.storage().persistent().get(&key).unwrap();
+            assignment.expires_at = 4_600;
+            env.storage().persistent().set(&key, &assignment);
+        });
+        env.ledger().set_timestamp(4_600);
+        assert!(!client.is_owner(&owner2));
+
+        // The remaining owner can still act and lower the threshold
+        let result = client.try_set_owner_threshold(&owner, &2);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+        client.set_owner_threshold(&owner, &1);
+        client.cleanup_expired(&owner2);
+        assert_eq!(client.get_summary().owner_count, 1);
     }
 }
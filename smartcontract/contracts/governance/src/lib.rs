@@ -1,9 +1,45 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, Address, Env, String,
-    Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, log, symbol_short, token,
+    xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
+use stellar_guard_pausable::PausableError;
+
+/// Maximum number of proposal IDs accepted by a single `sweep_expired` call.
+const MAX_SWEEP_BATCH: u32 = 50;
+
+/// Maximum number of proposal IDs returned by one `get_proposals_by_proposer`
+/// page.
+const MAX_PROPOSER_PAGE: u32 = 50;
+
+/// Shortest voting period that can be configured, in ledgers, so members
+/// always have a chance to take part.
+const MIN_VOTING_PERIOD: u32 = 10;
+
+/// Longest voting period a proposal can run for, in ledgers (about 30 days).
+const MAX_VOTING_PERIOD: u32 = 518_400;
+
+/// Approximate seconds per ledger. Voting period bounds on the timestamp
+/// clock are the ledger bounds scaled by this, and switching clocks converts
+/// configured periods with it.
+const SECONDS_PER_LEDGER: u32 = 5;
+
+/// Stellar's all-zero account and contract addresses, which nobody can sign for.
+const ZERO_ADDRESSES: [&str; 2] = [
+    "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+    "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM",
+];
+
+/// Version of this contract's code. Bump it with every release, and add a
+/// migration when the stored data layout changes.
+///
+/// Version 2 requires every stored proposal to record `quorum_reached`.
+pub const VERSION: u32 = 2;
+
+/// Schema version of the events this contract publishes, carried as the last
+/// topic of every event. Bump it whenever an event's payload layout changes.
+pub const EVENT_VERSION: u32 = 1;
 
 // ============================================================================
 // Error Codes
@@ -44,6 +80,64 @@ pub enum Error {
     StorageError = 14,
     /// Attempted to add an address that is already a member.
     MemberAlreadyExists = 15,
+    /// A member voting weight must be greater than zero.
+    InvalidWeight = 16,
+    /// Voter has delegated their voting power and cannot vote directly.
+    VoteDelegated = 17,
+    /// Delegation is not allowed (self, chained, or missing delegation).
+    InvalidDelegation = 18,
+    /// No vote has been recorded for this voter on the proposal.
+    VoteNotFound = 19,
+    /// Proposal already has votes and can only be cancelled by the admin.
+    ProposalHasVotes = 20,
+    /// Proposal was cancelled.
+    ProposalCancelled = 21,
+    /// Proposal passed but its execution timelock has not elapsed.
+    TimelockActive = 22,
+    /// Too many items were passed in a single batch call.
+    BatchTooLarge = 23,
+    /// No deposit is held for this proposal.
+    DepositNotFound = 24,
+    /// Deposit cannot be refunded until the proposal is settled.
+    DepositLocked = 25,
+    /// Proposal was vetoed by the admin.
+    ProposalVetoed = 26,
+    /// The treasury refused to release funds for a funding proposal.
+    FundingFailed = 27,
+    /// The access-control contract refused a membership change.
+    AccessControlFailed = 28,
+    /// Member does not have enough quadratic voting credits.
+    InsufficientCredits = 29,
+    /// Member must wait for the proposal cooldown to pass.
+    CooldownActive = 30,
+    /// A guarded entry point was re-entered.
+    Reentrancy = 31,
+    /// Governance is paused by the admin.
+    Paused = 32,
+    /// The target contract refused an upgrade.
+    UpgradeFailed = 33,
+    /// The maximum number of active proposals has been reached.
+    TooManyActive = 34,
+    /// Proposal is not waiting for the admin to break a tie.
+    NoTieToBreak = 35,
+    /// Voting period is outside the bounds for the voting clock.
+    InvalidVotingPeriod = 36,
+    /// Stored data is from an older version; the admin must call `migrate`.
+    MigrationRequired = 37,
+    /// Stored data is already at the current version.
+    AlreadyMigrated = 38,
+    /// Passed proposal was not executed within the execution window.
+    ExecutionWindowExpired = 39,
+    /// Member's voting rights are suspended.
+    VotingSuspended = 40,
+    /// Applicant already has a membership request open for voting.
+    RequestPending = 41,
+    /// Stored data predates versioning and cannot be migrated in place.
+    UnsupportedVersion = 42,
+}
+
+impl PausableError for Error {
+    const PAUSED: Self = Error::Paused;
 }
 
 // ============================================================================
@@ -70,6 +164,72 @@ pub enum DataKey {
     Proposal(u64),
     /// Record of a vote: (proposal_id, voter_address).
     Vote(u64, Address),
+    /// Optional per-member voting weights; members without an entry weigh 1.
+    MemberWeights,
+    /// The delegate a member has assigned their voting power to.
+    Delegation(Address),
+    /// Members that have delegated their voting power to an address.
+    Delegators(Address),
+    /// Ledger sequences a passed proposal must wait before execution.
+    ExecutionDelay,
+    /// Ledger sequences after finalize within which a passed proposal must
+    /// be executed.
+    ExecutionWindow,
+    /// Last ledger sequence at which a passed proposal can be executed.
+    ExecutionDeadline(u64),
+    /// Clock used to measure the voting period of new proposals.
+    VotingClock,
+    /// Deposit proposers must lock when creating a proposal.
+    DepositConfig,
+    /// Deposit locked by the proposer of a proposal.
+    Deposit(u64),
+    /// Optional per-action approval thresholds (percent of yea/nay weight).
+    ActionThresholds,
+    /// Optional per-action voting periods overriding the global period.
+    ActionVotingPeriods,
+    /// IDs of the proposals created by an address, oldest first.
+    ProposerIndex(Address),
+    /// Member whose voting rights are suspended by the admin.
+    VotingSuspended(Address),
+    /// Treasury contract that pays out passed funding proposals.
+    Treasury,
+    /// Access-control contract that is the source of truth for membership.
+    AccessControl,
+    /// Members and their voting weights when a proposal was created.
+    Snapshot(u64),
+    /// Addresses with a vote recorded on a proposal, in voting order.
+    VoterList(u64),
+    /// Whether `vote` finalizes a proposal once its outcome is certain.
+    EarlyFinalize,
+    /// Share of total voting weight, in percent, whose nay votes reject a
+    /// proposal immediately.
+    RejectionThreshold,
+    /// Quadratic voting credits a member has left.
+    Credits(Address),
+    /// Ledger sequences a member must wait between creating proposals.
+    ProposalCooldown,
+    /// Ledger sequence at which a member last created a proposal.
+    LastProposal(Address),
+    /// Latest membership request an applicant made.
+    MembershipRequest(Address),
+    /// Maximum number of proposals that may be active at once.
+    MaxActiveProposals,
+    /// Number of proposals currently `Active`.
+    ActiveCount,
+    /// How finalize settles a tied vote.
+    TieBreak,
+    /// Quorum percentage a proposal's proposer raised it to.
+    QuorumOverride(u64),
+    /// Set once a quadratic vote has been cast on a proposal.
+    QuadraticVoted(u64),
+    /// Number of proposals a member has cast their own vote on.
+    ParticipationCount(Address),
+    /// Number of proposals a member was eligible to vote on.
+    ProposalsSeen(Address),
+    /// Set while a guarded entry point is running.
+    Locked,
+    /// Contract version that last wrote the stored data.
+    Version,
 }
 
 /// The type of action a proposal requests.
@@ -86,6 +246,8 @@ pub enum ProposalAction {
     RemoveMember,
     /// A general-purpose proposal.
     General,
+    /// Upgrade a contract that trusts governance to the given WASM hash.
+    UpgradeContract(Address, BytesN<32>),
 }
 
 /// The current status of a proposal.
@@ -100,8 +262,53 @@ pub enum ProposalStatus {
     Rejected,
     /// Proposal was executed.
     Executed,
-    /// Proposal expired without reaching quorum.
+    /// Proposal expired without reaching quorum or a yea/nay decision.
     Expired,
+    /// Proposal was withdrawn by its proposer or the admin.
+    Cancelled,
+    /// Proposal passed but was blocked by the admin before execution.
+    Vetoed,
+    /// Vote tied and is waiting for the admin to decide the outcome.
+    TieBreakPending,
+    /// Proposal passed but was not executed within the execution window.
+    ExpiredUnexecuted,
+    /// Voting has closed but the proposal has not been finalized yet.
+    /// Only reported by `get_proposal`; never stored.
+    PendingFinalization,
+}
+
+/// How a proposal's voting window is measured.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VotingClock {
+    /// Voting period and deadlines are ledger sequence numbers.
+    Ledger,
+    /// Voting period is in seconds and deadlines are ledger timestamps.
+    Timestamp,
+}
+
+/// How `finalize` settles a vote with equal yea and nay weight.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TieBreak {
+    /// A tied proposal is rejected.
+    Reject,
+    /// A tied proposal passes.
+    Pass,
+    /// A tied proposal waits for the admin to call `resolve_tie`.
+    AdminDecides,
+}
+
+/// A member's choice when voting on a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VoteChoice {
+    /// In favor of the proposal.
+    Yea,
+    /// Against the proposal.
+    Nay,
+    /// Present but abstaining; counts toward quorum only.
+    Abstain,
 }
 
 /// A governance proposal.
@@ -112,28 +319,97 @@ pub struct Proposal {
     pub id: u64,
     /// Title of the proposal.
     pub title: String,
-    /// Short description.
-    pub description: String,
+    /// Content hash (e.g. IPFS digest) of the full off-chain description.
+    pub description: BytesN<32>,
     /// The type of action being proposed.
     pub action: ProposalAction,
     /// Address of the proposer.
     pub proposer: Address,
-    /// Number of votes in favor.
+    /// Voting weight cast in favor.
     pub votes_for: u32,
-    /// Number of votes against.
+    /// Voting weight cast against.
     pub votes_against: u32,
-    /// Total number of votes cast.
+    /// Voting weight cast as abstentions.
+    pub abstentions: u32,
+    /// Total voting weight cast, including abstentions.
     pub total_votes: u32,
     /// Current status.
     pub status: ProposalStatus,
-    /// Ledger sequence when voting opened.
-    pub created_at: u32,
-    /// Ledger sequence when voting closes.
-    pub ends_at: u32,
+    /// Clock that `created_at` and `ends_at` are measured in.
+    pub clock: VotingClock,
+    /// Ledger sequence or timestamp when voting opened.
+    pub created_at: u64,
+    /// Ledger sequence or timestamp when voting closes.
+    pub ends_at: u64,
     /// Optional: amount requested (for funding proposals).
     pub amount: i128,
     /// Optional: target address (for member add/remove).
     pub target: Address,
+    /// Ledger sequence from which a passed proposal may be executed.
+    pub executable_at: u32,
+    /// Whether the vote has reached quorum at some point.
+    pub quorum_reached: bool,
+}
+
+/// A page of proposals returned by `list_proposals`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalPage {
+    /// Proposals on this page.
+    pub items: Vec<Proposal>,
+    /// `start_id` for the next page, if there is one.
+    pub next_cursor: Option<u64>,
+    /// Whether more matching proposals follow this page.
+    pub has_more: bool,
+}
+
+/// Outcome of a proposal as computed by the contract, for display.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalResult {
+    /// Whether the proposal passed, or would pass if finalized now.
+    pub passed: bool,
+    /// Whether turnout meets quorum.
+    pub quorum_met: bool,
+    /// Voting weight needed for quorum.
+    pub quorum_threshold: u64,
+    /// The final status, or for an active proposal the status `finalize`
+    /// would give it with the current tallies.
+    pub final_or_projected_status: ProposalStatus,
+}
+
+/// Anti-spam deposit required to create a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositConfig {
+    /// Token the deposit is paid in.
+    pub token: Address,
+    /// Amount locked per proposal.
+    pub amount: i128,
+    /// Recipient of deposits forfeited by rejected proposals.
+    pub treasury: Address,
+}
+
+/// A deposit held against a proposal until it is refunded or forfeited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalDeposit {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// A recorded vote on a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteRecord {
+    /// The choice that was cast.
+    pub choice: VoteChoice,
+    /// Voting weight counted by this record, including delegated weight.
+    pub weight: u32,
+    /// Set when the vote was cast on this member's behalf by their delegate.
+    pub delegate: Option<Address>,
+    /// Delegators whose weight was carried by this vote.
+    pub proxies: Vec<Address>,
 }
 
 /// Governance configuration.
@@ -145,6 +421,68 @@ pub struct GovConfig {
     pub quorum_percent: u32,
     pub voting_period: u32,
     pub proposal_count: u64,
+    /// Sum of the voting weights of all current members.
+    pub total_voting_weight: u32,
+    /// Ledger sequences a passed proposal waits before it can be executed.
+    pub execution_delay: u32,
+    /// Ledger sequences after finalize within which a passed proposal must
+    /// be executed; zero when passed proposals never expire.
+    pub execution_window: u32,
+    /// Clock used to measure the voting period of new proposals.
+    pub voting_clock: VotingClock,
+    /// Deposit locked per proposal; zero when disabled.
+    pub proposal_deposit: i128,
+    /// Treasury contract that pays out funding proposals, if configured.
+    pub treasury: Option<Address>,
+    /// Access-control contract used for membership, if configured.
+    pub access_control: Option<Address>,
+    /// Whether proposals finalize as soon as their outcome is certain.
+    pub early_finalize: bool,
+    /// Percent of total voting weight whose nay votes reject a proposal
+    /// immediately; zero when disabled.
+    pub rejection_threshold: u32,
+    /// Ledger sequences a member must wait between proposals.
+    pub proposal_cooldown: u32,
+    /// Maximum number of active proposals; zero when unlimited.
+    pub max_active_proposals: u32,
+    /// How tied votes are settled.
+    pub tie_break: TieBreak,
+}
+
+// ============================================================================
+// External Interfaces
+// ============================================================================
+
+/// The subset of the treasury contract used to pay out funding proposals.
+#[contractclient(name = "TreasuryClient")]
+pub trait TreasuryInterface {
+    fn release_funds(env: Env, governance: Address, to: Address, amount: i128);
+}
+
+/// Role levels of the access-control contract.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccessRole {
+    Viewer = 1,
+    Member = 2,
+    Admin = 3,
+    Owner = 4,
+}
+
+/// The subset of the access-control contract used for membership.
+#[contractclient(name = "AccessControlClient")]
+pub trait AccessControlInterface {
+    fn has_permission(env: Env, address: Address, required_role: AccessRole) -> bool;
+    fn get_all_members(env: Env) -> Vec<Address>;
+    fn assign_role(env: Env, assignor: Address, target: Address, role: AccessRole);
+    fn revoke_role(env: Env, revoker: Address, target: Address);
+}
+
+/// Any contract whose `upgrade` accepts governance as the caller.
+#[contractclient(name = "UpgradeableClient")]
+pub trait UpgradeableInterface {
+    fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>);
 }
 
 // ============================================================================
@@ -166,7 +504,8 @@ impl GovernanceContract {
     /// * `admin` - The admin address.
     /// * `members` - Initial list of DAO members.
     /// * `quorum_percent` - Minimum vote percentage for quorum (1-100).
-    /// * `voting_period` - Duration of voting in ledger sequences.
+    /// * `voting_period` - Duration of voting in ledger sequences. Must be
+    ///   within `MIN_VOTING_PERIOD..=MAX_VOTING_PERIOD`.
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -178,13 +517,15 @@ impl GovernanceContract {
             return Err(Error::AlreadyInitialized);
         }
 
-        if quorum_percent < 1 || quorum_percent > 100 {
+        if !(1..=100).contains(&quorum_percent) {
             return Err(Error::InvalidProposal);
         }
+        Self::validate_voting_period(&VotingClock::Ledger, voting_period)?;
 
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Version, &VERSION);
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Members, &members);
         env.storage()
@@ -198,7 +539,7 @@ impl GovernanceContract {
             .set(&DataKey::ProposalCounter, &0_u64);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("init")),
+            (symbol_short!("gov"), symbol_short!("init"), EVENT_VERSION),
             (admin.clone(), members.len(), quorum_percent),
         );
 
@@ -220,175 +561,180 @@ impl GovernanceContract {
     /// # Arguments
     /// * `proposer` - Must be a DAO member.
     /// * `title` - Short title for the proposal (must not be empty).
-    /// * `description` - Description of what the proposal does (must not be empty).
+    /// * `description` - 32-byte content hash of the off-chain description (must not be all zeros).
     /// * `action` - The type of proposal action.
     /// * `amount` - Amount requested (must be >= 0, relevant for Funding proposals).
     /// * `target` - Target address (must be valid for AddMember/RemoveMember actions).
+    /// * `quorum_override` - Higher quorum percentage for this proposal than
+    ///   the global default, e.g. for a high-stakes change. It can only
+    ///   tighten quorum, never loosen it, and cannot be changed later.
+    ///
+    /// # Errors
+    /// * `Error::InvalidProposal` - If `quorum_override` is above 100 or
+    ///   below the global quorum.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_proposal(
         env: Env,
         proposer: Address,
         title: String,
-        description: String,
+        description: BytesN<32>,
         action: ProposalAction,
         amount: i128,
         target: Address,
+        quorum_override: Option<u32>,
     ) -> Result<u64, Error> {
         Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
         Self::require_member(&env, &proposer)?;
 
         proposer.require_auth();
 
-        // Validation checks
-        if title == String::from_str(&env, "") {
-            return Err(Error::InvalidProposal);
-        }
-        if description == String::from_str(&env, "") {
-            return Err(Error::InvalidProposal);
+        if let Some(quorum_percent) = quorum_override {
+            let global_quorum: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::QuorumPercent)
+                .unwrap_or(50);
+            if !(global_quorum.max(1)..=100).contains(&quorum_percent) {
+                return Err(Error::InvalidProposal);
+            }
         }
-        if amount < 0 {
-            return Err(Error::InvalidProposal);
+
+        Self::check_cooldown(&env, &proposer)?;
+
+        let proposal_id =
+            Self::open_proposal(&env, &proposer, title, description, action, amount, target)?;
+
+        if let Some(quorum_percent) = quorum_override {
+            env.storage()
+                .persistent()
+                .set(&DataKey::QuorumOverride(proposal_id), &quorum_percent);
+
+            env.events().publish(
+                (
+                    symbol_short!("gov"),
+                    symbol_short!("quorum_ov"),
+                    EVENT_VERSION,
+                ),
+                (proposal_id, quorum_percent),
+            );
         }
 
-        // Get and increment counter with overflow protection
-        let current_counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0);
-        let proposal_id = current_counter.checked_add(1).ok_or(Error::Overflow)?;
-        env.storage()
-            .instance()
-            .set(&DataKey::ProposalCounter, &proposal_id);
+        Ok(proposal_id)
+    }
 
-        // Calculate voting end
-        let voting_period: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1000);
-        let current_ledger = env.ledger().sequence();
-        let ends_at = current_ledger
-            .checked_add(voting_period)
-            .ok_or(Error::Overflow)?;
+    /// Ask to join the DAO.
+    ///
+    /// Any address may call this to open an `AddMember` proposal targeting
+    /// itself, which members then vote on like any other proposal. The
+    /// description is the SHA-256 of the applicant's address XDR.
+    /// Applicants are held to the proposal cooldown and may only have one
+    /// request open for voting at a time.
+    pub fn request_membership(env: Env, applicant: Address) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
 
-        let proposal = Proposal {
-            id: proposal_id,
-            title: title.clone(),
-            description,
-            action: action.clone(),
-            proposer: proposer.clone(),
-            votes_for: 0,
-            votes_against: 0,
-            total_votes: 0,
-            status: ProposalStatus::Active,
-            created_at: current_ledger,
-            ends_at,
-            amount,
-            target: target.clone(),
-        };
+        applicant.require_auth();
 
-        // Store proposal with error handling
-        env.storage()
+        if Self::is_member(&env, &applicant) {
+            return Err(Error::MemberAlreadyExists);
+        }
+
+        let request_key = DataKey::MembershipRequest(applicant.clone());
+        let open_request = env
+            .storage()
             .persistent()
-            .set(&DataKey::Proposal(proposal_id), &proposal);
+            .get::<DataKey, u64>(&request_key)
+            .and_then(|id| {
+                env.storage()
+                    .persistent()
+                    .get::<DataKey, Proposal>(&DataKey::Proposal(id))
+            })
+            .is_some_and(|request| request.status == ProposalStatus::Active);
+        if open_request {
+            return Err(Error::RequestPending);
+        }
+        Self::check_cooldown(&env, &applicant)?;
+
+        let description = env
+            .crypto()
+            .sha256(&applicant.clone().to_xdr(&env))
+            .to_bytes();
+        let proposal_id = Self::open_proposal(
+            &env,
+            &applicant,
+            String::from_str(&env, "Membership request"),
+            description,
+            ProposalAction::AddMember,
+            0,
+            applicant.clone(),
+        )?;
+        env.storage().persistent().set(&request_key, &proposal_id);
 
-        // Emit event with required payload
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("propose")),
-            (
-                proposal_id,
-                proposer.clone(),
-                ends_at,
-                target.clone(),
-                amount,
-            ),
+            (symbol_short!("gov"), symbol_short!("apply"), EVENT_VERSION),
+            (proposal_id, applicant),
         );
 
-        log!(&env, "Proposal #{} created by {:?}", proposal_id, proposer);
         Ok(proposal_id)
     }
 
-    // ========================================================================
-    // Voting
-    // ========================================================================
-
-    /// Cast a vote on an active proposal.
+    /// Edit a proposal before anyone has voted on it.
     ///
-    /// # Arguments
-    /// * `voter` - Must be a DAO member.
-    /// * `proposal_id` - The ID of the proposal to vote on.
-    /// * `vote_for` - `true` to vote in favor, `false` to vote against.
-    pub fn vote(env: Env, voter: Address, proposal_id: u64, vote_for: bool) -> Result<(), Error> {
+    /// Only the original proposer may edit, and only while voting is open and
+    /// no vote has been cast. The action cannot be changed.
+    pub fn edit_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_id: u64,
+        title: String,
+        description: BytesN<32>,
+        amount: i128,
+        target: Address,
+    ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
-        Self::require_member(&env, &voter)?;
-
-        voter.require_auth();
+        stellar_guard_pausable::require_not_paused(&env)?;
 
-        // Check if already voted
-        let vote_key = DataKey::Vote(proposal_id, voter.clone());
-        if env.storage().persistent().has(&vote_key) {
-            return Err(Error::AlreadyVoted);
-        }
+        proposer.require_auth();
 
-        // Load proposal
         let mut proposal: Proposal = env
             .storage()
             .persistent()
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(Error::ProposalNotFound)?;
 
-        // Check proposal is still active
-        if proposal.status != ProposalStatus::Active {
-            return Err(Error::VotingClosed);
-        }
-
-        // Check voting period hasn't ended
-        let current_ledger = env.ledger().sequence();
-        if current_ledger > proposal.ends_at {
-            return Err(Error::VotingClosed);
+        if proposal.proposer != proposer {
+            return Err(Error::Unauthorized);
         }
-
-        // Record vote
-        if vote_for {
-            proposal.votes_for += 1;
-        } else {
-            proposal.votes_against += 1;
+        Self::require_voting_open(&env, &proposal)?;
+        if proposal.total_votes > 0 {
+            return Err(Error::ProposalHasVotes);
         }
-        proposal.total_votes += 1;
 
-        // Save vote record (prevents double voting)
-        env.storage().persistent().set(&vote_key, &vote_for);
+        Self::validate_proposal(&env, &title, &description, amount)?;
 
-        // Save updated proposal
+        proposal.title = title;
+        proposal.description = description;
+        proposal.amount = amount;
+        proposal.target = target;
         env.storage()
             .persistent()
             .set(&DataKey::Proposal(proposal_id), &proposal);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("vote")),
-            (proposal_id, voter.clone(), vote_for),
-        );
-
-        log!(
-            &env,
-            "Vote cast on proposal #{}: {:?} voted {}",
-            proposal_id,
-            voter,
-            if vote_for { "FOR" } else { "AGAINST" }
+            (symbol_short!("gov"), symbol_short!("edit"), EVENT_VERSION),
+            (proposal_id, proposer),
         );
 
         Ok(())
     }
 
-    // ========================================================================
-    // Proposal Finalization
-    // ========================================================================
-
-    /// Finalize a proposal after the voting period ends.
-    /// Determines if the proposal passed or was rejected based on votes and quorum.
-    pub fn finalize(env: Env, caller: Address, proposal_id: u64) -> Result<ProposalStatus, Error> {
+    /// Cancel an active proposal.
+    ///
+    /// The original proposer may cancel until the first vote is cast; the
+    /// admin may cancel at any point while the proposal is still active.
+    pub fn cancel_proposal(env: Env, caller: Address, proposal_id: u64) -> Result<(), Error> {
         Self::require_initialized(&env)?;
-        Self::require_member(&env, &caller)?;
 
         caller.require_auth();
 
@@ -398,1164 +744,7252 @@ impl GovernanceContract {
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(Error::ProposalNotFound)?;
 
+        if proposal.status == ProposalStatus::Cancelled {
+            return Err(Error::ProposalCancelled);
+        }
         if proposal.status != ProposalStatus::Active {
             return Err(Error::VotingClosed);
         }
 
-        // Check voting period has ended
-        let current_ledger = env.ledger().sequence();
-        if current_ledger <= proposal.ends_at {
-            return Err(Error::VotingStillActive);
-        }
-
-        // Check quorum
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env));
-        let quorum_percent: u32 = env
+        let admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::QuorumPercent)
-            .unwrap_or(50);
-
-        let member_count = members.len();
-        let quorum_threshold = member_count
-            .checked_mul(quorum_percent)
-            .ok_or(Error::Overflow)?
-            / 100;
-        if proposal.total_votes < quorum_threshold {
-            proposal.status = ProposalStatus::Expired;
-        } else if proposal.votes_for > proposal.votes_against {
-            proposal.status = ProposalStatus::Passed;
-        } else {
-            proposal.status = ProposalStatus::Rejected;
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if caller != admin {
+            if caller != proposal.proposer {
+                return Err(Error::Unauthorized);
+            }
+            if proposal.total_votes > 0 {
+                return Err(Error::ProposalHasVotes);
+            }
         }
 
-        let final_status = proposal.status.clone();
-
+        proposal.status = ProposalStatus::Cancelled;
         env.storage()
             .persistent()
             .set(&DataKey::Proposal(proposal_id), &proposal);
+        Self::release_active_slot(&env);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("finalize")),
-            (proposal_id, final_status.clone()),
+            (symbol_short!("gov"), symbol_short!("cancel"), EVENT_VERSION),
+            (proposal_id, caller),
         );
 
-        Ok(final_status)
+        Ok(())
     }
 
-    /// Execute a passed proposal.
-    /// Only the admin or proposer can execute.
-    pub fn execute_proposal(env: Env, executor: Address, proposal_id: u64) -> Result<(), Error> {
+    /// Give a proposal a longer voting period than the default.
+    ///
+    /// Only the proposer can extend, and only before any vote is cast.
+    /// `voting_period` is measured from when the proposal was created, in
+    /// the proposal's clock, and may not exceed that clock's maximum.
+    ///
+    /// # Errors
+    /// * `Error::InvalidVotingPeriod` - If the period does not lengthen voting
+    ///   or exceeds the maximum for the proposal's clock.
+    /// * `Error::AlreadyVoted` - If votes have already been cast.
+    pub fn extend_voting_period(
+        env: Env,
+        proposer: Address,
+        proposal_id: u64,
+        voting_period: u32,
+    ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
 
-        executor.require_auth();
+        proposer.require_auth();
 
         let mut proposal: Proposal = env
             .storage()
             .persistent()
             .get(&DataKey::Proposal(proposal_id))
             .ok_or(Error::ProposalNotFound)?;
-
-        if proposal.status == ProposalStatus::Executed {
-            return Err(Error::AlreadyExecuted);
-        }
-
-        if proposal.status != ProposalStatus::Passed {
-            return Err(Error::ProposalRejected);
-        }
-
-        // Only admin or proposer can execute
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if executor != admin && executor != proposal.proposer {
+        if proposal.proposer != proposer {
             return Err(Error::Unauthorized);
         }
+        Self::require_voting_open(&env, &proposal)?;
+        if proposal.total_votes > 0 {
+            return Err(Error::AlreadyVoted);
+        }
 
-        // Handle member add/remove actions
-        match proposal.action {
-            ProposalAction::AddMember => {
-                Self::internal_add_member(&env, &proposal.target)?;
-            }
-            ProposalAction::RemoveMember => {
-                Self::internal_remove_member(&env, &proposal.target)?;
-            }
-            _ => {
-                // Funding and General proposals are handled externally
-            }
+        let ends_at = proposal
+            .created_at
+            .checked_add(voting_period as u64)
+            .ok_or(Error::Overflow)?;
+        let (_, max_period) = Self::voting_period_bounds(&proposal.clock);
+        if voting_period > max_period || ends_at <= proposal.ends_at {
+            return Err(Error::InvalidVotingPeriod);
         }
 
-        proposal.status = ProposalStatus::Executed;
+        proposal.ends_at = ends_at;
         env.storage()
             .persistent()
             .set(&DataKey::Proposal(proposal_id), &proposal);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("exec")),
-            (proposal_id, executor.clone()),
+            (symbol_short!("gov"), symbol_short!("extend"), EVENT_VERSION),
+            (proposal_id, ends_at),
         );
 
         Ok(())
     }
 
-    // ========================================================================
-    // Member Management (Internal)
-    // ========================================================================
+    /// Get the quorum percentage a proposal was raised to, if any.
+    pub fn get_quorum_override(env: Env, proposal_id: u64) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::QuorumOverride(proposal_id))
+    }
 
-    fn internal_add_member(env: &Env, member: &Address) -> Result<(), Error> {
-        let mut members: Vec<Address> = env
+    /// Veto a passed proposal before it is executed. Admin only.
+    ///
+    /// Intended for use during the execution timelock, so a malicious
+    /// proposal that wins the vote can still be stopped. Any deposit locked
+    /// by the proposer is forfeited to the treasury.
+    pub fn veto_proposal(env: Env, admin: Address, proposal_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
-
-        // Check not already a member
-        for i in 0..members.len() {
-            if members.get(i).unwrap() == *member {
-                return Err(Error::MemberAlreadyExists);
-            }
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
         }
 
-        members.push_back(member.clone());
-        env.storage().instance().set(&DataKey::Members, &members);
-        Ok(())
-    }
+        admin.require_auth();
 
-    fn internal_remove_member(env: &Env, member: &Address) -> Result<(), Error> {
-        let members: Vec<Address> = env
+        let mut proposal: Proposal = env
             .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
 
-        let mut new_members = Vec::new(env);
-        for i in 0..members.len() {
-            let m = members.get(i).unwrap();
-            if m != *member {
-                new_members.push_back(m);
-            }
+        match proposal.status {
+            ProposalStatus::Passed => {}
+            ProposalStatus::Executed => return Err(Error::AlreadyExecuted),
+            ProposalStatus::Vetoed => return Err(Error::ProposalVetoed),
+            _ => return Err(Error::ProposalRejected),
         }
 
+        proposal.status = ProposalStatus::Vetoed;
         env.storage()
-            .instance()
-            .set(&DataKey::Members, &new_members);
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        Self::forfeit_deposit(&env, proposal_id);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("veto"), EVENT_VERSION),
+            (proposal_id, admin),
+        );
+
         Ok(())
     }
 
     // ========================================================================
-    // Query Functions
+    // Voting
     // ========================================================================
 
-    /// Get a proposal by its ID.
-    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .ok_or(Error::ProposalNotFound)
+    /// Cast a vote on an active proposal.
+    ///
+    /// Equivalent to [`vote_choice`](Self::vote_choice) with `Yea` or `Nay`.
+    ///
+    /// # Arguments
+    /// * `voter` - Must be a DAO member.
+    /// * `proposal_id` - The ID of the proposal to vote on.
+    /// * `vote_for` - `true` to vote in favor, `false` to vote against.
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, vote_for: bool) -> Result<(), Error> {
+        let choice = if vote_for {
+            VoteChoice::Yea
+        } else {
+            VoteChoice::Nay
+        };
+        Self::vote_choice(env, voter, proposal_id, choice)
     }
 
-    /// Get governance configuration.
-    pub fn get_config(env: Env) -> Result<GovConfig, Error> {
+    /// Cast a yea, nay or abstain vote on an active proposal.
+    ///
+    /// The vote carries the voter's own weight plus the weight of every
+    /// member that has delegated to them and has not already voted. Those
+    /// delegators are marked as having voted through the delegate.
+    /// Abstentions count toward quorum but not toward the pass decision.
+    pub fn vote_choice(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        choice: VoteChoice,
+    ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_snapshot_member(&env, proposal_id, &voter)?;
+        Self::require_voting_rights(&env, &voter)?;
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env));
-        let quorum_percent: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::QuorumPercent)
-            .unwrap_or(50);
-        let voting_period: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1000);
-        let proposal_count: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0);
+        voter.require_auth();
 
-        Ok(GovConfig {
-            admin,
-            member_count: members.len(),
-            quorum_percent,
-            voting_period,
-            proposal_count,
-        })
-    }
+        // Delegators must let their delegate vote for them
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Delegation(voter.clone()))
+        {
+            return Err(Error::VoteDelegated);
+        }
 
-    /// Get the list of DAO members.
-    pub fn get_members(env: Env) -> Vec<Address> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env))
-    }
+        // Check if already voted
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(Error::AlreadyVoted);
+        }
 
-    /// Check if an address has voted on a proposal.
-    pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
-        env.storage()
+        // Load proposal
+        let mut proposal: Proposal = env
+            .storage()
             .persistent()
-            .has(&DataKey::Vote(proposal_id, voter))
-    }
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
 
-    // ========================================================================
-    // Admin Functions
-    // ========================================================================
+        Self::require_voting_open(&env, &proposal)?;
+
+        // Record vote, weighted by the voter's weight plus delegated weight,
+        // as of the proposal's snapshot
+        let snapshot = Self::voting_snapshot(&env, proposal_id);
+        let mut weight = snapshot.get(voter.clone()).unwrap_or(0);
+        let mut proxies = Vec::new(&env);
+        for delegator in Self::delegators_of(&env, &voter).iter() {
+            let delegator_key = DataKey::Vote(proposal_id, delegator.clone());
+            let Some(delegator_weight) = snapshot.get(delegator.clone()) else {
+                continue;
+            };
+            if env.storage().persistent().has(&delegator_key)
+                || Self::require_voting_rights(&env, &delegator).is_err()
+            {
+                continue;
+            }
+            weight = weight
+                .checked_add(delegator_weight)
+                .ok_or(Error::Overflow)?;
+            let proxy_record = VoteRecord {
+                choice: choice.clone(),
+                weight: 0,
+                delegate: Some(voter.clone()),
+                proxies: Vec::new(&env),
+            };
+            env.storage()
+                .persistent()
+                .set(&delegator_key, &proxy_record);
+            proxies.push_back(delegator);
+        }
+        Self::add_to_tally(&mut proposal, &choice, weight)?;
 
-    /// Transfer admin role to a new address.
-    pub fn transfer_admin(
-        env: Env,
-        current_admin: Address,
-        new_admin: Address,
-    ) -> Result<(), Error> {
-        Self::require_initialized(&env)?;
+        // Save vote record (prevents double voting)
+        let record = VoteRecord {
+            choice: choice.clone(),
+            weight,
+            delegate: None,
+            proxies,
+        };
+        env.storage().persistent().set(&vote_key, &record);
 
-        let admin: Address = env
+        // Append to the roll-call, including delegators voted for by proxy
+        let voters_key = DataKey::VoterList(proposal_id);
+        let mut voters: Vec<Address> = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if current_admin != admin {
-            return Err(Error::Unauthorized);
-        }
+            .persistent()
+            .get(&voters_key)
+            .unwrap_or(Vec::new(&env));
+        voters.push_back(voter.clone());
+        voters.append(&record.proxies);
+        env.storage().persistent().set(&voters_key, &voters);
+        Self::count_participation(&env, &voter, true);
 
-        current_admin.require_auth();
+        // Save updated proposal
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        match choice {
+            VoteChoice::Yea | VoteChoice::Nay => env.events().publish(
+                (symbol_short!("gov"), symbol_short!("vote"), EVENT_VERSION),
+                (proposal_id, voter.clone(), choice == VoteChoice::Yea),
+            ),
+            VoteChoice::Abstain => env.events().publish(
+                (
+                    symbol_short!("gov"),
+                    symbol_short!("abstain"),
+                    EVENT_VERSION,
+                ),
+                (proposal_id, voter.clone()),
+            ),
+        }
+        Self::note_quorum(&env, &mut proposal)?;
+        Self::reject_if_opposed(&env, &mut proposal)?;
+
+        if proposal.status == ProposalStatus::Active
+            && env
+                .storage()
+                .instance()
+                .get(&DataKey::EarlyFinalize)
+                .unwrap_or(false)
+        {
+            Self::conclude_if_decided(&env, &mut proposal)?;
+        }
 
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("admin")),
-            (current_admin, new_admin.clone()),
+        log!(
+            &env,
+            "Vote cast on proposal #{}: {:?} voted {}",
+            proposal_id,
+            voter,
+            match choice {
+                VoteChoice::Yea => "FOR",
+                VoteChoice::Nay => "AGAINST",
+                VoteChoice::Abstain => "ABSTAIN",
+            }
         );
 
         Ok(())
     }
 
-    /// Update the quorum percentage. Admin only.
-    pub fn set_quorum(env: Env, admin: Address, new_quorum: u32) -> Result<(), Error> {
+    /// Cast `votes` yea or nay votes on an active proposal at a quadratic
+    /// cost.
+    ///
+    /// Spends `votes * votes` of the voter's credits and adds `votes` to the
+    /// tally, ignoring member weight and delegated weight. Credits spent are
+    /// not refunded if the vote is later changed or retracted.
+    ///
+    /// Once a proposal has a quadratic vote it is no longer finalized or
+    /// rejected early, since unspent credits can outweigh the remaining
+    /// member weight; it runs until `finalize`.
+    pub fn vote_quadratic(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        votes: u32,
+        support: bool,
+    ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_snapshot_member(&env, proposal_id, &voter)?;
+        Self::require_voting_rights(&env, &voter)?;
 
-        let stored_admin: Address = env
+        voter.require_auth();
+
+        if votes == 0 {
+            return Err(Error::InvalidWeight);
+        }
+
+        if env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
+            .persistent()
+            .has(&DataKey::Delegation(voter.clone()))
+        {
+            return Err(Error::VoteDelegated);
         }
 
-        admin.require_auth();
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(Error::AlreadyVoted);
+        }
 
-        // Validate quorum range (1-100)
-        if new_quorum < 1 || new_quorum > 100 {
-            return Err(Error::InvalidProposal);
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        Self::require_voting_open(&env, &proposal)?;
+
+        // Spend credits
+        let cost = votes.checked_mul(votes).ok_or(Error::Overflow)?;
+        let credits_key = DataKey::Credits(voter.clone());
+        let credits: u32 = env.storage().persistent().get(&credits_key).unwrap_or(0);
+        if credits < cost {
+            return Err(Error::InsufficientCredits);
         }
+        env.storage()
+            .persistent()
+            .set(&credits_key, &(credits - cost));
+
+        let choice = if support {
+            VoteChoice::Yea
+        } else {
+            VoteChoice::Nay
+        };
+        Self::add_to_tally(&mut proposal, &choice, votes)?;
 
+        let record = VoteRecord {
+            choice,
+            weight: votes,
+            delegate: None,
+            proxies: Vec::new(&env),
+        };
+        env.storage().persistent().set(&vote_key, &record);
         env.storage()
-            .instance()
-            .set(&DataKey::QuorumPercent, &new_quorum);
+            .persistent()
+            .set(&DataKey::QuadraticVoted(proposal_id), &true);
+
+        let voters_key = DataKey::VoterList(proposal_id);
+        let mut voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&voters_key)
+            .unwrap_or(Vec::new(&env));
+        voters.push_back(voter.clone());
+        env.storage().persistent().set(&voters_key, &voters);
+        Self::count_participation(&env, &voter, true);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("qvote"), EVENT_VERSION),
+            (proposal_id, voter.clone(), support, votes),
+        );
+        Self::note_quorum(&env, &mut proposal)?;
+        Self::reject_if_opposed(&env, &mut proposal)?;
+
+        if proposal.status == ProposalStatus::Active
+            && env
+                .storage()
+                .instance()
+                .get(&DataKey::EarlyFinalize)
+                .unwrap_or(false)
+        {
+            Self::conclude_if_decided(&env, &mut proposal)?;
+        }
 
-        env.events()
-            .publish((symbol_short!("gov"), symbol_short!("quorum")), new_quorum);
+        log!(
+            &env,
+            "Quadratic vote on proposal #{}: {:?} cast {} votes for {} credits",
+            proposal_id,
+            voter,
+            votes,
+            cost
+        );
 
         Ok(())
     }
 
-    /// Upgrade contract WASM. Admin only.
-    pub fn upgrade(
+    /// Change an existing vote while the voting period is still open.
+    ///
+    /// Moves the vote's full weight to the new choice without changing
+    /// `total_votes`. Votes cast through a delegate can only be changed by
+    /// that delegate.
+    pub fn change_vote(
         env: Env,
-        admin: Address,
-        new_wasm_hash: soroban_sdk::BytesN<32>,
+        voter: Address,
+        proposal_id: u64,
+        choice: VoteChoice,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_snapshot_member(&env, proposal_id, &voter)?;
+        Self::require_voting_rights(&env, &voter)?;
 
-        let stored_admin: Address = env
+        voter.require_auth();
+
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        let mut record: VoteRecord = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
+            .persistent()
+            .get(&vote_key)
+            .ok_or(Error::VoteNotFound)?;
+        if record.delegate.is_some() {
+            return Err(Error::VoteDelegated);
         }
 
-        admin.require_auth();
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        Self::require_voting_open(&env, &proposal)?;
+
+        if record.choice != choice {
+            Self::remove_from_tally(&mut proposal, &record.choice, record.weight);
+            Self::add_to_tally(&mut proposal, &choice, record.weight)?;
+
+            record.choice = choice.clone();
+            env.storage().persistent().set(&vote_key, &record);
+            for delegator in record.proxies.iter() {
+                let proxy_key = DataKey::Vote(proposal_id, delegator);
+                if let Some(mut proxy) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, VoteRecord>(&proxy_key)
+                {
+                    proxy.choice = choice.clone();
+                    env.storage().persistent().set(&proxy_key, &proxy);
+                }
+            }
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
-        Ok(())
-    }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Proposal(proposal_id), &proposal);
+        }
 
-    // ========================================================================
-    // Internal Helpers
-    // ========================================================================
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("chg_vote"),
+                EVENT_VERSION,
+            ),
+            (proposal_id, voter, choice),
+        );
 
-    fn require_initialized(env: &Env) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(Error::NotInitialized);
-        }
         Ok(())
     }
 
-    fn require_member(env: &Env, caller: &Address) -> Result<(), Error> {
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
+    /// Retract an existing vote while the voting period is still open.
+    ///
+    /// Removes the vote's full weight from the tallies and `total_votes`, so
+    /// a retraction can take a proposal back below quorum. Delegators whose
+    /// weight was carried by the vote become free to vote again.
+    pub fn retract_vote(env: Env, voter: Address, proposal_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_snapshot_member(&env, proposal_id, &voter)?;
+
+        voter.require_auth();
+
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        let record: VoteRecord = env
+            .storage()
+            .persistent()
+            .get(&vote_key)
+            .ok_or(Error::VoteNotFound)?;
+        if record.delegate.is_some() {
+            return Err(Error::VoteDelegated);
+        }
+
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        Self::require_voting_open(&env, &proposal)?;
+
+        Self::remove_from_tally(&mut proposal, &record.choice, record.weight);
+
+        env.storage().persistent().remove(&vote_key);
+        for delegator in record.proxies.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Vote(proposal_id, delegator));
+        }
+
+        let voters_key = DataKey::VoterList(proposal_id);
+        let voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&voters_key)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining = Vec::new(&env);
+        for address in voters.iter() {
+            if address != voter && !record.proxies.contains(&address) {
+                remaining.push_back(address);
+            }
+        }
+        env.storage().persistent().set(&voters_key, &remaining);
+        Self::count_participation(&env, &voter, false);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("retract"),
+                EVENT_VERSION,
+            ),
+            (proposal_id, voter),
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Delegation
+    // ========================================================================
+
+    /// Delegate voting power to another member.
+    ///
+    /// Delegation is single-hop: a member that already delegates cannot
+    /// receive delegations, and a member holding delegations cannot delegate
+    /// onward. Calling this again replaces the existing delegation.
+    ///
+    /// # Arguments
+    /// * `from` - The delegating member.
+    /// * `to` - The member who will vote on `from`'s behalf.
+    pub fn delegate(env: Env, from: Address, to: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_member(&env, &from)?;
+        Self::require_member(&env, &to)?;
+
+        from.require_auth();
+
+        if from == to {
+            return Err(Error::InvalidDelegation);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Delegation(to.clone()))
+        {
+            return Err(Error::InvalidDelegation);
+        }
+        if !Self::delegators_of(&env, &from).is_empty() {
+            return Err(Error::InvalidDelegation);
+        }
+
+        if let Some(previous) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::Delegation(from.clone()))
+        {
+            Self::remove_delegator(&env, &previous, &from);
+        }
+
+        let mut delegators = Self::delegators_of(&env, &to);
+        delegators.push_back(from.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegators(to.clone()), &delegators);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegation(from.clone()), &to);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("delegate"),
+                EVENT_VERSION,
+            ),
+            (from, to),
+        );
+
+        Ok(())
+    }
+
+    /// Revoke an existing delegation so the member can vote directly again.
+    pub fn undelegate(env: Env, from: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+
+        from.require_auth();
+
+        let to: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Delegation(from.clone()))
+            .ok_or(Error::InvalidDelegation)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegation(from.clone()));
+        Self::remove_delegator(&env, &to, &from);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("undeleg"),
+                EVENT_VERSION,
+            ),
+            (from, to),
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Proposal Finalization
+    // ========================================================================
+
+    /// Finalize a proposal after the voting period ends.
+    /// Determines if the proposal passed or was rejected based on votes and quorum.
+    pub fn finalize(env: Env, caller: Address, proposal_id: u64) -> Result<ProposalStatus, Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::require_member(&env, &caller)?;
+
+        caller.require_auth();
+
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        if proposal.status == ProposalStatus::Cancelled {
+            return Err(Error::ProposalCancelled);
+        }
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::VotingClosed);
+        }
+
+        // Check voting period has ended
+        if Self::clock_now(&env, &proposal.clock) <= proposal.ends_at {
+            return Err(Error::VotingStillActive);
+        }
+
+        let (status, quorum_threshold) = Self::tally(&env, &proposal)?;
+        Self::conclude(&env, &mut proposal, status.clone(), quorum_threshold)?;
+        Ok(status)
+    }
+
+    /// Decide a tied proposal left pending by the `AdminDecides` tie-break
+    /// policy. Admin only.
+    ///
+    /// # Errors
+    /// * `Error::NoTieToBreak` - If the proposal is not awaiting a tie-break.
+    pub fn resolve_tie(
+        env: Env,
+        admin: Address,
+        proposal_id: u64,
+        pass: bool,
+    ) -> Result<ProposalStatus, Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        if proposal.status != ProposalStatus::TieBreakPending {
+            return Err(Error::NoTieToBreak);
+        }
+
+        let status = if pass {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        };
+        let (_, quorum_threshold) = Self::quorum(&env, proposal_id)?;
+        Self::conclude(&env, &mut proposal, status.clone(), quorum_threshold)?;
+        Ok(status)
+    }
+
+    /// Refund the deposit locked when creating a proposal.
+    ///
+    /// Available once the proposal has passed, expired or been cancelled.
+    /// Deposits of rejected proposals are forfeited to the treasury during
+    /// `finalize` and cannot be claimed.
+    pub fn claim_deposit(env: Env, proposer: Address, proposal_id: u64) -> Result<i128, Error> {
+        Self::require_initialized(&env)?;
+
+        proposer.require_auth();
+
+        let proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        if proposal.proposer != proposer {
+            return Err(Error::Unauthorized);
+        }
+
+        let deposit: ProposalDeposit = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Deposit(proposal_id))
+            .ok_or(Error::DepositNotFound)?;
+
+        match proposal.status {
+            ProposalStatus::Passed
+            | ProposalStatus::Executed
+            | ProposalStatus::Expired
+            | ProposalStatus::ExpiredUnexecuted
+            | ProposalStatus::Cancelled => {}
+            ProposalStatus::Active
+            | ProposalStatus::Rejected
+            | ProposalStatus::Vetoed
+            | ProposalStatus::TieBreakPending
+            | ProposalStatus::PendingFinalization => return Err(Error::DepositLocked),
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Deposit(proposal_id));
+        token::TokenClient::new(&env, &deposit.token).transfer(
+            &env.current_contract_address(),
+            &proposer,
+            &deposit.amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("refund"), EVENT_VERSION),
+            (proposal_id, proposer, deposit.amount),
+        );
+
+        Ok(deposit.amount)
+    }
+
+    /// Settle a batch of proposals whose voting period has ended, and mark
+    /// passed proposals whose execution window has lapsed as
+    /// `ExpiredUnexecuted`.
+    ///
+    /// Ended proposals are tallied exactly as `finalize` would, so only those
+    /// short of quorum become `Expired` and losing proposals still forfeit
+    /// their deposit. Other IDs are skipped. Returns the number swept.
+    pub fn sweep_expired(env: Env, caller: Address, ids: Vec<u64>) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &caller)?;
+
+        caller.require_auth();
+
+        if ids.len() > MAX_SWEEP_BATCH {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut swept: u32 = 0;
+        for proposal_id in ids.iter() {
+            let key = DataKey::Proposal(proposal_id);
+            let Some(mut proposal) = env.storage().persistent().get::<DataKey, Proposal>(&key)
+            else {
+                continue;
+            };
+            if proposal.status == ProposalStatus::Passed
+                && Self::execution_window_lapsed(&env, proposal_id)
+            {
+                proposal.status = ProposalStatus::ExpiredUnexecuted;
+                env.storage().persistent().set(&key, &proposal);
+            } else if proposal.status == ProposalStatus::Active
+                && Self::clock_now(&env, &proposal.clock) > proposal.ends_at
+            {
+                let (status, quorum_threshold) = Self::tally(&env, &proposal)?;
+                Self::conclude(&env, &mut proposal, status, quorum_threshold)?;
+            } else {
+                continue;
+            }
+            swept += 1;
+        }
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("sweep"), EVENT_VERSION),
+            swept,
+        );
+
+        Ok(swept)
+    }
+
+    /// Execute a passed proposal.
+    /// Only the admin or proposer can execute.
+    pub fn execute_proposal(env: Env, executor: Address, proposal_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        stellar_guard_pausable::require_not_paused(&env)?;
+        Self::lock(&env)?;
+
+        executor.require_auth();
+
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        if proposal.status == ProposalStatus::Executed {
+            return Err(Error::AlreadyExecuted);
+        }
+        if proposal.status == ProposalStatus::Cancelled {
+            return Err(Error::ProposalCancelled);
+        }
+        if proposal.status == ProposalStatus::Vetoed {
+            return Err(Error::ProposalVetoed);
+        }
+
+        if proposal.status != ProposalStatus::Passed {
+            return Err(Error::ProposalRejected);
+        }
+
+        // Only admin or proposer can execute
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if executor != admin && executor != proposal.proposer {
+            return Err(Error::Unauthorized);
+        }
+
+        // Give members time to react before a passed proposal takes effect
+        if env.ledger().sequence() < proposal.executable_at {
+            return Err(Error::TimelockActive);
+        }
+        if Self::execution_window_lapsed(&env, proposal_id) {
+            return Err(Error::ExecutionWindowExpired);
+        }
+
+        // Handle member add/remove actions
+        match proposal.action.clone() {
+            ProposalAction::AddMember => {
+                Self::internal_add_member(&env, &proposal.target)?;
+            }
+            ProposalAction::RemoveMember => {
+                Self::internal_remove_member(&env, &proposal.target)?;
+            }
+            ProposalAction::Funding => {
+                // Without a configured treasury, funding is handled externally
+                if let Some(treasury) = env
+                    .storage()
+                    .instance()
+                    .get::<DataKey, Address>(&DataKey::Treasury)
+                {
+                    let result = TreasuryClient::new(&env, &treasury).try_release_funds(
+                        &env.current_contract_address(),
+                        &proposal.target,
+                        &proposal.amount,
+                    );
+                    if !matches!(result, Ok(Ok(()))) {
+                        return Err(Error::FundingFailed);
+                    }
+                }
+            }
+            ProposalAction::UpgradeContract(target, new_wasm_hash) => {
+                let result = UpgradeableClient::new(&env, &target)
+                    .try_upgrade(&env.current_contract_address(), &new_wasm_hash);
+                if !matches!(result, Ok(Ok(()))) {
+                    return Err(Error::UpgradeFailed);
+                }
+            }
+            _ => {
+                // Policy changes and general proposals are handled externally
+            }
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("exec"), EVENT_VERSION),
+            (proposal_id, executor.clone()),
+        );
+
+        Self::unlock(&env);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Member Management (Internal)
+    // ========================================================================
+
+    fn internal_add_member(env: &Env, member: &Address) -> Result<(), Error> {
+        if Self::is_zero_address(env, member) {
+            return Err(Error::InvalidProposal);
+        }
+        if let Some(access_control) = Self::access_control(env) {
+            if Self::is_member(env, member) {
+                return Err(Error::MemberAlreadyExists);
+            }
+            let result = AccessControlClient::new(env, &access_control).try_assign_role(
+                &env.current_contract_address(),
+                member,
+                &AccessRole::Member,
+            );
+            if !matches!(result, Ok(Ok(()))) {
+                return Err(Error::AccessControlFailed);
+            }
+            return Ok(());
+        }
+
+        let mut members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        // Check not already a member
+        for i in 0..members.len() {
+            if members.get(i).unwrap() == *member {
+                return Err(Error::MemberAlreadyExists);
+            }
+        }
+
+        members.push_back(member.clone());
+        env.storage().instance().set(&DataKey::Members, &members);
+        Ok(())
+    }
+
+    fn internal_remove_member(env: &Env, member: &Address) -> Result<(), Error> {
+        Self::release_delegations(env, member);
+
+        if let Some(access_control) = Self::access_control(env) {
+            let result = AccessControlClient::new(env, &access_control)
+                .try_revoke_role(&env.current_contract_address(), member);
+            if !matches!(result, Ok(Ok(()))) {
+                return Err(Error::AccessControlFailed);
+            }
+            return Ok(());
+        }
+
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        let mut new_members = Vec::new(env);
+        for i in 0..members.len() {
+            let m = members.get(i).unwrap();
+            if m != *member {
+                new_members.push_back(m);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Members, &new_members);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get a proposal by its ID.
+    ///
+    /// An active proposal whose voting period has ended is reported as
+    /// `PendingFinalization` until `finalize` runs, and a passed proposal
+    /// whose execution window has lapsed as `ExpiredUnexecuted` until it is
+    /// swept.
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+        if proposal.status == ProposalStatus::Active
+            && Self::clock_now(&env, &proposal.clock) > proposal.ends_at
+        {
+            proposal.status = ProposalStatus::PendingFinalization;
+        } else if proposal.status == ProposalStatus::Passed
+            && Self::execution_window_lapsed(&env, proposal_id)
+        {
+            proposal.status = ProposalStatus::ExpiredUnexecuted;
+        }
+        Ok(proposal)
+    }
+
+    /// Check whether a proposal is still accepting votes: active and within
+    /// its voting period. False for unknown proposals.
+    pub fn is_active(env: Env, proposal_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Proposal>(&DataKey::Proposal(proposal_id))
+            .is_some_and(|proposal| Self::require_voting_open(&env, &proposal).is_ok())
+    }
+
+    /// Summarize a proposal's outcome using the same rules as `finalize`.
+    ///
+    /// For an active proposal the status is projected from the current
+    /// tallies, as if voting closed now.
+    pub fn get_proposal_result(env: Env, proposal_id: u64) -> Result<ProposalResult, Error> {
+        let proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        let (_, quorum_threshold) = Self::quorum(&env, proposal_id)?;
+        let quorum_met = proposal.total_votes as u64 >= quorum_threshold;
+        let status = if proposal.status != ProposalStatus::Active {
+            proposal.status
+        } else if !quorum_met || (proposal.votes_for == 0 && proposal.votes_against == 0) {
+            ProposalStatus::Expired
+        } else {
+            Self::outcome(
+                &env,
+                &proposal.action,
+                proposal.votes_for as u64,
+                proposal.votes_against as u64,
+            )
+        };
+
+        Ok(ProposalResult {
+            passed: matches!(status, ProposalStatus::Passed | ProposalStatus::Executed),
+            quorum_met,
+            quorum_threshold,
+            final_or_projected_status: status,
+        })
+    }
+
+    /// List proposals with pagination, optionally filtered by status.
+    ///
+    /// Scans IDs from `start_id` up to the latest proposal and returns at most
+    /// `limit` records. To fetch the next page, pass `next_cursor` as
+    /// `start_id`.
+    pub fn list_proposals(
+        env: Env,
+        status: Option<ProposalStatus>,
+        start_id: u64,
+        limit: u32,
+    ) -> ProposalPage {
+        let mut page = ProposalPage {
+            items: Vec::new(&env),
+            next_cursor: None,
+            has_more: false,
+        };
+        if limit == 0 {
+            return page;
+        }
+
+        let max_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+
+        let mut current_id = start_id.max(1);
+        while current_id <= max_id {
+            if let Some(proposal) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Proposal>(&DataKey::Proposal(current_id))
+            {
+                if status.as_ref().is_none_or(|s| proposal.status == *s) {
+                    // One match past the limit means another page exists
+                    if page.items.len() >= limit {
+                        page.next_cursor = Some(current_id);
+                        page.has_more = true;
+                        break;
+                    }
+                    page.items.push_back(proposal);
+                }
+            }
+            current_id = current_id.saturating_add(1);
+        }
+
+        page
+    }
+
+    /// List the IDs of proposals created by `proposer`, oldest first.
+    ///
+    /// Returns at most `limit` IDs, capped at `MAX_PROPOSER_PAGE`, starting
+    /// from the `start`-th proposal they created.
+    pub fn get_proposals_by_proposer(
+        env: Env,
+        proposer: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let proposal_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposerIndex(proposer))
+            .unwrap_or(Vec::new(&env));
+        let start = start.min(proposal_ids.len());
+        let end = start
+            .saturating_add(limit.min(MAX_PROPOSER_PAGE))
+            .min(proposal_ids.len());
+        proposal_ids.slice(start..end)
+    }
+
+    /// Get governance configuration.
+    pub fn get_config(env: Env) -> Result<GovConfig, Error> {
+        Self::require_initialized(&env)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        let members = Self::current_members(&env);
+        let quorum_percent: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::QuorumPercent)
+            .unwrap_or(50);
+        let voting_period: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(1000);
+        let proposal_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let total_voting_weight = Self::total_voting_weight(&env, &members)?;
+        let execution_delay: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExecutionDelay)
+            .unwrap_or(0);
+        let execution_window: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExecutionWindow)
+            .unwrap_or(0);
+        let voting_clock = Self::voting_clock(&env);
+        let proposal_deposit = env
+            .storage()
+            .instance()
+            .get::<DataKey, DepositConfig>(&DataKey::DepositConfig)
+            .map_or(0, |config| config.amount);
+        let treasury: Option<Address> = env.storage().instance().get(&DataKey::Treasury);
+        let access_control = Self::access_control(&env);
+        let early_finalize: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::EarlyFinalize)
+            .unwrap_or(false);
+        let proposal_cooldown: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCooldown)
+            .unwrap_or(0);
+        let max_active_proposals: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxActiveProposals)
+            .unwrap_or(0);
+        let tie_break = Self::tie_break(&env);
+
+        Ok(GovConfig {
+            admin,
+            member_count: members.len(),
+            quorum_percent,
+            voting_period,
+            proposal_count,
+            total_voting_weight,
+            execution_delay,
+            execution_window,
+            voting_clock,
+            proposal_deposit,
+            treasury,
+            access_control,
+            early_finalize,
+            rejection_threshold: env
+                .storage()
+                .instance()
+                .get(&DataKey::RejectionThreshold)
+                .unwrap_or(0),
+            proposal_cooldown,
+            max_active_proposals,
+            tie_break,
+        })
+    }
+
+    /// Get the list of DAO members.
+    pub fn get_members(env: Env) -> Vec<Address> {
+        Self::current_members(&env)
+    }
+
+    /// Get the number of proposals a member has voted on themselves.
+    /// Votes cast for them by a delegate do not count, and retracted votes
+    /// are removed.
+    pub fn get_participation(env: Env, member: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ParticipationCount(member))
+            .unwrap_or(0)
+    }
+
+    /// Get the number of proposals a member was eligible to vote on, for
+    /// use with `get_participation` as a participation rate.
+    pub fn get_proposals_seen(env: Env, member: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProposalsSeen(member))
+            .unwrap_or(0)
+    }
+
+    /// Get the voting weight of an address (1 unless configured otherwise).
+    pub fn get_member_weight(env: Env, member: Address) -> u32 {
+        Self::member_weight(&env, &member)
+    }
+
+    /// Get the quadratic voting credits a member has left.
+    pub fn get_credits(env: Env, member: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Credits(member))
+            .unwrap_or(0)
+    }
+
+    /// Get the delegate a member has assigned their voting power to, if any.
+    pub fn get_delegate(env: Env, member: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Delegation(member))
+    }
+
+    /// Get the members that have delegated their voting power to `delegate`.
+    pub fn get_delegators(env: Env, delegate: Address) -> Vec<Address> {
+        Self::delegators_of(&env, &delegate)
+    }
+
+    /// Check if an address has voted on a proposal.
+    pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Vote(proposal_id, voter))
+    }
+
+    /// Roll-call of a proposal: every address with a recorded vote and its
+    /// choice, in voting order. Delegators appear with their delegate's choice.
+    pub fn get_voters(env: Env, proposal_id: u64) -> Vec<(Address, VoteChoice)> {
+        let voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VoterList(proposal_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut roll_call = Vec::new(&env);
+        for voter in voters.iter() {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, VoteRecord>(&DataKey::Vote(proposal_id, voter.clone()))
+            {
+                roll_call.push_back((voter, record.choice));
+            }
+        }
+        roll_call
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Transfer admin role to a new address.
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if current_admin != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        current_admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("admin"), EVENT_VERSION),
+            (current_admin, new_admin.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Update the quorum percentage. Admin only.
+    pub fn set_quorum(env: Env, admin: Address, new_quorum: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        // Validate quorum range (1-100)
+        if !(1..=100).contains(&new_quorum) {
+            return Err(Error::InvalidProposal);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::QuorumPercent, &new_quorum);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("quorum"), EVENT_VERSION),
+            new_quorum,
+        );
+
+        Ok(())
+    }
+
+    /// Update the execution delay, in ledger sequences. Admin only.
+    ///
+    /// Applies to proposals finalized after the change.
+    pub fn set_execution_delay(env: Env, admin: Address, delay: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ExecutionDelay, &delay);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("delay"), EVENT_VERSION),
+            delay,
+        );
+
+        Ok(())
+    }
+
+    /// Update the execution window, in ledger sequences counted from
+    /// finalize, after which a passed proposal can no longer be executed.
+    /// Zero lets passed proposals wait indefinitely. Admin only.
+    ///
+    /// Applies to proposals finalized after the change. The window includes
+    /// the execution delay.
+    pub fn set_execution_window(env: Env, admin: Address, window: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ExecutionWindow, &window);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("exec_win"),
+                EVENT_VERSION,
+            ),
+            window,
+        );
+
+        Ok(())
+    }
+
+    /// Update the default voting period for new proposals, in the current
+    /// voting clock's units. Admin only.
+    ///
+    /// # Errors
+    /// * `Error::InvalidVotingPeriod` - If the period is outside the bounds
+    ///   for the voting clock.
+    pub fn set_voting_period(env: Env, admin: Address, voting_period: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        Self::validate_voting_period(&Self::voting_clock(&env), voting_period)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::VotingPeriod, &voting_period);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("period"), EVENT_VERSION),
+            voting_period,
+        );
+
+        Ok(())
+    }
+
+    /// Update the proposal cooldown, in ledger sequences. Admin only.
+    ///
+    /// A member must wait this many ledgers after creating a proposal
+    /// before creating another. The admin is exempt.
+    pub fn set_proposal_cooldown(env: Env, admin: Address, cooldown: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCooldown, &cooldown);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("cooldown"),
+                EVENT_VERSION,
+            ),
+            cooldown,
+        );
+
+        Ok(())
+    }
+
+    /// Cap how many proposals may be active at once. Admin only.
+    ///
+    /// Finalizing, sweeping or cancelling a proposal frees its slot. A cap of
+    /// zero removes the limit.
+    pub fn set_max_active_proposals(env: Env, admin: Address, max: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxActiveProposals, &max);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("max_act"),
+                EVENT_VERSION,
+            ),
+            max,
+        );
+
+        Ok(())
+    }
+
+    /// Choose how `finalize` settles a tied vote. Admin only.
+    ///
+    /// Ties are rejected unless configured otherwise. Only ties that miss
+    /// the approval threshold are affected.
+    pub fn set_tie_break(env: Env, admin: Address, policy: TieBreak) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::TieBreak, &policy);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("tie"), EVENT_VERSION),
+            policy,
+        );
+
+        Ok(())
+    }
+
+    /// Switch between ledger-sequence and timestamp voting windows. Admin only.
+    ///
+    /// Applies to proposals created after the change; existing proposals keep
+    /// the clock they were created with. The default and per-action voting
+    /// periods are converted to the new clock's units at `SECONDS_PER_LEDGER`
+    /// seconds per ledger.
+    pub fn set_voting_clock(env: Env, admin: Address, clock: VotingClock) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let previous = Self::voting_clock(&env);
+        if let Some(voting_period) = env
+            .storage()
+            .instance()
+            .get::<DataKey, u32>(&DataKey::VotingPeriod)
+        {
+            let voting_period = Self::convert_voting_period(voting_period, &previous, &clock);
+            env.storage()
+                .instance()
+                .set(&DataKey::VotingPeriod, &voting_period);
+        }
+        if let Some(periods) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Map<ProposalAction, u32>>(&DataKey::ActionVotingPeriods)
+        {
+            let mut converted = Map::new(&env);
+            for (action, voting_period) in periods.iter() {
+                converted.set(
+                    action,
+                    Self::convert_voting_period(voting_period, &previous, &clock),
+                );
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::ActionVotingPeriods, &converted);
+        }
+        env.storage().instance().set(&DataKey::VotingClock, &clock);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("clock"), EVENT_VERSION),
+            clock,
+        );
+
+        Ok(())
+    }
+
+    /// Configure the deposit proposers must lock. Admin only.
+    ///
+    /// An `amount` of zero disables the deposit for new proposals. Deposits
+    /// forfeited by rejected proposals are sent to `treasury`.
+    pub fn set_proposal_deposit(
+        env: Env,
+        admin: Address,
+        token: Address,
+        amount: i128,
+        treasury: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        if amount < 0 {
+            return Err(Error::InvalidProposal);
+        }
+
+        env.storage().instance().set(
+            &DataKey::DepositConfig,
+            &DepositConfig {
+                token,
+                amount,
+                treasury,
+            },
+        );
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("deposit"),
+                EVENT_VERSION,
+            ),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Set per-member voting weights. Admin only.
+    ///
+    /// Replaces any previously configured weights. Members missing from
+    /// `weights` fall back to a weight of 1, so an empty map restores
+    /// one-member-one-vote.
+    pub fn set_member_weights(
+        env: Env,
+        admin: Address,
+        weights: Map<Address, u32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        for (_, weight) in weights.iter() {
+            if weight == 0 {
+                return Err(Error::InvalidWeight);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MemberWeights, &weights);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("weights"),
+                EVENT_VERSION,
+            ),
+            weights.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Set the treasury contract that pays out funding proposals. Admin only.
+    ///
+    /// The treasury must in turn list this contract as its governance
+    /// contract for `execute_proposal` to release funds.
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("treasury"),
+                EVENT_VERSION,
+            ),
+            treasury,
+        );
+
+        Ok(())
+    }
+
+    /// Use an access-control contract as the source of truth for membership.
+    /// Admin only.
+    ///
+    /// Once set, any address holding at least the Member role there is a
+    /// member here and the local member list is ignored. Executing
+    /// `AddMember`/`RemoveMember` proposals assigns or revokes that role, so
+    /// this contract must hold the Admin role in the access-control contract.
+    pub fn set_access_control(
+        env: Env,
+        admin: Address,
+        access_control: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AccessControl, &access_control);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("acl"), EVENT_VERSION),
+            access_control,
+        );
+
+        Ok(())
+    }
+
+    /// Enable or disable early finalization. Admin only.
+    ///
+    /// When enabled, `vote` marks a proposal `Passed` or `Rejected` as soon as
+    /// the weight that has not voted yet can no longer change the outcome,
+    /// instead of waiting for the voting period to end. Votes can no longer
+    /// be changed or retracted once a proposal has been decided this way.
+    pub fn set_early_finalize(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EarlyFinalize, &enabled);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("early"), EVENT_VERSION),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Set the share of total voting weight, in percent, whose nay votes
+    /// reject a proposal as soon as they are cast, without waiting for the
+    /// voting period to end. Zero disables early rejection. Admin only.
+    ///
+    /// # Errors
+    /// * `Error::InvalidProposal` - If the threshold is above 100.
+    pub fn set_rejection_threshold(env: Env, admin: Address, percent: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        if percent > 100 {
+            return Err(Error::InvalidProposal);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RejectionThreshold, &percent);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("reject_th"),
+                EVENT_VERSION,
+            ),
+            percent,
+        );
+
+        Ok(())
+    }
+
+    /// Pause proposing, voting, delegation, finalization and execution,
+    /// e.g. while a suspected key compromise is investigated. Admin
+    /// functions and queries keep working. Admin only.
+    pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::set_paused(&env, &admin, true)
+    }
+
+    /// Resume governance after a pause. Admin only.
+    pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::set_paused(&env, &admin, false)
+    }
+
+    /// Check whether governance is paused.
+    pub fn is_paused(env: Env) -> bool {
+        stellar_guard_pausable::is_paused(&env)
+    }
+
+    /// Suspend a member's voting rights, e.g. pending an investigation.
+    /// The member stays in the DAO but cannot vote, and delegates stop
+    /// carrying their weight. Admin only.
+    pub fn suspend_voting(env: Env, admin: Address, member: Address) -> Result<(), Error> {
+        Self::set_voting_suspended(&env, &admin, &member, true)
+    }
+
+    /// Restore a member's suspended voting rights. Admin only.
+    pub fn restore_voting(env: Env, admin: Address, member: Address) -> Result<(), Error> {
+        Self::set_voting_suspended(&env, &admin, &member, false)
+    }
+
+    /// Check whether a member's voting rights are suspended.
+    pub fn is_voting_suspended(env: Env, member: Address) -> bool {
+        Self::require_voting_rights(&env, &member).is_err()
+    }
+
+    /// Set a member's quadratic voting credits for the current epoch.
+    /// Admin only.
+    ///
+    /// Replaces any credits left over from the previous epoch.
+    pub fn grant_credits(
+        env: Env,
+        admin: Address,
+        member: Address,
+        credits: u32,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+        Self::require_member(&env, &member)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Credits(member.clone()), &credits);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("credits"),
+                EVENT_VERSION,
+            ),
+            (member, credits),
+        );
+
+        Ok(())
+    }
+
+    /// Set per-action approval thresholds. Admin only.
+    ///
+    /// Each threshold is the minimum share of yea weight, as a percentage of
+    /// the yea and nay weight cast, a proposal of that action needs to pass.
+    /// Replaces any previously configured thresholds; actions missing from
+    /// `thresholds` fall back to a simple majority.
+    pub fn set_action_thresholds(
+        env: Env,
+        admin: Address,
+        thresholds: Map<ProposalAction, u32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        for (_, percent) in thresholds.iter() {
+            if !(1..=100).contains(&percent) {
+                return Err(Error::InvalidProposal);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ActionThresholds, &thresholds);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("thresh"), EVENT_VERSION),
+            thresholds.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Get the approval threshold configured for an action, if any.
+    pub fn get_action_threshold(env: Env, action: ProposalAction) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get::<DataKey, Map<ProposalAction, u32>>(&DataKey::ActionThresholds)
+            .and_then(|thresholds| thresholds.get(action))
+    }
+
+    /// Set per-action voting periods. Admin only.
+    ///
+    /// New proposals of a listed action vote for its period instead of the
+    /// global one, so weightier changes can get longer deliberation.
+    /// Replaces any previously configured periods.
+    ///
+    /// # Errors
+    /// * `Error::InvalidVotingPeriod` - If a period is outside the bounds for
+    ///   the voting clock.
+    pub fn set_action_voting_periods(
+        env: Env,
+        admin: Address,
+        periods: Map<ProposalAction, u32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let clock = Self::voting_clock(&env);
+        for (_, voting_period) in periods.iter() {
+            Self::validate_voting_period(&clock, voting_period)?;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ActionVotingPeriods, &periods);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("periods"),
+                EVENT_VERSION,
+            ),
+            periods.len(),
+        );
+
+        Ok(())
+    }
+
+    /// Get the voting period configured for an action, if any.
+    pub fn get_action_voting_period(env: Env, action: ProposalAction) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get::<DataKey, Map<ProposalAction, u32>>(&DataKey::ActionVotingPeriods)
+            .and_then(|periods| periods.get(action))
+    }
+
+    /// Bring data written by an older version up to the current layout.
+    /// Admin only.
+    ///
+    /// After an upgrade that changes the layout, every entry point that
+    /// checks initialization fails with `Error::MigrationRequired` until
+    /// this has run. Data with no stored version was written by the original
+    /// contract, whose proposals and votes use a different layout, and is
+    /// refused rather than misread.
+    ///
+    /// # Returns
+    /// The version the data is now at.
+    ///
+    /// # Errors
+    /// * `Error::UnsupportedVersion` - If the data predates versioning.
+    /// * `Error::AlreadyMigrated` - If the data is already current.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let from = Self::stored_version(&env);
+        if from == 0 {
+            return Err(Error::UnsupportedVersion);
+        }
+        if from >= VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+        if from < 2 {
+            Self::migrate_quorum_reached(&env)?;
+        }
+        env.storage().instance().set(&DataKey::Version, &VERSION);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("migrate"),
+                EVENT_VERSION,
+            ),
+            (from, VERSION),
+        );
+
+        Ok(VERSION)
+    }
+
+    /// Upgrade contract WASM. Admin only.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    /// Get the version of the deployed contract code.
+    pub fn get_version(_env: Env) -> u32 {
+        VERSION
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    /// Add or remove one of `voter`'s own votes from their participation.
+    fn count_participation(env: &Env, voter: &Address, voted: bool) {
+        let key = DataKey::ParticipationCount(voter.clone());
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let count = if voted {
+            count.saturating_add(1)
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage().persistent().set(&key, &count);
+    }
+
+    /// Whether a passed proposal's execution window has run out.
+    fn execution_window_lapsed(env: &Env, proposal_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::ExecutionDeadline(proposal_id))
+            .is_some_and(|deadline| env.ledger().sequence() > deadline)
+    }
+
+    fn require_initialized(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+        if Self::stored_version(env) < VERSION {
+            return Err(Error::MigrationRequired);
+        }
+        Ok(())
+    }
+
+    /// Version of the stored data; 0 for data written before versioning.
+    fn stored_version(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::Version).unwrap_or(0)
+    }
+
+    /// Version 2: add `quorum_reached` to proposals stored without it,
+    /// which cannot be read as a `Proposal` until then.
+    fn migrate_quorum_reached(env: &Env) -> Result<(), Error> {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let field = Symbol::new(env, "quorum_reached");
+        let mut active_count: u32 = 0;
+        for id in 1..=count {
+            let key = DataKey::Proposal(id);
+            let record: Option<Map<Symbol, Val>> = env.storage().persistent().get(&key);
+            let Some(mut record) = record else {
+                continue;
+            };
+            // Version 1 counted active proposals by scanning them all
+            if record
+                .get(Symbol::new(env, "status"))
+                .and_then(|status| ProposalStatus::try_from_val(env, &status).ok())
+                .is_some_and(|status| status == ProposalStatus::Active)
+            {
+                active_count += 1;
+            }
+            if record.contains_key(field.clone()) {
+                continue;
+            }
+
+            record.set(field.clone(), false.into_val(env));
+            env.storage().persistent().set(&key, &record);
+
+            let mut proposal: Proposal = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .ok_or(Error::ProposalNotFound)?;
+            let (_, quorum_threshold) = Self::quorum(env, id)?;
+            proposal.quorum_reached = proposal.total_votes as u64 >= quorum_threshold;
+            env.storage().persistent().set(&key, &proposal);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveCount, &active_count);
+        Ok(())
+    }
+
+    fn member_weight(env: &Env, member: &Address) -> u32 {
+        let weights: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::MemberWeights)
+            .unwrap_or(Map::new(env));
+        weights.get(member.clone()).unwrap_or(1)
+    }
+
+    fn live_voting_weights(env: &Env) -> Map<Address, u32> {
+        let mut weights = Map::new(env);
+        for member in Self::current_members(env).iter() {
+            let weight = Self::member_weight(env, &member);
+            weights.set(member, weight);
+        }
+        weights
+    }
+
+    /// Members and weights eligible to vote on a proposal. Proposals created
+    /// before snapshots existed fall back to the live member set.
+    fn voting_snapshot(env: &Env, proposal_id: u64) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Snapshot(proposal_id))
+            .unwrap_or_else(|| Self::live_voting_weights(env))
+    }
+
+    fn require_snapshot_member(
+        env: &Env,
+        proposal_id: u64,
+        address: &Address,
+    ) -> Result<(), Error> {
+        if Self::voting_snapshot(env, proposal_id).contains_key(address.clone()) {
+            return Ok(());
+        }
+        Err(Error::NotAMember)
+    }
+
+    fn total_voting_weight(env: &Env, members: &Vec<Address>) -> Result<u32, Error> {
+        let mut total: u32 = 0;
+        for member in members.iter() {
+            total = total
+                .checked_add(Self::member_weight(env, &member))
+                .ok_or(Error::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    fn forfeit_deposit(env: &Env, proposal_id: u64) {
+        let key = DataKey::Deposit(proposal_id);
+        let Some(deposit) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ProposalDeposit>(&key)
+        else {
+            return;
+        };
+        let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, DepositConfig>(&DataKey::DepositConfig)
+        else {
+            return;
+        };
+
+        env.storage().persistent().remove(&key);
+        token::TokenClient::new(env, &deposit.token).transfer(
+            &env.current_contract_address(),
+            &config.treasury,
+            &deposit.amount,
+        );
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("forfeit"),
+                EVENT_VERSION,
+            ),
+            (proposal_id, deposit.amount),
+        );
+    }
+
+    /// Whether the yea weight clears the threshold for the proposal's action.
+    /// Actions without a configured threshold need a simple majority.
+    fn meets_approval_threshold(
+        env: &Env,
+        action: &ProposalAction,
+        votes_for: u64,
+        votes_against: u64,
+    ) -> bool {
+        let thresholds: Map<ProposalAction, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActionThresholds)
+            .unwrap_or(Map::new(env));
+        match thresholds.get(action.clone()) {
+            Some(percent) => votes_for * 100 >= percent as u64 * (votes_for + votes_against),
+            None => votes_for > votes_against,
+        }
+    }
+
+    /// Total snapshotted voting weight of a proposal and the weight needed
+    /// for quorum.
+    fn quorum(env: &Env, proposal_id: u64) -> Result<(u32, u64), Error> {
+        let quorum_percent: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::QuorumOverride(proposal_id))
+        {
+            Some(quorum_percent) => quorum_percent,
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::QuorumPercent)
+                .unwrap_or(50),
+        };
+
+        let mut total_weight: u32 = 0;
+        for (_, weight) in Self::voting_snapshot(env, proposal_id).iter() {
+            total_weight = total_weight.checked_add(weight).ok_or(Error::Overflow)?;
+        }
+        // Round up so a 50% quorum of 3 requires 2 votes, widening to u64 so
+        // the multiply cannot overflow.
+        let quorum_threshold = (total_weight as u64 * quorum_percent as u64).div_ceil(100);
+        Ok((total_weight, quorum_threshold))
+    }
+
+    /// Finalize an active proposal early once the weight that has not voted
+    /// yet can no longer change the outcome. Proposals with quadratic votes
+    /// are left to `finalize`.
+    fn conclude_if_decided(env: &Env, proposal: &mut Proposal) -> Result<(), Error> {
+        if Self::has_quadratic_votes(env, proposal.id) {
+            return Ok(());
+        }
+        let (total_weight, quorum_threshold) = Self::quorum(env, proposal.id)?;
+        let votes_for = proposal.votes_for as u64;
+        let votes_against = proposal.votes_against as u64;
+        if (proposal.total_votes as u64) < quorum_threshold || votes_for + votes_against == 0 {
+            return Ok(());
+        }
+
+        let remaining = (total_weight as u64).saturating_sub(proposal.total_votes as u64);
+        if Self::outcome(env, &proposal.action, votes_for, votes_against + remaining)
+            == ProposalStatus::Passed
+        {
+            Self::conclude(env, proposal, ProposalStatus::Passed, quorum_threshold)
+        } else if Self::outcome(env, &proposal.action, votes_for + remaining, votes_against)
+            == ProposalStatus::Rejected
+        {
+            Self::conclude(env, proposal, ProposalStatus::Rejected, quorum_threshold)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reject an active proposal straight away once its nay weight exceeds
+    /// the rejection threshold's share of the snapshotted voting weight.
+    /// Proposals with quadratic votes are left to `finalize`.
+    fn reject_if_opposed(env: &Env, proposal: &mut Proposal) -> Result<(), Error> {
+        let rejection_threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RejectionThreshold)
+            .unwrap_or(0);
+        if rejection_threshold == 0 || Self::has_quadratic_votes(env, proposal.id) {
+            return Ok(());
+        }
+
+        let (total_weight, quorum_threshold) = Self::quorum(env, proposal.id)?;
+        if proposal.votes_against as u64 * 100 > rejection_threshold as u64 * total_weight as u64 {
+            Self::conclude(env, proposal, ProposalStatus::Rejected, quorum_threshold)?;
+        }
+        Ok(())
+    }
+
+    /// Whether any vote on a proposal was cast with `vote_quadratic`, whose
+    /// tally is not bounded by the snapshotted voting weight.
+    fn has_quadratic_votes(env: &Env, proposal_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::QuadraticVoted(proposal_id))
+            .unwrap_or(false)
+    }
+
+    /// Final status of a proposal whose voting period has ended, and the
+    /// weight its quorum required.
+    fn tally(env: &Env, proposal: &Proposal) -> Result<(ProposalStatus, u64), Error> {
+        let (_, quorum_threshold) = Self::quorum(env, proposal.id)?;
+        // Abstentions count toward quorum, but a proposal with no yea or nay
+        // weight reached no decision and expires.
+        let status = if (proposal.total_votes as u64) < quorum_threshold
+            || (proposal.votes_for == 0 && proposal.votes_against == 0)
+        {
+            ProposalStatus::Expired
+        } else {
+            Self::outcome(
+                env,
+                &proposal.action,
+                proposal.votes_for as u64,
+                proposal.votes_against as u64,
+            )
+        };
+        Ok((status, quorum_threshold))
+    }
+
+    /// Outcome of a quorate vote: passed if it meets the approval threshold,
+    /// otherwise settled by the tie-break policy on an exact tie, otherwise
+    /// rejected.
+    fn outcome(
+        env: &Env,
+        action: &ProposalAction,
+        votes_for: u64,
+        votes_against: u64,
+    ) -> ProposalStatus {
+        if Self::meets_approval_threshold(env, action, votes_for, votes_against) {
+            return ProposalStatus::Passed;
+        }
+        if votes_for != votes_against {
+            return ProposalStatus::Rejected;
+        }
+        match Self::tie_break(env) {
+            TieBreak::Reject => ProposalStatus::Rejected,
+            TieBreak::Pass => ProposalStatus::Passed,
+            TieBreak::AdminDecides => ProposalStatus::TieBreakPending,
+        }
+    }
+
+    fn tie_break(env: &Env) -> TieBreak {
+        env.storage()
+            .instance()
+            .get(&DataKey::TieBreak)
+            .unwrap_or(TieBreak::Reject)
+    }
+
+    /// Flag a proposal the first time its turnout reaches quorum and tell
+    /// indexers it has become viable.
+    fn note_quorum(env: &Env, proposal: &mut Proposal) -> Result<(), Error> {
+        if proposal.quorum_reached {
+            return Ok(());
+        }
+        let (_, quorum_threshold) = Self::quorum(env, proposal.id)?;
+        if (proposal.total_votes as u64) < quorum_threshold {
+            return Ok(());
+        }
+
+        proposal.quorum_reached = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.id), proposal);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                Symbol::new(env, "quorum_hit"),
+                EVENT_VERSION,
+            ),
+            (
+                proposal.id,
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.abstentions,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Record the outcome of a proposal and emit the finalize event with the
+    /// final tallies, so subscribers need not fetch the proposal.
+    fn conclude(
+        env: &Env,
+        proposal: &mut Proposal,
+        status: ProposalStatus,
+        quorum_threshold: u64,
+    ) -> Result<(), Error> {
+        match status {
+            ProposalStatus::Passed => {
+                let execution_delay: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::ExecutionDelay)
+                    .unwrap_or(0);
+                proposal.executable_at = env
+                    .ledger()
+                    .sequence()
+                    .checked_add(execution_delay)
+                    .ok_or(Error::Overflow)?;
+
+                let execution_window: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::ExecutionWindow)
+                    .unwrap_or(0);
+                if execution_window > 0 {
+                    env.storage().persistent().set(
+                        &DataKey::ExecutionDeadline(proposal.id),
+                        &env.ledger().sequence().saturating_add(execution_window),
+                    );
+                }
+            }
+            ProposalStatus::Rejected => Self::forfeit_deposit(env, proposal.id),
+            _ => {}
+        }
+        if proposal.status == ProposalStatus::Active {
+            Self::release_active_slot(env);
+        }
+        proposal.status = status.clone();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.id), proposal);
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("finalize"),
+                EVENT_VERSION,
+            ),
+            (
+                proposal.id,
+                status,
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.total_votes,
+                quorum_threshold,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Rate-limit proposers other than the admin, recording this proposal
+    /// as their latest.
+    fn check_cooldown(env: &Env, proposer: &Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *proposer == admin {
+            return Ok(());
+        }
+
+        let cooldown: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCooldown)
+            .unwrap_or(0);
+        let now = env.ledger().sequence();
+        let last_key = DataKey::LastProposal(proposer.clone());
+        if let Some(last) = env.storage().persistent().get::<DataKey, u32>(&last_key) {
+            if now < last.saturating_add(cooldown) {
+                return Err(Error::CooldownActive);
+            }
+        }
+        env.storage().persistent().set(&last_key, &now);
+        Ok(())
+    }
+
+    /// Validate and store a new proposal, snapshot the electorate and lock
+    /// the proposer's deposit. Callers check who may propose.
+    fn open_proposal(
+        env: &Env,
+        proposer: &Address,
+        title: String,
+        description: BytesN<32>,
+        action: ProposalAction,
+        amount: i128,
+        target: Address,
+    ) -> Result<u64, Error> {
+        Self::validate_proposal(env, &title, &description, amount)?;
+        if action == ProposalAction::AddMember && Self::is_zero_address(env, &target) {
+            return Err(Error::InvalidProposal);
+        }
+        let max_active: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxActiveProposals)
+            .unwrap_or(0);
+        let active_count = Self::active_proposal_count(env);
+        if max_active > 0 && active_count >= max_active {
+            return Err(Error::TooManyActive);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveCount, &(active_count + 1));
+
+        // Get and increment counter with overflow protection
+        let current_counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let proposal_id = current_counter.checked_add(1).ok_or(Error::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCounter, &proposal_id);
+
+        // Calculate voting end
+        let voting_period: u32 = env
+            .storage()
+            .instance()
+            .get::<DataKey, Map<ProposalAction, u32>>(&DataKey::ActionVotingPeriods)
+            .and_then(|periods| periods.get(action.clone()))
+            .or_else(|| env.storage().instance().get(&DataKey::VotingPeriod))
+            .unwrap_or(1000);
+        let clock = Self::voting_clock(env);
+        let created_at = Self::clock_now(env, &clock);
+        let ends_at = created_at
+            .checked_add(voting_period as u64)
+            .ok_or(Error::Overflow)?;
+
+        let proposal = Proposal {
+            id: proposal_id,
+            title: title.clone(),
+            description,
+            action: action.clone(),
+            proposer: proposer.clone(),
+            votes_for: 0,
+            votes_against: 0,
+            abstentions: 0,
+            total_votes: 0,
+            status: ProposalStatus::Active,
+            clock,
+            created_at,
+            ends_at,
+            amount,
+            target: target.clone(),
+            executable_at: 0,
+            quorum_reached: false,
+        };
+
+        // Freeze the electorate so membership or weight changes made while
+        // voting is open cannot sway this proposal
+        let snapshot = Self::live_voting_weights(env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Snapshot(proposal_id), &snapshot);
+        for member in snapshot.keys().iter() {
+            let key = DataKey::ProposalsSeen(member);
+            let seen: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&key, &seen.saturating_add(1));
+        }
+
+        // Lock the anti-spam deposit, if one is configured
+        if let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, DepositConfig>(&DataKey::DepositConfig)
+        {
+            if config.amount > 0 {
+                token::TokenClient::new(env, &config.token).transfer(
+                    proposer,
+                    &env.current_contract_address(),
+                    &config.amount,
+                );
+                env.storage().persistent().set(
+                    &DataKey::Deposit(proposal_id),
+                    &ProposalDeposit {
+                        token: config.token,
+                        amount: config.amount,
+                    },
+                );
+            }
+        }
+
+        // Store proposal with error handling
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        let index_key = DataKey::ProposerIndex(proposer.clone());
+        let mut proposal_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(env));
+        proposal_ids.push_back(proposal_id);
+        env.storage().persistent().set(&index_key, &proposal_ids);
+
+        // Emit event with required payload
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("propose"),
+                EVENT_VERSION,
+            ),
+            (
+                proposal_id,
+                proposer.clone(),
+                ends_at,
+                target.clone(),
+                amount,
+                proposal.description,
+            ),
+        );
+
+        log!(env, "Proposal #{} created by {:?}", proposal_id, proposer);
+        Ok(proposal_id)
+    }
+
+    fn validate_proposal(
+        env: &Env,
+        title: &String,
+        description: &BytesN<32>,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if *title == String::from_str(env, "") {
+            return Err(Error::InvalidProposal);
+        }
+        if *description == BytesN::from_array(env, &[0; 32]) {
+            return Err(Error::InvalidProposal);
+        }
+        if amount < 0 {
+            return Err(Error::InvalidProposal);
+        }
+        Ok(())
+    }
+
+    fn voting_clock(env: &Env) -> VotingClock {
+        env.storage()
+            .instance()
+            .get(&DataKey::VotingClock)
+            .unwrap_or(VotingClock::Ledger)
+    }
+
+    /// Shortest and longest voting period allowed on a clock.
+    fn voting_period_bounds(clock: &VotingClock) -> (u32, u32) {
+        match clock {
+            VotingClock::Ledger => (MIN_VOTING_PERIOD, MAX_VOTING_PERIOD),
+            VotingClock::Timestamp => (
+                MIN_VOTING_PERIOD * SECONDS_PER_LEDGER,
+                MAX_VOTING_PERIOD * SECONDS_PER_LEDGER,
+            ),
+        }
+    }
+
+    fn validate_voting_period(clock: &VotingClock, voting_period: u32) -> Result<(), Error> {
+        let (min_period, max_period) = Self::voting_period_bounds(clock);
+        if !(min_period..=max_period).contains(&voting_period) {
+            return Err(Error::InvalidVotingPeriod);
+        }
+        Ok(())
+    }
+
+    /// Express a voting period measured on one clock in the units of another.
+    fn convert_voting_period(period: u32, from: &VotingClock, to: &VotingClock) -> u32 {
+        match (from, to) {
+            (VotingClock::Ledger, VotingClock::Timestamp) => period * SECONDS_PER_LEDGER,
+            (VotingClock::Timestamp, VotingClock::Ledger) => period.div_ceil(SECONDS_PER_LEDGER),
+            _ => period,
+        }
+    }
+
+    fn active_proposal_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActiveCount)
+            .unwrap_or(0)
+    }
+
+    /// Free the slot of a proposal leaving the `Active` status.
+    fn release_active_slot(env: &Env) {
+        let count = Self::active_proposal_count(env).saturating_sub(1);
+        env.storage().instance().set(&DataKey::ActiveCount, &count);
+    }
+
+    fn is_zero_address(env: &Env, address: &Address) -> bool {
+        ZERO_ADDRESSES
+            .iter()
+            .any(|zero| *address == Address::from_string(&String::from_str(env, zero)))
+    }
+
+    fn add_to_tally(
+        proposal: &mut Proposal,
+        choice: &VoteChoice,
+        weight: u32,
+    ) -> Result<(), Error> {
+        let tally = match choice {
+            VoteChoice::Yea => &mut proposal.votes_for,
+            VoteChoice::Nay => &mut proposal.votes_against,
+            VoteChoice::Abstain => &mut proposal.abstentions,
+        };
+        *tally = tally.checked_add(weight).ok_or(Error::Overflow)?;
+        proposal.total_votes = proposal
+            .total_votes
+            .checked_add(weight)
+            .ok_or(Error::Overflow)?;
+        Ok(())
+    }
+
+    fn remove_from_tally(proposal: &mut Proposal, choice: &VoteChoice, weight: u32) {
+        match choice {
+            VoteChoice::Yea => proposal.votes_for -= weight,
+            VoteChoice::Nay => proposal.votes_against -= weight,
+            VoteChoice::Abstain => proposal.abstentions -= weight,
+        }
+        proposal.total_votes -= weight;
+    }
+
+    fn require_voting_open(env: &Env, proposal: &Proposal) -> Result<(), Error> {
+        if proposal.status == ProposalStatus::Cancelled {
+            return Err(Error::ProposalCancelled);
+        }
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::VotingClosed);
+        }
+        if Self::clock_now(env, &proposal.clock) > proposal.ends_at {
+            return Err(Error::VotingClosed);
+        }
+        Ok(())
+    }
+
+    fn clock_now(env: &Env, clock: &VotingClock) -> u64 {
+        match clock {
+            VotingClock::Ledger => env.ledger().sequence() as u64,
+            VotingClock::Timestamp => env.ledger().timestamp(),
+        }
+    }
+
+    fn delegators_of(env: &Env, delegate: &Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Delegators(delegate.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Drop every delegation to or from a departing member, emitting an
+    /// `undeleg` event for each, so no vote weight is routed through them.
+    fn release_delegations(env: &Env, member: &Address) {
+        let delegation_key = DataKey::Delegation(member.clone());
+        if let Some(delegate) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&delegation_key)
+        {
+            env.storage().persistent().remove(&delegation_key);
+            Self::remove_delegator(env, &delegate, member);
+            env.events().publish(
+                (
+                    symbol_short!("gov"),
+                    symbol_short!("undeleg"),
+                    EVENT_VERSION,
+                ),
+                (member.clone(), delegate),
+            );
+        }
+
+        for delegator in Self::delegators_of(env, member).iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Delegation(delegator.clone()));
+            env.events().publish(
+                (
+                    symbol_short!("gov"),
+                    symbol_short!("undeleg"),
+                    EVENT_VERSION,
+                ),
+                (delegator, member.clone()),
+            );
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegators(member.clone()));
+    }
+
+    fn remove_delegator(env: &Env, delegate: &Address, delegator: &Address) {
+        let delegators = Self::delegators_of(env, delegate);
+        let mut remaining = Vec::new(env);
+        for d in delegators.iter() {
+            if d != *delegator {
+                remaining.push_back(d);
+            }
+        }
+        if remaining.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Delegators(delegate.clone()));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Delegators(delegate.clone()), &remaining);
+        }
+    }
+
+    fn access_control(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AccessControl)
+    }
+
+    /// Suspend or restore a member's voting rights for `suspend_voting` and
+    /// `restore_voting`. Admin only.
+    fn set_voting_suspended(
+        env: &Env,
+        admin: &Address,
+        member: &Address,
+        suspended: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let key = DataKey::VotingSuspended(member.clone());
+        if suspended {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish(
+            (
+                symbol_short!("gov"),
+                symbol_short!("vote_susp"),
+                EVENT_VERSION,
+            ),
+            (member.clone(), suspended),
+        );
+
+        Ok(())
+    }
+
+    fn require_voting_rights(env: &Env, voter: &Address) -> Result<(), Error> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::VotingSuspended(voter.clone()))
+        {
+            return Err(Error::VotingSuspended);
+        }
+        Ok(())
+    }
+
+    /// Current members: addresses holding at least the Member role in the
+    /// access-control contract if one is configured, else the local list.
+    fn current_members(env: &Env) -> Vec<Address> {
+        let Some(access_control) = Self::access_control(env) else {
+            return env
+                .storage()
+                .instance()
+                .get(&DataKey::Members)
+                .unwrap_or(Vec::new(env));
+        };
+
+        let client = AccessControlClient::new(env, &access_control);
+        let mut members = Vec::new(env);
+        for address in client.get_all_members().iter() {
+            if client.has_permission(&address, &AccessRole::Member) {
+                members.push_back(address);
+            }
+        }
+        members
+    }
+
+    fn is_member(env: &Env, address: &Address) -> bool {
+        if let Some(access_control) = Self::access_control(env) {
+            return AccessControlClient::new(env, &access_control)
+                .has_permission(address, &AccessRole::Member);
+        }
+
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        for i in 0..members.len() {
+            if members.get(i).unwrap() == *address {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Reentrancy guard for `execute_proposal`, which calls out to the
+    /// treasury, token and access-control contracts. The Soroban host
+    /// already rejects re-entry into a running contract; this keeps the
+    /// invariant explicit in the contract itself. On an error return the
+    /// lock is rolled back with the rest of the call.
+    fn lock(env: &Env) -> Result<(), Error> {
+        if env
+            .storage()
+            .instance()
+            .get(&DataKey::Locked)
+            .unwrap_or(false)
+        {
+            return Err(Error::Reentrancy);
+        }
+        env.storage().instance().set(&DataKey::Locked, &true);
+        Ok(())
+    }
+
+    fn unlock(env: &Env) {
+        env.storage().instance().remove(&DataKey::Locked);
+    }
+
+    fn set_paused(env: &Env, admin: &Address, paused: bool) -> Result<(), Error> {
+        Self::require_initialized(env)?;
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        stellar_guard_pausable::set_paused(env, symbol_short!("gov"), EVENT_VERSION, admin, paused);
+
+        Ok(())
+    }
+
+    fn require_member(env: &Env, caller: &Address) -> Result<(), Error> {
+        if Self::is_member(env, caller) {
+            return Ok(());
+        }
+        Err(Error::NotAMember)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::Events as _;
+    use soroban_sdk::testutils::Ledger as _;
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{vec, Env, IntoVal, TryFromVal, Val};
+
+    fn setup_contract() -> (Env, Address, GovernanceContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, GovernanceContract);
+        let client = GovernanceContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        (env, admin, client)
+    }
+
+    fn text(env: &Env, value: &str) -> String {
+        String::from_str(env, value)
+    }
+
+    fn content_hash(env: &Env, value: &str) -> BytesN<32> {
+        env.crypto()
+            .sha256(&soroban_sdk::Bytes::from_slice(env, value.as_bytes()))
+            .to_bytes()
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let config = client.get_config();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.member_count, 2);
+        assert_eq!(config.quorum_percent, 50);
+        assert_eq!(config.voting_period, 1000);
+    }
+
+    #[test]
+    fn test_create_proposal_and_vote() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        // Create a funding proposal
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Fund development sprint"),
+            &content_hash(&env, "Allocate budget for the next engineering sprint"),
+            &ProposalAction::Funding,
+            &500_000,
+            &member1,
+            &None,
+        );
+        assert_eq!(proposal_id, 1);
+
+        // Vote
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 2);
+        assert_eq!(proposal.total_votes, 2);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+    }
+
+    #[test]
+    fn test_create_proposal_success() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        // Create a funding proposal
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Fund development sprint"),
+            &content_hash(&env, "Allocate budget for the next engineering sprint"),
+            &ProposalAction::Funding,
+            &500_000,
+            &member1,
+            &None,
+        );
+        assert_eq!(proposal_id, 1);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.id, 1);
+        assert_eq!(proposal.proposer, member1);
+        assert_eq!(proposal.title, text(&env, "Fund development sprint"));
+        assert_eq!(
+            proposal.description,
+            content_hash(&env, "Allocate budget for the next engineering sprint")
+        );
+        assert_eq!(proposal.action, ProposalAction::Funding);
+        assert_eq!(proposal.amount, 500_000);
+        assert_eq!(proposal.target, member1);
+        assert_eq!(proposal.votes_for, 0);
+        assert_eq!(proposal.votes_against, 0);
+        assert_eq!(proposal.total_votes, 0);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+
+        // Verify ends_at is calculated correctly (current_ledger + voting_period)
+        let current_ledger = env.ledger().sequence();
+        assert_eq!(proposal.ends_at, current_ledger as u64 + 1000);
+    }
+
+    #[test]
+    fn test_create_proposal_non_member_fails() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let non_member = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let result = client.try_create_proposal(
+            &non_member,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &non_member,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::NotAMember)));
+    }
+
+    #[test]
+    fn test_create_proposal_empty_title_fails() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let result = client.try_create_proposal(
+            &member1,
+            &text(&env, ""), // Empty title
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+    }
+
+    #[test]
+    fn test_create_proposal_empty_description_fails() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let result = client.try_create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &BytesN::from_array(&env, &[0; 32]), // Empty description hash
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+    }
+
+    #[test]
+    fn test_create_proposal_negative_amount_fails() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let result = client.try_create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::Funding,
+            &-100, // Negative amount
+            &member1,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+    }
+
+    #[test]
+    fn test_create_proposal_incremental_ids() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        // Create multiple proposals
+        let proposal_id1 = client.create_proposal(
+            &member1,
+            &text(&env, "test1"),
+            &content_hash(&env, "test1"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        let proposal_id2 = client.create_proposal(
+            &member1,
+            &text(&env, "test2"),
+            &content_hash(&env, "test2"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        let proposal_id3 = client.create_proposal(
+            &member1,
+            &text(&env, "test3"),
+            &content_hash(&env, "test3"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        assert_eq!(proposal_id1, 1);
+        assert_eq!(proposal_id2, 2);
+        assert_eq!(proposal_id3, 3);
+
+        // Verify proposal counter is updated
+        let config = client.get_config();
+        assert_eq!(config.proposal_count, 3);
+    }
+
+    #[test]
+    fn test_create_proposal_event_emission() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        // Create proposal and check event
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Fund development sprint"),
+            &content_hash(&env, "Allocate budget for the next engineering sprint"),
+            &ProposalAction::Funding,
+            &500_000,
+            &member1,
+            &None,
+        );
+
+        // Verify the event was published with correct payload
+        let events = env.events().all();
+        assert_eq!(events.len(), 2); // init + propose events
+
+        // The propose event should be the second one
+        let propose_event = events.get(1).unwrap();
+        let expected_topics: Vec<Val> = vec![
+            &env,
+            symbol_short!("gov").into_val(&env),
+            symbol_short!("propose").into_val(&env),
+            EVENT_VERSION.into_val(&env),
+        ];
+        assert_eq!(propose_event.1, expected_topics);
+
+        let event_data: Vec<Val> = Vec::try_from_val(&env, &propose_event.2).unwrap();
+        let expected_data: Vec<Val> = vec![
+            &env,
+            proposal_id.into_val(&env),
+            member1.clone().into_val(&env),
+            (env.ledger().sequence() as u64 + 1000).into_val(&env),
+            member1.clone().into_val(&env),
+            500_000_i128.into_val(&env),
+            content_hash(&env, "Allocate budget for the next engineering sprint").into_val(&env),
+        ];
+        assert_eq!(event_data, expected_data);
+    }
+
+    #[test]
+    fn test_create_proposal_persistence() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        // Create proposal
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        // Verify proposal is persisted
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.id, proposal_id);
+        assert_eq!(proposal.proposer, member1);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+    }
+
+    #[test]
+    fn test_has_voted() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        assert!(!client.has_voted(&proposal_id, &member1));
+
+        client.vote(&member1, &proposal_id, &true);
+
+        assert!(client.has_voted(&proposal_id, &member1));
+    }
+
+    #[test]
+    fn test_execute_proposal_add_member() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let new_member = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "add_mem"),
+            &content_hash(&env, "new_mem"),
+            &ProposalAction::AddMember,
+            &0,
+            &new_member,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+
+        client.execute_proposal(&admin, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+
+        let all_members = client.get_members();
+        assert_eq!(all_members.len(), 3);
+    }
+
+    #[test]
+    fn test_execute_proposal_remove_member() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "rem_mem"),
+            &content_hash(&env, "rem"),
+            &ProposalAction::RemoveMember,
+            &0,
+            &member3,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        client.finalize(&member1, &proposal_id);
+        client.execute_proposal(&member1, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+
+        let all_members = client.get_members();
+        assert_eq!(all_members.len(), 2);
+    }
+
+    #[test]
+    fn test_execute_proposal_unauthorized() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        client.finalize(&member1, &proposal_id);
+
+        let result = client.try_execute_proposal(&member3, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_execute_proposal_already_executed() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        client.finalize(&member1, &proposal_id);
+        client.execute_proposal(&admin, &proposal_id);
+
+        let result = client.try_execute_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyExecuted)));
+    }
+
+    #[test]
+    fn test_execute_proposal_emits_event() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        client.finalize(&member1, &proposal_id);
+
+        client.execute_proposal(&admin, &proposal_id);
+    }
+
+    #[test]
+    fn test_finalize_rejected() {
+        let (env, admin, client) = setup_contract();
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &false); // Against
+        client.vote(&member2, &proposal_id, &false); // Against
+
+        env.ledger().set_sequence_number(100);
+
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Rejected);
+    }
+
+    #[test]
+    fn test_finalize_expired_quorum_not_met() {
+        let (env, admin, client) = setup_contract();
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &100, &10); // 100% quorum required
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        // member2 doesn't vote
+
+        env.ledger().set_sequence_number(100);
+
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Expired);
+    }
+
+    #[test]
+    fn test_finalize_passed_and_emits_event() {
+        let (env, admin, client) = setup_contract();
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "policy"),
+            &content_hash(&env, "update"),
+            &ProposalAction::PolicyChange,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+        client.vote(&member3, &proposal_id, &false);
+
+        env.ledger().set_sequence_number(100);
+
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+
+        let events = env.events().all();
+        let finalize_event = events.get(events.len() - 1).unwrap();
+        let expected_topics: Vec<Val> = vec![
+            &env,
+            symbol_short!("gov").into_val(&env),
+            symbol_short!("finalize").into_val(&env),
+            EVENT_VERSION.into_val(&env),
+        ];
+        assert_eq!(finalize_event.1, expected_topics);
+
+        let expected_data: Vec<Val> = vec![
+            &env,
+            proposal_id.into_val(&env),
+            ProposalStatus::Passed.into_val(&env),
+            2u32.into_val(&env),
+            1u32.into_val(&env),
+            3u32.into_val(&env),
+            2u64.into_val(&env),
+        ];
+        let actual_data: Vec<Val> = Vec::try_from_val(&env, &finalize_event.2).unwrap();
+        assert_eq!(actual_data, expected_data);
+    }
+
+    #[test]
+    fn test_finalize_rejects_when_voting_period_is_still_active() {
+        let (env, admin, client) = setup_contract();
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &50);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "active"),
+            &content_hash(&env, "wait"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(25);
+
+        let result = client.try_finalize(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::VotingStillActive)));
+    }
+
+    #[test]
+    fn test_full_governance_workflow_propose_vote_finalize_execute() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let existing_member = Address::generate(&env);
+        let new_member = Address::generate(&env);
+        let members = Vec::from_array(
+            &env,
+            [member1.clone(), member2.clone(), existing_member.clone()],
+        );
+
+        client.initialize(&admin, &members, &66, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "add_mem"),
+            &content_hash(&env, "expand DAO membership beyond symbol limits"),
+            &ProposalAction::AddMember,
+            &0,
+            &new_member,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+        client.vote(&existing_member, &proposal_id, &false);
+
+        env.ledger().set_sequence_number(100);
+
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+
+        client.execute_proposal(&admin, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+
+        let all_members = client.get_members();
+        assert_eq!(all_members.len(), 4);
+        assert!(all_members.contains(new_member));
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 8);
+        assert_eq!(
+            events.get(0).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("init").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(
+            events.get(1).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("propose").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(
+            events.get(2).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("vote").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(
+            events.get(3).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("vote").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        // The second yea vote reaches the two-vote quorum
+        assert_eq!(
+            events.get(4).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                Symbol::new(&env, "quorum_hit").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(
+            events.get(5).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("vote").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(
+            events.get(6).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("finalize").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        let finalize_data: (u64, ProposalStatus, u32, u32, u32, u64) =
+            <_>::try_from_val(&env, &events.get(6).unwrap().2).unwrap();
+        assert_eq!(
+            finalize_data,
+            (proposal_id, ProposalStatus::Passed, 2, 1, 3, 2)
+        );
+        assert_eq!(
+            events.get(7).unwrap().1,
+            vec![
+                &env,
+                symbol_short!("gov").into_val(&env),
+                symbol_short!("exec").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vote_closed_period_ended() {
+        let (env, admin, client) = setup_contract();
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "test"),
+            &content_hash(&env, "test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        env.ledger().set_sequence_number(100);
+
+        let result = client.try_vote(&member1, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+    }
+
+    #[test]
+    fn test_transfer_admin() {
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+
+        let new_admin = Address::generate(&env);
+        client.transfer_admin(&admin, &new_admin);
+
+        assert_eq!(client.get_config().admin, new_admin);
+    }
+
+    #[test]
+    fn test_set_quorum() {
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+
+        client.set_quorum(&admin, &75);
+        assert_eq!(client.get_config().quorum_percent, 75);
+    }
+
+    #[test]
+    fn test_set_quorum_rejects_zero() {
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+
+        let result = client.try_set_quorum(&admin, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+    }
+
+    #[test]
+    fn test_set_quorum_rejects_above_100() {
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+
+        let result = client.try_set_quorum(&admin, &101);
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+    }
+
+    #[test]
+    fn test_set_quorum_accepts_boundary_values() {
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+
+        // Test minimum valid value
+        client.set_quorum(&admin, &1);
+        assert_eq!(client.get_config().quorum_percent, 1);
+
+        // Test maximum valid value
+        client.set_quorum(&admin, &100);
+        assert_eq!(client.get_config().quorum_percent, 100);
+    }
+
+    #[test]
+    fn test_execute_add_member_proposal_fails_for_existing_member() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "dup_mem"),
+            &content_hash(&env, "try_add_existing"),
+            &ProposalAction::AddMember,
+            &0,
+            &member2,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+        env.ledger().set_sequence_number(100);
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+
+        let result = client.try_execute_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::MemberAlreadyExists)));
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+
+        let all_members = client.get_members();
+        assert_eq!(all_members.len(), 2);
+    }
+
+    #[test]
+    fn test_weighted_vote_adds_member_weight() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut weights = Map::new(&env);
+        weights.set(member1.clone(), 5);
+        weights.set(member2.clone(), 2);
+        client.set_member_weights(&admin, &weights);
+
+        assert_eq!(client.get_member_weight(&member1), 5);
+        assert_eq!(client.get_member_weight(&member3), 1);
+        assert_eq!(client.get_config().total_voting_weight, 8);
+
+        let proposal_id = client.create_proposal(
+            &member2,
+            &text(&env, "weighted"),
+            &content_hash(&env, "weighted"),
+            &ProposalAction::General,
+            &0,
+            &member2,
+            &None,
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &false);
+        client.vote(&member3, &proposal_id, &false);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 5);
+        assert_eq!(proposal.votes_against, 3);
+        assert_eq!(proposal.total_votes, 8);
+
+        env.ledger().set_sequence_number(100);
+        assert_eq!(
+            client.finalize(&member1, &proposal_id),
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_weighted_quorum_uses_total_weight() {
+        let (env, admin, client) = setup_contract();
+
+        let whale = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [whale.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut weights = Map::new(&env);
+        weights.set(whale.clone(), 10);
+        client.set_member_weights(&admin, &weights);
+
+        // Two of three members vote, but only 2 of 12 weight is cast.
+        let proposal_id = client.create_proposal(
+            &member2,
+            &text(&env, "quorum"),
+            &content_hash(&env, "quorum"),
+            &ProposalAction::General,
+            &0,
+            &member2,
+            &None,
+        );
+        client.vote(&member2, &proposal_id, &true);
+        client.vote(&member3, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+        assert_eq!(
+            client.finalize(&member2, &proposal_id),
+            ProposalStatus::Expired
+        );
+    }
+
+    #[test]
+    fn test_default_weight_is_one_per_member() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        assert_eq!(client.get_config().total_voting_weight, 2);
+        assert_eq!(client.get_member_weight(&member1), 1);
+    }
+
+    #[test]
+    fn test_set_member_weights_rejects_zero_and_non_admin() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut weights = Map::new(&env);
+        weights.set(member1.clone(), 0);
+        let result = client.try_set_member_weights(&admin, &weights);
+        assert_eq!(result, Err(Ok(Error::InvalidWeight)));
+
+        weights.set(member1.clone(), 3);
+        let result = client.try_set_member_weights(&member1, &weights);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_delegate_vote_counts_delegator_weight() {
+        let (env, admin, client) = setup_contract();
+
+        let member_a = Address::generate(&env);
+        let member_b = Address::generate(&env);
+        let member_c = Address::generate(&env);
+        let members = Vec::from_array(&env, [member_a.clone(), member_b.clone(), member_c.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        client.delegate(&member_a, &member_b);
+        assert_eq!(client.get_delegate(&member_a), Some(member_b.clone()));
+        assert_eq!(
+            client.get_delegators(&member_b),
+            Vec::from_array(&env, [member_a.clone()])
+        );
+
+        let proposal_id = client.create_proposal(
+            &member_c,
+            &text(&env, "delegated"),
+            &content_hash(&env, "delegated"),
+            &ProposalAction::General,
+            &0,
+            &member_c,
+            &None,
+        );
+
+        client.vote(&member_b, &proposal_id, &true);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 2);
+        assert_eq!(proposal.total_votes, 2);
+        assert!(client.has_voted(&proposal_id, &member_a));
+
+        let result = client.try_vote(&member_a, &proposal_id, &false);
+        assert_eq!(result, Err(Ok(Error::VoteDelegated)));
+    }
+
+    #[test]
+    fn test_undelegate_restores_direct_voting() {
+        let (env, admin, client) = setup_contract();
+
+        let member_a = Address::generate(&env);
+        let member_b = Address::generate(&env);
+        let members = Vec::from_array(&env, [member_a.clone(), member_b.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        client.delegate(&member_a, &member_b);
+        client.undelegate(&member_a);
+        assert_eq!(client.get_delegate(&member_a), None);
+        assert_eq!(client.get_delegators(&member_b).len(), 0);
+
+        let proposal_id = client.create_proposal(
+            &member_b,
+            &text(&env, "direct"),
+            &content_hash(&env, "direct"),
+            &ProposalAction::General,
+            &0,
+            &member_b,
+            &None,
+        );
+        client.vote(&member_a, &proposal_id, &false);
+        client.vote(&member_b, &proposal_id, &true);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+
+        let result = client.try_undelegate(&member_a);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+    }
+
+    #[test]
+    fn test_delegate_rejects_self_and_chains() {
+        let (env, admin, client) = setup_contract();
+
+        let member_a = Address::generate(&env);
+        let member_b = Address::generate(&env);
+        let member_c = Address::generate(&env);
+        let members = Vec::from_array(&env, [member_a.clone(), member_b.clone(), member_c.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let result = client.try_delegate(&member_a, &member_a);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+
+        client.delegate(&member_a, &member_b);
+
+        // B holds a delegation, so B cannot delegate onward.
+        let result = client.try_delegate(&member_b, &member_c);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+
+        // A already delegates, so nobody can delegate to A.
+        let result = client.try_delegate(&member_c, &member_a);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+    }
+
+    #[test]
+    fn test_delegator_who_voted_first_is_not_double_counted() {
+        let (env, admin, client) = setup_contract();
+
+        let member_a = Address::generate(&env);
+        let member_b = Address::generate(&env);
+        let members = Vec::from_array(&env, [member_a.clone(), member_b.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member_a,
+            &text(&env, "order"),
+            &content_hash(&env, "order"),
+            &ProposalAction::General,
+            &0,
+            &member_a,
+            &None,
+        );
+        client.vote(&member_a, &proposal_id, &false);
+        client.delegate(&member_a, &member_b);
+        client.vote(&member_b, &proposal_id, &true);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+        assert_eq!(proposal.total_votes, 2);
+    }
+
+    #[test]
+    fn test_change_vote_moves_weight_between_tallies() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Flip"),
+            &content_hash(&env, "Vote to be changed"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        client.change_vote(&member2, &proposal_id, &VoteChoice::Nay);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+        assert_eq!(proposal.total_votes, 2);
+
+        // Changing to the same choice leaves the tallies untouched
+        client.change_vote(&member2, &proposal_id, &VoteChoice::Nay);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+        assert_eq!(proposal.total_votes, 2);
+    }
+
+    #[test]
+    fn test_change_vote_rejected_after_voting_closes() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Late"),
+            &content_hash(&env, "Vote changed too late"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(100);
+
+        let result = client.try_change_vote(&member1, &proposal_id, &VoteChoice::Nay);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+        let result = client.try_retract_vote(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+    }
+
+    #[test]
+    fn test_retract_vote_reduces_total_and_allows_revote() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &100, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Retract"),
+            &content_hash(&env, "Vote to be retracted"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &false);
+
+        client.retract_vote(&member2, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 0);
+        assert_eq!(proposal.total_votes, 1);
+        assert!(!client.has_voted(&proposal_id, &member2));
+
+        let result = client.try_retract_vote(&member2, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::VoteNotFound)));
+
+        // With a 100% quorum the retraction leaves the proposal short
+        env.ledger().set_sequence_number(100);
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Expired);
+    }
+
+    #[test]
+    fn test_change_vote_by_delegate_moves_delegated_weight() {
+        let (env, admin, client) = setup_contract();
+
+        let member_a = Address::generate(&env);
+        let member_b = Address::generate(&env);
+        let members = Vec::from_array(&env, [member_a.clone(), member_b.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        client.delegate(&member_a, &member_b);
+        let proposal_id = client.create_proposal(
+            &member_b,
+            &text(&env, "Proxy"),
+            &content_hash(&env, "Delegated vote to be changed"),
+            &ProposalAction::General,
+            &0,
+            &member_b,
+            &None,
+        );
+        client.vote(&member_b, &proposal_id, &true);
+
+        let result = client.try_change_vote(&member_a, &proposal_id, &VoteChoice::Nay);
+        assert_eq!(result, Err(Ok(Error::VoteDelegated)));
+
+        client.change_vote(&member_b, &proposal_id, &VoteChoice::Nay);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 0);
+        assert_eq!(proposal.votes_against, 2);
+
+        client.retract_vote(&member_b, &proposal_id);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.total_votes, 0);
+        assert!(!client.has_voted(&proposal_id, &member_a));
+    }
+
+    #[test]
+    fn test_abstentions_count_toward_quorum_only() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Abstain"),
+            &content_hash(&env, "Yea outweighs nay with abstentions present"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote_choice(&member2, &proposal_id, &VoteChoice::Abstain);
+        client.vote_choice(&member3, &proposal_id, &VoteChoice::Abstain);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 0);
+        assert_eq!(proposal.abstentions, 2);
+        assert_eq!(proposal.total_votes, 3);
+
+        env.ledger().set_sequence_number(100);
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_abstentions_alone_reach_quorum_without_decision() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &100, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Abstain"),
+            &content_hash(&env, "Everyone abstains"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote_choice(&member1, &proposal_id, &VoteChoice::Abstain);
+        client.vote_choice(&member2, &proposal_id, &VoteChoice::Abstain);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.total_votes, 2);
+        assert_eq!(proposal.abstentions, 2);
+
+        env.ledger().set_sequence_number(100);
+        let status = client.finalize(&member1, &proposal_id);
+        assert_ne!(status, ProposalStatus::Passed);
+        assert_ne!(status, ProposalStatus::Rejected);
+        assert_eq!(status, ProposalStatus::Expired);
+    }
+
+    #[test]
+    fn test_change_vote_to_abstain() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Abstain"),
+            &content_hash(&env, "Switch to abstain"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.change_vote(&member1, &proposal_id, &VoteChoice::Abstain);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 0);
+        assert_eq!(proposal.abstentions, 1);
+        assert_eq!(proposal.total_votes, 1);
+    }
+
+    #[test]
+    fn test_proposer_can_cancel_proposal() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Withdrawn"),
+            &content_hash(&env, "Proposer changes their mind"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        let result = client.try_cancel_proposal(&member2, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.cancel_proposal(&member1, &proposal_id);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Cancelled);
+
+        let result = client.try_vote(&member2, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::ProposalCancelled)));
+
+        env.ledger().set_sequence_number(100);
+        let result = client.try_finalize(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalCancelled)));
+        let result = client.try_execute_proposal(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalCancelled)));
+    }
+
+    #[test]
+    fn test_proposer_cannot_cancel_after_votes() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Contested"),
+            &content_hash(&env, "Already has a vote"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member2, &proposal_id, &false);
+
+        let result = client.try_cancel_proposal(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalHasVotes)));
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+    }
+
+    #[test]
+    fn test_admin_can_cancel_proposal_with_votes() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Malicious"),
+            &content_hash(&env, "Admin steps in"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+
+        client.cancel_proposal(&admin, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Cancelled);
+
+        let result = client.try_cancel_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalCancelled)));
+    }
+
+    fn finalize_with_yea_votes(
+        member_count: usize,
+        quorum: u32,
+        yea_votes: usize,
+    ) -> ProposalStatus {
+        let (env, admin, client) = setup_contract();
+
+        let mut members = Vec::new(&env);
+        for _ in 0..member_count {
+            members.push_back(Address::generate(&env));
+        }
+        client.initialize(&admin, &members, &quorum, &10);
+
+        let proposer = members.get(0).unwrap();
+        let proposal_id = client.create_proposal(
+            &proposer,
+            &text(&env, "Quorum"),
+            &content_hash(&env, "Quorum boundary"),
+            &ProposalAction::General,
+            &0,
+            &proposer,
+            &None,
+        );
+        for i in 0..yea_votes {
+            client.vote(&members.get(i as u32).unwrap(), &proposal_id, &true);
+        }
+
+        env.ledger().set_sequence_number(100);
+        client.finalize(&proposer, &proposal_id)
+    }
+
+    #[test]
+    fn test_quorum_rounds_up_three_members_fifty_percent() {
+        assert_eq!(finalize_with_yea_votes(3, 50, 1), ProposalStatus::Expired);
+        assert_eq!(finalize_with_yea_votes(3, 50, 2), ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_quorum_rounds_up_seven_members_sixty_six_percent() {
+        assert_eq!(finalize_with_yea_votes(7, 66, 4), ProposalStatus::Expired);
+        assert_eq!(finalize_with_yea_votes(7, 66, 5), ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_execution_timelock_blocks_early_execution() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let new_member = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_execution_delay(&admin, &50);
+        assert_eq!(client.get_config().execution_delay, 50);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Add member"),
+            &content_hash(&env, "Timelocked membership change"),
+            &ProposalAction::AddMember,
+            &0,
+            &new_member,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        env.ledger().set_sequence_number(20);
+        client.finalize(&member1, &proposal_id);
+        assert_eq!(client.get_proposal(&proposal_id).executable_at, 70);
+
+        env.ledger().set_sequence_number(69);
+        let result = client.try_execute_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::TimelockActive)));
+
+        env.ledger().set_sequence_number(70);
+        client.execute_proposal(&admin, &proposal_id);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(client.get_members().len(), 3);
+    }
+
+    #[test]
+    fn test_set_execution_delay_unauthorized() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let result = client.try_set_execution_delay(&member1, &50);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_config().execution_delay, 0);
+    }
+
+    #[test]
+    fn test_sweep_expired_marks_only_ended_active_proposals() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, "Sweep candidate"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            )
+        };
+        let stale1 = create("Stale one");
+        let stale2 = create("Stale two");
+        let cancelled = create("Cancelled");
+        client.cancel_proposal(&member1, &cancelled);
+
+        env.ledger().set_sequence_number(50);
+        let fresh = create("Fresh");
+
+        let ids = Vec::from_array(&env, [stale1, stale2, cancelled, fresh, 999]);
+        assert_eq!(client.sweep_expired(&member1, &ids), 2);
+
+        assert_eq!(client.get_proposal(&stale1).status, ProposalStatus::Expired);
+        assert_eq!(client.get_proposal(&stale2).status, ProposalStatus::Expired);
+        assert_eq!(
+            client.get_proposal(&cancelled).status,
+            ProposalStatus::Cancelled
+        );
+        assert_eq!(client.get_proposal(&fresh).status, ProposalStatus::Active);
+
+        let events = env.events().all();
+        let (_, _, data) = events.last().unwrap();
+        assert_eq!(u32::try_from_val(&env, &data).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_sweep_expired_rejects_oversized_batch() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut ids = Vec::new(&env);
+        for id in 0..=MAX_SWEEP_BATCH as u64 {
+            ids.push_back(id);
+        }
+        let result = client.try_sweep_expired(&member1, &ids);
+        assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
+    }
+
+    #[test]
+    fn test_timestamp_voting_window() {
+        let (env, admin, client) = setup_contract();
+        env.ledger().set_timestamp(1_000);
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        // One week of ledgers, converted to seconds by the clock switch
+        client.initialize(&admin, &members, &50, &120_960);
+        client.set_voting_clock(&admin, &VotingClock::Timestamp);
+        assert_eq!(client.get_config().voting_clock, VotingClock::Timestamp);
+        assert_eq!(client.get_config().voting_period, 604_800);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Weekly"),
+            &content_hash(&env, "Measured in seconds"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.clock, VotingClock::Timestamp);
+        assert_eq!(proposal.created_at, 1_000);
+        assert_eq!(proposal.ends_at, 605_800);
+
+        client.vote(&member1, &proposal_id, &true);
+        let result = client.try_finalize(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::VotingStillActive)));
+
+        env.ledger().set_timestamp(605_801);
+        let result = client.try_vote(&member2, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_voting_clock_change_keeps_existing_proposal_clock() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        assert_eq!(client.get_config().voting_clock, VotingClock::Ledger);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Ledger"),
+            &content_hash(&env, "Created in ledger mode"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.set_voting_clock(&admin, &VotingClock::Timestamp);
+
+        env.ledger().set_sequence_number(100);
+        let result = client.try_vote(&member1, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+
+        let result = client.try_set_voting_clock(&member1, &VotingClock::Ledger);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_list_proposals_filters_by_status_and_limit() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, "Listed proposal"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            )
+        };
+        let passed = create("Passed");
+        let cancelled = create("Cancelled");
+        let expired = create("Expired");
+        client.vote(&member1, &passed, &true);
+        client.cancel_proposal(&member1, &cancelled);
+
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &passed);
+        client.finalize(&member1, &expired);
+        let active1 = create("Active one");
+        let active2 = create("Active two");
+
+        let all = client.list_proposals(&None, &0, &10);
+        assert_eq!(all.items.len(), 5);
+        assert!(!all.has_more);
+        assert_eq!(all.next_cursor, None);
+
+        let active = client.list_proposals(&Some(ProposalStatus::Active), &0, &10);
+        assert_eq!(active.items.len(), 2);
+        assert_eq!(active.items.get(0).unwrap().id, active1);
+        assert_eq!(active.items.get(1).unwrap().id, active2);
+
+        let expired_list = client.list_proposals(&Some(ProposalStatus::Expired), &0, &10);
+        assert_eq!(expired_list.items.len(), 1);
+        assert_eq!(expired_list.items.get(0).unwrap().id, expired);
+
+        // Page through everything two at a time
+        let page1 = client.list_proposals(&None, &1, &2);
+        assert_eq!(page1.items.len(), 2);
+        assert_eq!(page1.items.get(1).unwrap().id, cancelled);
+        assert!(page1.has_more);
+        assert_eq!(page1.next_cursor, Some(expired));
+        let page2 = client.list_proposals(&None, &page1.next_cursor.unwrap(), &2);
+        assert_eq!(page2.items.len(), 2);
+        assert_eq!(page2.items.get(0).unwrap().id, expired);
+        assert!(page2.has_more);
+        let page3 = client.list_proposals(&None, &page2.next_cursor.unwrap(), &2);
+        assert_eq!(page3.items.len(), 1);
+        assert!(!page3.has_more);
+        assert_eq!(page3.next_cursor, None);
+
+        // A page that exactly fills the limit is the last one
+        let active = client.list_proposals(&Some(ProposalStatus::Active), &0, &2);
+        assert!(!active.has_more);
+
+        assert_eq!(client.list_proposals(&None, &1, &0).items.len(), 0);
+    }
+
+    fn setup_deposit(
+        env: &Env,
+        client: &GovernanceContractClient<'_>,
+        admin: &Address,
+        members: &Vec<Address>,
+        amount: i128,
+    ) -> (Address, TokenClient<'static>) {
+        let token_admin = Address::generate(env);
+        let token_address = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let sac = StellarAssetClient::new(env, &token_address);
+        for member in members.iter() {
+            sac.mint(&member, &1_000);
+        }
+        let treasury = Address::generate(env);
+        client.set_proposal_deposit(admin, &token_address, &amount, &treasury);
+        (treasury, TokenClient::new(env, &token_address))
+    }
+
+    #[test]
+    fn test_proposal_deposit_refunded_on_pass() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        let (_treasury, token) = setup_deposit(&env, &client, &admin, &members, 100);
+        assert_eq!(client.get_config().proposal_deposit, 100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Deposit"),
+            &content_hash(&env, "Refunded after passing"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        assert_eq!(token.balance(&member1), 900);
+
+        let result = client.try_claim_deposit(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::DepositLocked)));
+
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
+
+        assert_eq!(client.claim_deposit(&member1, &proposal_id), 100);
+        assert_eq!(token.balance(&member1), 1_000);
+
+        let result = client.try_claim_deposit(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::DepositNotFound)));
+    }
+
+    #[test]
+    fn test_proposal_deposit_forfeited_on_rejection() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        let (treasury, token) = setup_deposit(&env, &client, &admin, &members, 100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Deposit"),
+            &content_hash(&env, "Forfeited after rejection"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &false);
+        client.vote(&member2, &proposal_id, &false);
+        env.ledger().set_sequence_number(50);
+        assert_eq!(
+            client.finalize(&member1, &proposal_id),
+            ProposalStatus::Rejected
+        );
+
+        assert_eq!(token.balance(&treasury), 100);
+        assert_eq!(token.balance(&member1), 900);
+        let result = client.try_claim_deposit(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::DepositNotFound)));
+    }
+
+    #[test]
+    fn test_sweeping_losing_proposal_still_forfeits_deposit() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        let (treasury, token) = setup_deposit(&env, &client, &admin, &members, 100);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, "Swept before finalize"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            )
+        };
+        let losing = create("Losing");
+        let winning = create("Winning");
+        client.vote(&member1, &losing, &false);
+        client.vote(&member2, &losing, &false);
+        client.vote(&member1, &winning, &true);
+        client.vote(&member2, &winning, &true);
+
+        // The proposer sweeps before anyone finalizes
+        env.ledger().set_sequence_number(50);
+        let ids = Vec::from_array(&env, [losing, winning]);
+        assert_eq!(client.sweep_expired(&member1, &ids), 2);
+
+        assert_eq!(
+            client.get_proposal(&losing).status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(client.get_proposal(&winning).status, ProposalStatus::Passed);
+        assert_eq!(token.balance(&treasury), 100);
+        let result = client.try_claim_deposit(&member1, &losing);
+        assert_eq!(result, Err(Ok(Error::DepositNotFound)));
+        assert_eq!(client.claim_deposit(&member1, &winning), 100);
+    }
+
+    #[test]
+    fn test_proposal_deposit_refunded_on_expiry() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        let (_treasury, token) = setup_deposit(&env, &client, &admin, &members, 100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Deposit"),
+            &content_hash(&env, "Refunded after expiring"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
+
+        let result = client.try_claim_deposit(&member2, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.claim_deposit(&member1, &proposal_id);
+        assert_eq!(token.balance(&member1), 1_000);
+    }
+
+    #[test]
+    fn test_admin_can_veto_passed_proposal_during_timelock() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_execution_delay(&admin, &100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Drain"),
+            &content_hash(&env, "Malicious but passing"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
+
+        client.veto_proposal(&admin, &proposal_id);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Vetoed
+        );
+
+        env.ledger().set_sequence_number(200);
+        let result = client.try_execute_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalVetoed)));
+    }
+
+    #[test]
+    fn test_veto_requires_admin_and_passed_status() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Veto"),
+            &content_hash(&env, "Only the admin may veto"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        let result = client.try_veto_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::ProposalRejected)));
+
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
+
+        let result = client.try_veto_proposal(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_action_threshold_requires_supermajority() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut weights = Map::new(&env);
+        weights.set(member1.clone(), 55);
+        weights.set(member2.clone(), 45);
+        client.set_member_weights(&admin, &weights);
+
+        let mut thresholds = Map::new(&env);
+        thresholds.set(ProposalAction::PolicyChange, 66);
+        thresholds.set(ProposalAction::RemoveMember, 66);
+        client.set_action_thresholds(&admin, &thresholds);
+        assert_eq!(
+            client.get_action_threshold(&ProposalAction::PolicyChange),
+            Some(66)
+        );
+        assert_eq!(client.get_action_threshold(&ProposalAction::Funding), None);
+
+        let funding_id = client.create_proposal(
+            &member1,
+            &text(&env, "Funding"),
+            &content_hash(&env, "Simple majority"),
+            &ProposalAction::Funding,
+            &100,
+            &member1,
+            &None,
+        );
+        let policy_id = client.create_proposal(
+            &member1,
+            &text(&env, "Policy"),
+            &content_hash(&env, "Needs a supermajority"),
+            &ProposalAction::PolicyChange,
+            &0,
+            &member1,
+            &None,
+        );
+        for proposal_id in [funding_id, policy_id] {
+            client.vote(&member1, &proposal_id, &true);
+            client.vote(&member2, &proposal_id, &false);
+        }
+
+        env.ledger().set_sequence_number(50);
+        assert_eq!(
+            client.finalize(&member1, &funding_id),
+            ProposalStatus::Passed
+        );
+        assert_eq!(
+            client.finalize(&member1, &policy_id),
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_set_action_thresholds_validates_range() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let mut thresholds = Map::new(&env);
+        thresholds.set(ProposalAction::PolicyChange, 101);
+        let result = client.try_set_action_thresholds(&admin, &thresholds);
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+
+        thresholds.set(ProposalAction::PolicyChange, 66);
+        let result = client.try_set_action_thresholds(&member1, &thresholds);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_description_hash_round_trips() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let hash = BytesN::from_array(&env, &[7; 32]);
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Roadmap 2027"),
+            &hash,
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.title, text(&env, "Roadmap 2027"));
+        assert_eq!(proposal.description, hash);
+    }
+    #[test]
+    fn test_funding_proposal_releases_treasury_funds() {
+        use stellar_guard_treasury::{TreasuryContract, TreasuryContractClient};
+
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        // Fund a treasury that trusts this governance contract
+        let token_admin = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        StellarAssetClient::new(&env, &asset).mint(&member1, &1_000);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(&admin, &1, &members, &asset);
+        treasury.deposit(&member1, &asset, &1_000);
+        treasury.set_governance(&admin, &client.address);
+        client.set_treasury(&admin, &treasury_id);
+        assert_eq!(client.get_config().treasury, Some(treasury_id.clone()));
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Grant"),
+            &content_hash(&env, "Pay the grant recipient"),
+            &ProposalAction::Funding,
+            &250,
+            &recipient,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
+        client.execute_proposal(&member1, &proposal_id);
+
+        assert_eq!(treasury.get_balance(&asset), 750);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&recipient), 250);
+    }
+
+    #[test]
+    fn test_funding_proposal_fails_when_treasury_refuses() {
+        use stellar_guard_treasury::{TreasuryContract, TreasuryContractClient};
+
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let token_admin = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(&admin, &1, &members, &asset);
+        treasury.set_governance(&admin, &client.address);
+        client.set_treasury(&admin, &treasury_id);
+
+        // The treasury is empty, so the release is refused
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Grant"),
+            &content_hash(&env, "More than the treasury holds"),
+            &ProposalAction::Funding,
+            &250,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &proposal_id);
 
-        for i in 0..members.len() {
-            if members.get(i).unwrap() == *caller {
-                return Ok(());
-            }
-        }
-        Err(Error::NotAMember)
+        let result = client.try_execute_proposal(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::FundingFailed)));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
+        );
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    fn setup_access_control(
+        env: &Env,
+        client: &GovernanceContractClient<'_>,
+        admin: &Address,
+    ) -> (
+        Address,
+        stellar_guard_access_control::AccessControlContractClient<'static>,
+    ) {
+        use stellar_guard_access_control::{AccessControlContract, AccessControlContractClient};
+
+        let owner = Address::generate(env);
+        let access_control_id = env.register_contract(None, AccessControlContract);
+        let access_control = AccessControlContractClient::new(env, &access_control_id);
+        access_control.initialize(&owner);
+        client.set_access_control(admin, &access_control_id);
+        (owner, access_control)
+    }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::testutils::Events as _;
-    use soroban_sdk::testutils::Ledger as _;
-    use soroban_sdk::{vec, Env, IntoVal, TryFromVal, Val};
+    #[test]
+    fn test_access_control_role_grants_membership() {
+        use stellar_guard_access_control::Role;
 
-    fn setup_contract() -> (Env, Address, GovernanceContractClient<'static>) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, GovernanceContract);
-        let client = GovernanceContractClient::new(&env, &contract_id);
-        let admin = Address::generate(&env);
-        (env, admin, client)
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let (owner, access_control) = setup_access_control(&env, &client, &admin);
+
+        let user = Address::generate(&env);
+        let result = client.try_create_proposal(
+            &user,
+            &text(&env, "Hello"),
+            &content_hash(&env, "Not yet a member"),
+            &ProposalAction::General,
+            &0,
+            &user,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::NotAMember)));
+
+        access_control.assign_role(&owner, &user, &Role::Member);
+
+        let proposal_id = client.create_proposal(
+            &user,
+            &text(&env, "Hello"),
+            &content_hash(&env, "Member through access control"),
+            &ProposalAction::General,
+            &0,
+            &user,
+            &None,
+        );
+        assert_eq!(proposal_id, 1);
+
+        // The owner outranks Member, so both count toward quorum
+        let members = client.get_members();
+        assert_eq!(members.len(), 2);
+        assert!(members.contains(&user));
+        assert!(members.contains(&owner));
     }
 
-    fn text(env: &Env, value: &str) -> String {
-        String::from_str(env, value)
+    #[test]
+    fn test_access_control_viewer_is_not_a_member() {
+        use stellar_guard_access_control::Role;
+
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let (owner, access_control) = setup_access_control(&env, &client, &admin);
+
+        let viewer = Address::generate(&env);
+        access_control.assign_role(&owner, &viewer, &Role::Viewer);
+
+        let result = client.try_create_proposal(
+            &viewer,
+            &text(&env, "Hello"),
+            &content_hash(&env, "Viewers cannot propose"),
+            &ProposalAction::General,
+            &0,
+            &viewer,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::NotAMember)));
+        assert!(!client.get_members().contains(&viewer));
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_add_member_proposal_assigns_access_control_role() {
+        use stellar_guard_access_control::Role;
+
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let (owner, access_control) = setup_access_control(&env, &client, &admin);
+        access_control.assign_role(&owner, &client.address, &Role::Admin);
+
+        let newcomer = Address::generate(&env);
+        let proposal_id = client.create_proposal(
+            &owner,
+            &text(&env, "Welcome"),
+            &content_hash(&env, "Add a member through governance"),
+            &ProposalAction::AddMember,
+            &0,
+            &newcomer,
+            &None,
+        );
+        client.vote(&owner, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&owner, &proposal_id);
+        client.execute_proposal(&owner, &proposal_id);
+
+        assert!(access_control.has_permission(&newcomer, &Role::Member));
+        assert!(client.get_members().contains(&newcomer));
+    }
+
+    #[test]
+    fn test_remove_member_proposal_revokes_access_control_role() {
+        use stellar_guard_access_control::Role;
+
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let (owner, access_control) = setup_access_control(&env, &client, &admin);
+        access_control.assign_role(&owner, &client.address, &Role::Admin);
+
+        let leaver = Address::generate(&env);
+        access_control.assign_role(&owner, &leaver, &Role::Member);
+        assert!(client.get_members().contains(&leaver));
+
+        let proposal_id = client.create_proposal(
+            &owner,
+            &text(&env, "Farewell"),
+            &content_hash(&env, "Remove a member through governance"),
+            &ProposalAction::RemoveMember,
+            &0,
+            &leaver,
+            &None,
+        );
+        client.vote(&owner, &proposal_id, &true);
+        client.vote(&leaver, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&owner, &proposal_id);
+        client.execute_proposal(&owner, &proposal_id);
+
+        assert!(!access_control.has_permission(&leaver, &Role::Viewer));
+        assert!(!client.get_members().contains(&leaver));
+    }
+
+    #[test]
+    fn test_member_proposal_fails_without_access_control_admin() {
+        use stellar_guard_access_control::Role;
+
+        let (env, admin, client) = setup_contract();
+        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        // Governance is only a Member, so it cannot assign roles
+        let (owner, access_control) = setup_access_control(&env, &client, &admin);
+        access_control.assign_role(&owner, &client.address, &Role::Member);
+
+        let newcomer = Address::generate(&env);
+        let proposal_id = client.create_proposal(
+            &owner,
+            &text(&env, "Welcome"),
+            &content_hash(&env, "Add a member through governance"),
+            &ProposalAction::AddMember,
+            &0,
+            &newcomer,
+            &None,
+        );
+        client.vote(&owner, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&owner, &proposal_id);
+
+        let result = client.try_execute_proposal(&owner, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::AccessControlFailed)));
+        assert!(!access_control.has_permission(&newcomer, &Role::Viewer));
+    }
+
+    #[test]
+    fn test_member_added_mid_vote_cannot_vote_on_open_proposal() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let newcomer = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let add_id = client.create_proposal(
+            &member1,
+            &text(&env, "Welcome"),
+            &content_hash(&env, "Add the newcomer"),
+            &ProposalAction::AddMember,
+            &0,
+            &newcomer,
+            &None,
+        );
+        client.vote(&member1, &add_id, &true);
+        env.ledger().set_sequence_number(20);
+        client.finalize(&member1, &add_id);
+
+        // Opened while the newcomer is not yet a member
+        let open_id = client.create_proposal(
+            &member1,
+            &text(&env, "In flight"),
+            &content_hash(&env, "Opened before the newcomer joined"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.execute_proposal(&member1, &add_id);
+        assert!(client.get_members().contains(&newcomer));
+
+        let result = client.try_vote(&newcomer, &open_id, &true);
+        assert_eq!(result, Err(Ok(Error::NotAMember)));
+
+        let later_id = client.create_proposal(
+            &member1,
+            &text(&env, "Later"),
+            &content_hash(&env, "Opened after the newcomer joined"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&newcomer, &later_id, &true);
+        assert_eq!(client.get_proposal(&later_id).votes_for, 1);
+    }
+
+    #[test]
+    fn test_vote_weight_is_taken_from_snapshot() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Snapshot"),
+            &content_hash(&env, "Weights frozen at creation"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        let mut weights = Map::new(&env);
+        weights.set(member2.clone(), 10);
+        client.set_member_weights(&admin, &weights);
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &false);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
+    }
+
+    #[test]
+    fn test_edit_proposal_before_votes() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Fund developmnet"),
+            &content_hash(&env, "Draft"),
+            &ProposalAction::Funding,
+            &5_000,
+            &member1,
+            &None,
+        );
+
+        let result = client.try_edit_proposal(
+            &member2,
+            &proposal_id,
+            &text(&env, "Hijacked"),
+            &content_hash(&env, "Draft"),
+            &5_000,
+            &member2,
+        );
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.edit_proposal(
+            &member1,
+            &proposal_id,
+            &text(&env, "Fund development"),
+            &content_hash(&env, "Final"),
+            &500,
+            &member2,
+        );
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.title, text(&env, "Fund development"));
+        assert_eq!(proposal.description, content_hash(&env, "Final"));
+        assert_eq!(proposal.amount, 500);
+        assert_eq!(proposal.target, member2);
+        assert_eq!(proposal.action, ProposalAction::Funding);
+    }
+
+    #[test]
+    fn test_edit_proposal_rejected_after_vote() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Locked"),
+            &content_hash(&env, "Already voted on"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &true);
+
+        let result = client.try_edit_proposal(
+            &member1,
+            &proposal_id,
+            &text(&env, "Changed"),
+            &content_hash(&env, "Already voted on"),
+            &0,
+            &member1,
+        );
+        assert_eq!(result, Err(Ok(Error::ProposalHasVotes)));
+    }
+
+    #[test]
+    fn test_edit_proposal_invalid_amount() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Funding"),
+            &content_hash(&env, "Valid at first"),
+            &ProposalAction::Funding,
+            &100,
+            &member1,
+            &None,
+        );
+
+        let result = client.try_edit_proposal(
+            &member1,
+            &proposal_id,
+            &text(&env, "Funding"),
+            &content_hash(&env, "Valid at first"),
+            &-1,
+            &member1,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+        assert_eq!(client.get_proposal(&proposal_id).amount, 100);
+    }
+
+    #[test]
+    fn test_get_voters_returns_roll_call() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
+        let members = Vec::from_array(
+            &env,
+            [
+                member1.clone(),
+                member2.clone(),
+                member3.clone(),
+                member4.clone(),
+            ],
+        );
+        client.initialize(&admin, &members, &50, &10);
+        client.delegate(&member4, &member3);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Roll call"),
+            &content_hash(&env, "Mixed votes"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        assert_eq!(client.get_voters(&proposal_id).len(), 0);
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &false);
+        client.vote_choice(&member3, &proposal_id, &VoteChoice::Abstain);
+
+        let expected = Vec::from_array(
+            &env,
+            [
+                (member1.clone(), VoteChoice::Yea),
+                (member2.clone(), VoteChoice::Nay),
+                (member3.clone(), VoteChoice::Abstain),
+                (member4.clone(), VoteChoice::Abstain),
+            ],
+        );
+        assert_eq!(client.get_voters(&proposal_id), expected);
+
+        // Changed and retracted votes are reflected in the roll-call
+        client.change_vote(&member2, &proposal_id, &VoteChoice::Yea);
+        client.retract_vote(&member3, &proposal_id);
+        let expected = Vec::from_array(
+            &env,
+            [
+                (member1.clone(), VoteChoice::Yea),
+                (member2.clone(), VoteChoice::Yea),
+            ],
+        );
+        assert_eq!(client.get_voters(&proposal_id), expected);
+    }
+
+    #[test]
+    fn test_decisive_vote_finalizes_early() {
         let (env, admin, client) = setup_contract();
 
-        let member1 = Address::generate(&env);
-        let member2 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &1000);
+        client.set_early_finalize(&admin, &true);
+        assert!(client.get_config().early_finalize);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Early"),
+            &content_hash(&env, "Decided before the deadline"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        // One yea could still be outvoted by the two remaining members
+        client.vote(&member1, &proposal_id, &true);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+
+        // Two of three yea cannot be overturned by the last member
+        client.vote(&member2, &proposal_id, &true);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
+        );
 
-        client.initialize(&admin, &members, &50, &1000);
+        let events = env.events().all();
+        let (_, topics, data) = events.last().unwrap();
+        let expected_topics: Vec<Val> = vec![
+            &env,
+            symbol_short!("gov").into_val(&env),
+            symbol_short!("finalize").into_val(&env),
+            EVENT_VERSION.into_val(&env),
+        ];
+        assert_eq!(topics, expected_topics);
+        let payload: (u64, ProposalStatus, u32, u32, u32, u64) =
+            <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload, (proposal_id, ProposalStatus::Passed, 2, 0, 2, 2));
 
-        let config = client.get_config();
-        assert_eq!(config.admin, admin);
-        assert_eq!(config.member_count, 2);
-        assert_eq!(config.quorum_percent, 50);
-        assert_eq!(config.voting_period, 1000);
+        let result = client.try_vote(&member3, &proposal_id, &false);
+        assert_eq!(result, Err(Ok(Error::VotingClosed)));
     }
 
     #[test]
-    fn test_create_proposal_and_vote() {
+    fn test_decisive_vote_rejects_early() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let member3 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
-
         client.initialize(&admin, &members, &50, &1000);
+        client.set_early_finalize(&admin, &true);
 
-        // Create a funding proposal
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "Fund development sprint"),
-            &text(&env, "Allocate budget for the next engineering sprint"),
-            &ProposalAction::Funding,
-            &500_000,
+            &text(&env, "Early"),
+            &content_hash(&env, "Rejected before the deadline"),
+            &ProposalAction::General,
+            &0,
             &member1,
+            &None,
         );
-        assert_eq!(proposal_id, 1);
-
-        // Vote
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
+        client.vote(&member1, &proposal_id, &false);
+        client.vote(&member2, &proposal_id, &false);
 
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.votes_for, 2);
-        assert_eq!(proposal.total_votes, 2);
-        assert_eq!(proposal.status, ProposalStatus::Active);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Rejected
+        );
     }
 
     #[test]
-    fn test_create_proposal_success() {
+    fn test_early_finalize_disabled_by_default() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
-
         client.initialize(&admin, &members, &50, &1000);
+        assert!(!client.get_config().early_finalize);
 
-        // Create a funding proposal
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "Fund development sprint"),
-            &text(&env, "Allocate budget for the next engineering sprint"),
-            &ProposalAction::Funding,
-            &500_000,
+            &text(&env, "Default"),
+            &content_hash(&env, "Waits for the deadline"),
+            &ProposalAction::General,
+            &0,
             &member1,
+            &None,
         );
-        assert_eq!(proposal_id, 1);
-
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.id, 1);
-        assert_eq!(proposal.proposer, member1);
-        assert_eq!(proposal.title, text(&env, "Fund development sprint"));
+        client.vote(&member1, &proposal_id, &true);
         assert_eq!(
-            proposal.description,
-            text(&env, "Allocate budget for the next engineering sprint")
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
         );
-        assert_eq!(proposal.action, ProposalAction::Funding);
-        assert_eq!(proposal.amount, 500_000);
-        assert_eq!(proposal.target, member1);
-        assert_eq!(proposal.votes_for, 0);
-        assert_eq!(proposal.votes_against, 0);
-        assert_eq!(proposal.total_votes, 0);
-        assert_eq!(proposal.status, ProposalStatus::Active);
-
-        // Verify ends_at is calculated correctly (current_ledger + voting_period)
-        let current_ledger = env.ledger().sequence();
-        assert_eq!(proposal.ends_at, current_ledger + 1000);
     }
 
     #[test]
-    fn test_create_proposal_non_member_fails() {
+    fn test_membership_request_approved_then_applicant_votes() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
-        let non_member = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1]);
+        let member2 = Address::generate(&env);
+        let applicant = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
 
-        client.initialize(&admin, &members, &50, &1000);
+        let request_id = client.request_membership(&applicant);
+        let request = client.get_proposal(&request_id);
+        assert_eq!(request.action, ProposalAction::AddMember);
+        assert_eq!(request.proposer, applicant);
+        assert_eq!(request.target, applicant);
 
-        let result = client.try_create_proposal(
-            &non_member,
-            &text(&env, "test"),
-            &text(&env, "test"),
+        // The applicant is not part of the electorate for their own request
+        let result = client.try_vote(&applicant, &request_id, &true);
+        assert_eq!(result, Err(Ok(Error::NotAMember)));
+
+        client.vote(&member1, &request_id, &true);
+        client.vote(&member2, &request_id, &true);
+        env.ledger().set_sequence_number(20);
+        client.finalize(&member1, &request_id);
+        client.execute_proposal(&applicant, &request_id);
+        assert!(client.get_members().contains(&applicant));
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "First vote"),
+            &content_hash(&env, "The new member takes part"),
             &ProposalAction::General,
             &0,
-            &non_member,
+            &member1,
+            &None,
         );
-        assert_eq!(result, Err(Ok(Error::NotAMember)));
+        client.vote(&applicant, &proposal_id, &true);
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 1);
     }
 
     #[test]
-    fn test_create_proposal_empty_title_fails() {
+    fn test_membership_request_rejected_for_existing_member() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
 
-        client.initialize(&admin, &members, &50, &1000);
+        let result = client.try_request_membership(&member1);
+        assert_eq!(result, Err(Ok(Error::MemberAlreadyExists)));
+    }
 
-        let result = client.try_create_proposal(
+    #[test]
+    fn test_vote_tally_overflow_returns_error() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, ""), // Empty title
-            &text(&env, "test"),
+            &text(&env, "Overflow"),
+            &content_hash(&env, "Tally at the u32 limit"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+
+        // Pre-seed a tally that cannot take another vote
+        env.as_contract(&client.address, || {
+            let key = DataKey::Proposal(proposal_id);
+            let mut proposal: Proposal = env.storage().persistent().get(&key).unwrap();
+            proposal.votes_for = u32::MAX;
+            proposal.total_votes = u32::MAX;
+            env.storage().persistent().set(&key, &proposal);
+        });
+
+        let result = client.try_vote(&member1, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::Overflow)));
+        assert!(!client.has_voted(&proposal_id, &member1));
     }
 
     #[test]
-    fn test_create_proposal_empty_description_fails() {
+    fn test_quadratic_vote_costs_square_of_votes() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone()]);
-
-        client.initialize(&admin, &members, &50, &1000);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.grant_credits(&admin, &member1, &10);
+        client.grant_credits(&admin, &member2, &4);
 
-        let result = client.try_create_proposal(
+        let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, ""), // Empty description
+            &text(&env, "Quadratic"),
+            &content_hash(&env, "Votes cost their square"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+
+        client.vote_quadratic(&member1, &proposal_id, &3, &true);
+        assert_eq!(client.get_credits(&member1), 1);
+        client.vote_quadratic(&member2, &proposal_id, &2, &false);
+        assert_eq!(client.get_credits(&member2), 0);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 3);
+        assert_eq!(proposal.votes_against, 2);
+        assert_eq!(proposal.total_votes, 5);
     }
 
     #[test]
-    fn test_create_proposal_negative_amount_fails() {
+    fn test_quadratic_vote_rejected_without_credits() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.grant_credits(&admin, &member1, &5);
 
-        client.initialize(&admin, &members, &50, &1000);
-
-        let result = client.try_create_proposal(
+        let first = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
-            &ProposalAction::Funding,
-            &-100, // Negative amount
+            &text(&env, "First"),
+            &content_hash(&env, "Spends most credits"),
+            &ProposalAction::General,
+            &0,
             &member1,
+            &None,
         );
-        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+        let second = client.create_proposal(
+            &member1,
+            &text(&env, "Second"),
+            &content_hash(&env, "Not enough credits left"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+
+        // 3 votes would cost 9 credits
+        let result = client.try_vote_quadratic(&member1, &first, &3, &true);
+        assert_eq!(result, Err(Ok(Error::InsufficientCredits)));
+
+        client.vote_quadratic(&member1, &first, &2, &true);
+        assert_eq!(client.get_credits(&member1), 1);
+
+        let result = client.try_vote_quadratic(&member1, &second, &2, &true);
+        assert_eq!(result, Err(Ok(Error::InsufficientCredits)));
+        assert!(!client.has_voted(&second, &member1));
+
+        // A new epoch's grant replaces what is left
+        client.grant_credits(&admin, &member1, &4);
+        client.vote_quadratic(&member1, &second, &2, &true);
+        assert_eq!(client.get_credits(&member1), 0);
     }
 
     #[test]
-    fn test_create_proposal_incremental_ids() {
+    fn test_proposal_cooldown_blocks_rapid_proposals() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_proposal_cooldown(&admin, &100);
+        assert_eq!(client.get_config().proposal_cooldown, 100);
 
-        client.initialize(&admin, &members, &50, &1000);
-
-        // Create multiple proposals
-        let proposal_id1 = client.create_proposal(
+        client.create_proposal(
             &member1,
-            &text(&env, "test1"),
-            &text(&env, "test1"),
+            &text(&env, "First"),
+            &content_hash(&env, "First proposal"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-        let proposal_id2 = client.create_proposal(
+
+        let result = client.try_create_proposal(
             &member1,
-            &text(&env, "test2"),
-            &text(&env, "test2"),
+            &text(&env, "Second"),
+            &content_hash(&env, "Too soon"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-        let proposal_id3 = client.create_proposal(
+        assert_eq!(result, Err(Ok(Error::CooldownActive)));
+
+        env.ledger().set_sequence_number(100);
+        client.create_proposal(
             &member1,
-            &text(&env, "test3"),
-            &text(&env, "test3"),
+            &text(&env, "Second"),
+            &content_hash(&env, "After the cooldown"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
+        assert_eq!(client.get_config().proposal_count, 2);
+    }
 
-        assert_eq!(proposal_id1, 1);
-        assert_eq!(proposal_id2, 2);
-        assert_eq!(proposal_id3, 3);
+    #[test]
+    fn test_proposal_cooldown_exempts_admin() {
+        let (env, admin, client) = setup_contract();
 
-        // Verify proposal counter is updated
-        let config = client.get_config();
-        assert_eq!(config.proposal_count, 3);
+        let members = Vec::from_array(&env, [admin.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_proposal_cooldown(&admin, &100);
+
+        for title in ["First", "Second"] {
+            client.create_proposal(
+                &admin,
+                &text(&env, title),
+                &content_hash(&env, title),
+                &ProposalAction::General,
+                &0,
+                &admin,
+                &None,
+            );
+        }
+        assert_eq!(client.get_config().proposal_count, 2);
     }
 
     #[test]
-    fn test_create_proposal_event_emission() {
+    fn test_execute_proposal_rejects_reentry() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
 
-        client.initialize(&admin, &members, &50, &1000);
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::Locked, &true);
+        });
+        let result = client.try_execute_proposal(&admin, &1);
+        assert_eq!(result, Err(Ok(Error::Reentrancy)));
+    }
+
+    #[test]
+    fn test_pause_blocks_proposals_and_votes() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
 
-        // Create proposal and check event
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "Fund development sprint"),
-            &text(&env, "Allocate budget for the next engineering sprint"),
-            &ProposalAction::Funding,
-            &500_000,
+            &text(&env, "before"),
+            &content_hash(&env, "before pause"),
+            &ProposalAction::General,
+            &0,
             &member1,
+            &None,
         );
 
-        // Verify the event was published with correct payload
-        let events = env.events().all();
-        assert_eq!(events.len(), 2); // init + propose events
+        let result = client.try_pause(&member1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.pause(&admin);
+        assert!(client.is_paused());
 
-        // The propose event should be the second one
-        let propose_event = events.get(1).unwrap();
-        let expected_topics: Vec<Val> = vec![
-            &env,
-            symbol_short!("gov").into_val(&env),
-            symbol_short!("propose").into_val(&env),
-        ];
-        assert_eq!(propose_event.1, expected_topics);
+        let result = client.try_create_proposal(
+            &member1,
+            &text(&env, "during"),
+            &content_hash(&env, "during pause"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::Paused)));
+        let result = client.try_vote(&member2, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::Paused)));
+        let result = client.try_delegate(&member2, &member1);
+        assert_eq!(result, Err(Ok(Error::Paused)));
+        let result = client.try_execute_proposal(&admin, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::Paused)));
 
-        let event_data: Vec<Val> = Vec::try_from_val(&env, &propose_event.2).unwrap();
-        let expected_data: Vec<Val> = vec![
-            &env,
-            proposal_id.into_val(&env),
-            member1.clone().into_val(&env),
-            (env.ledger().sequence() + 1000).into_val(&env),
-            member1.clone().into_val(&env),
-            500_000_i128.into_val(&env),
-        ];
-        assert_eq!(event_data, expected_data);
+        // Admin functions and queries keep working
+        client.set_quorum(&admin, &60);
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 0);
+
+        client.unpause(&admin);
+        client.vote(&member2, &proposal_id, &true);
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 1);
+    }
+
+    /// The smallest module the host accepts as a contract: a WASM header
+    /// plus a `contractenvmetav0` section declaring interface version 21.
+    const MINIMAL_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x11, b'c', b'o', b'n', b't',
+        b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn upgrade_proposal(
+        env: &Env,
+        client: &GovernanceContractClient<'_>,
+        member: &Address,
+        target: &Address,
+        wasm_hash: &BytesN<32>,
+    ) -> u64 {
+        let proposal_id = client.create_proposal(
+            member,
+            &text(env, "Upgrade"),
+            &content_hash(env, "Upgrade the treasury"),
+            &ProposalAction::UpgradeContract(target.clone(), wasm_hash.clone()),
+            &0,
+            target,
+            &None,
+        );
+        client.vote(member, &proposal_id, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(member, &proposal_id);
+        proposal_id
+    }
+
+    #[test]
+    fn test_upgrade_proposal_upgrades_treasury() {
+        use stellar_guard_treasury::{TreasuryContract, TreasuryContractClient};
+
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        let token_admin = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(&admin, &1, &members, &asset);
+        treasury.set_governance(&admin, &client.address);
+
+        let wasm_hash = env.deployer().upload_contract_wasm(MINIMAL_WASM);
+        let proposal_id = upgrade_proposal(&env, &client, &member1, &treasury_id, &wasm_hash);
+        client.execute_proposal(&member1, &proposal_id);
+
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Executed
+        );
+        // The treasury now runs the new code, which exports no functions
+        assert!(treasury.try_get_balance(&asset).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_proposal_fails_when_target_refuses() {
+        use stellar_guard_treasury::{TreasuryContract, TreasuryContractClient};
+
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        // The treasury has not been told to trust this governance contract
+        let token_admin = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(&admin, &1, &members, &asset);
+
+        let wasm_hash = env.deployer().upload_contract_wasm(MINIMAL_WASM);
+        let proposal_id = upgrade_proposal(&env, &client, &member1, &treasury_id, &wasm_hash);
+        let result = client.try_execute_proposal(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::UpgradeFailed)));
+
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
+        );
+        assert_eq!(treasury.get_balance(&asset), 0);
+    }
+
+    #[test]
+    fn test_add_member_rejects_zero_address() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+
+        for zero in ZERO_ADDRESSES {
+            let zero = Address::from_string(&String::from_str(&env, zero));
+            let result = client.try_create_proposal(
+                &member1,
+                &text(&env, "Welcome"),
+                &content_hash(&env, "Add the zero address"),
+                &ProposalAction::AddMember,
+                &0,
+                &zero,
+                &None,
+            );
+            assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+
+            let result = client.try_request_membership(&zero);
+            assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+        }
+        assert_eq!(client.get_members().len(), 1);
     }
 
     #[test]
-    fn test_create_proposal_persistence() {
+    fn test_max_active_proposals_caps_open_proposals() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_max_active_proposals(&admin, &2);
+        assert_eq!(client.get_config().max_active_proposals, 2);
+
+        let create = |title: &str| {
+            client.try_create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, "Capped proposal"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            )
+        };
+        let first = create("First").unwrap().unwrap();
+        create("Second").unwrap().unwrap();
+        assert_eq!(create("Third"), Err(Ok(Error::TooManyActive)));
+
+        // Finalizing the first proposal frees its slot
+        client.vote(&member1, &first, &true);
+        env.ledger().set_sequence_number(50);
+        client.finalize(&member1, &first);
+        let third = create("Third").unwrap().unwrap();
+        assert_eq!(create("Fourth"), Err(Ok(Error::TooManyActive)));
+
+        // Cancelling frees a slot too
+        client.cancel_proposal(&member1, &third);
+        create("Fourth").unwrap().unwrap();
+        assert_eq!(create("Fifth"), Err(Ok(Error::TooManyActive)));
+
+        client.set_max_active_proposals(&admin, &0);
+        create("Fifth").unwrap().unwrap();
+        assert_eq!(client.get_config().proposal_count, 5);
+    }
 
-        client.initialize(&admin, &members, &50, &1000);
-
-        // Create proposal
+    /// Two members vote one each way on a fresh proposal and finalize it.
+    fn finalize_tie(
+        env: &Env,
+        client: &GovernanceContractClient<'_>,
+        member1: &Address,
+        member2: &Address,
+    ) -> (u64, ProposalStatus) {
         let proposal_id = client.create_proposal(
-            &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            member1,
+            &text(env, "Split"),
+            &content_hash(env, "A divisive proposal"),
             &ProposalAction::General,
             &0,
-            &member1,
+            member1,
+            &None,
         );
-
-        // Verify proposal is persisted
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.id, proposal_id);
-        assert_eq!(proposal.proposer, member1);
-        assert_eq!(proposal.status, ProposalStatus::Active);
+        client.vote(member1, &proposal_id, &true);
+        client.vote(member2, &proposal_id, &false);
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 50);
+        let status = client.finalize(member1, &proposal_id);
+        (proposal_id, status)
     }
 
     #[test]
-    fn test_has_voted() {
+    fn test_tie_break_policies() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone()]);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
 
-        client.initialize(&admin, &members, &50, &1000);
+        // Ties are rejected by default
+        assert_eq!(client.get_config().tie_break, TieBreak::Reject);
+        let (_, status) = finalize_tie(&env, &client, &member1, &member2);
+        assert_eq!(status, ProposalStatus::Rejected);
 
-        let proposal_id = client.create_proposal(
-            &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
-            &ProposalAction::General,
-            &0,
-            &member1,
+        client.set_tie_break(&admin, &TieBreak::Pass);
+        assert_eq!(client.get_config().tie_break, TieBreak::Pass);
+        let (proposal_id, status) = finalize_tie(&env, &client, &member1, &member2);
+        assert_eq!(status, ProposalStatus::Passed);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
         );
+    }
 
-        assert_eq!(client.has_voted(&proposal_id, &member1), false);
+    #[test]
+    fn test_tie_break_admin_decides() {
+        let (env, admin, client) = setup_contract();
 
-        client.vote(&member1, &proposal_id, &true);
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_tie_break(&admin, &TieBreak::AdminDecides);
+
+        let (first, status) = finalize_tie(&env, &client, &member1, &member2);
+        assert_eq!(status, ProposalStatus::TieBreakPending);
+        let result = client.try_execute_proposal(&member1, &first);
+        assert_eq!(result, Err(Ok(Error::ProposalRejected)));
+
+        let result = client.try_resolve_tie(&member1, &first, &true);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(
+            client.resolve_tie(&admin, &first, &true),
+            ProposalStatus::Passed
+        );
+        client.execute_proposal(&member1, &first);
 
-        assert_eq!(client.has_voted(&proposal_id, &member1), true);
+        let (second, _) = finalize_tie(&env, &client, &member1, &member2);
+        assert_eq!(
+            client.resolve_tie(&admin, &second, &false),
+            ProposalStatus::Rejected
+        );
+        let result = client.try_resolve_tie(&admin, &second, &true);
+        assert_eq!(result, Err(Ok(Error::NoTieToBreak)));
     }
 
     #[test]
-    fn test_execute_proposal_add_member() {
+    fn test_quorum_hit_event_fires_once() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
-        let new_member = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-
+        let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
+        let members = Vec::from_array(
+            &env,
+            [
+                member1.clone(),
+                member2.clone(),
+                member3.clone(),
+                member4.clone(),
+            ],
+        );
+        // Quorum of 50% across four members is two votes
         client.initialize(&admin, &members, &50, &10);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "add_mem"),
-            &text(&env, "new_mem"),
-            &ProposalAction::AddMember,
+            &text(&env, "Viable"),
+            &content_hash(&env, "Watch for quorum"),
+            &ProposalAction::General,
             &0,
-            &new_member,
+            &member1,
+            &None,
         );
+        let quorum_hit = (
+            symbol_short!("gov"),
+            Symbol::new(&env, "quorum_hit"),
+            EVENT_VERSION,
+        )
+            .into_val(&env);
+        let hits = |env: &Env| {
+            env.events()
+                .all()
+                .iter()
+                .filter(|(_, topics, _)| topics.clone() == quorum_hit)
+                .count()
+        };
 
         client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
-
-        env.ledger().set_sequence_number(100);
-
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
-
-        client.execute_proposal(&admin, &proposal_id);
-
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.status, ProposalStatus::Executed);
-
-        let all_members = client.get_members();
-        assert_eq!(all_members.len(), 3);
+        assert_eq!(hits(&env), 0);
+        assert!(!client.get_proposal(&proposal_id).quorum_reached);
+
+        client.vote(&member2, &proposal_id, &false);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, quorum_hit);
+        let payload: (u64, u32, u32, u32) = <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload, (proposal_id, 1, 1, 0));
+        assert!(client.get_proposal(&proposal_id).quorum_reached);
+
+        // Later votes do not fire it again
+        client.vote(&member3, &proposal_id, &true);
+        client.vote_choice(&member4, &proposal_id, &VoteChoice::Abstain);
+        assert_eq!(hits(&env), 1);
     }
 
     #[test]
-    fn test_execute_proposal_remove_member() {
+    fn test_proposal_result_projects_finalize() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let member3 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
-
         client.initialize(&admin, &members, &50, &10);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "rem_mem"),
-            &text(&env, "rem"),
-            &ProposalAction::RemoveMember,
+            &text(&env, "Projected"),
+            &content_hash(&env, "Compare projection and result"),
+            &ProposalAction::General,
             &0,
-            &member3,
+            &member1,
+            &None,
         );
 
+        // Without quorum the proposal is heading for expiry
         client.vote(&member1, &proposal_id, &true);
+        let result = client.get_proposal_result(&proposal_id);
+        assert_eq!(
+            result,
+            ProposalResult {
+                passed: false,
+                quorum_met: false,
+                quorum_threshold: 2,
+                final_or_projected_status: ProposalStatus::Expired,
+            }
+        );
+
         client.vote(&member2, &proposal_id, &true);
+        let projected = client.get_proposal_result(&proposal_id);
+        assert!(projected.passed);
+        assert!(projected.quorum_met);
+        assert_eq!(projected.final_or_projected_status, ProposalStatus::Passed);
 
-        env.ledger().set_sequence_number(100);
+        client.vote(&member3, &proposal_id, &false);
+        let projected = client.get_proposal_result(&proposal_id);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
 
-        client.finalize(&member1, &proposal_id);
-        client.execute_proposal(&member1, &proposal_id);
+        env.ledger().set_sequence_number(50);
+        let status = client.finalize(&member1, &proposal_id);
+        let result = client.get_proposal_result(&proposal_id);
+        assert_eq!(result, projected);
+        assert_eq!(result.final_or_projected_status, status);
 
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.status, ProposalStatus::Executed);
+        client.execute_proposal(&member1, &proposal_id);
+        let result = client.get_proposal_result(&proposal_id);
+        assert!(result.passed);
+        assert_eq!(result.final_or_projected_status, ProposalStatus::Executed);
 
-        let all_members = client.get_members();
-        assert_eq!(all_members.len(), 2);
+        let result = client.try_get_proposal_result(&99);
+        assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
     }
 
     #[test]
-    fn test_execute_proposal_unauthorized() {
+    fn test_proposal_result_projects_rejection() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
-        let member3 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-
         client.initialize(&admin, &members, &50, &10);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            &text(&env, "Unpopular"),
+            &content_hash(&env, "Heading for rejection"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
+        );
+        client.vote(&member1, &proposal_id, &false);
+        client.vote(&member2, &proposal_id, &false);
+        let projected = client.get_proposal_result(&proposal_id);
+        assert!(!projected.passed);
+        assert_eq!(
+            projected.final_or_projected_status,
+            ProposalStatus::Rejected
         );
 
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
-
-        env.ledger().set_sequence_number(100);
-
+        env.ledger().set_sequence_number(50);
         client.finalize(&member1, &proposal_id);
+        assert_eq!(client.get_proposal_result(&proposal_id), projected);
+    }
 
-        let result = client.try_execute_proposal(&member3, &proposal_id);
+    #[test]
+    fn test_voting_period_bounds() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        let result = client.try_initialize(&admin, &members, &50, &1);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        let result = client.try_initialize(&admin, &members, &50, &(MAX_VOTING_PERIOD + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        client.initialize(&admin, &members, &50, &MIN_VOTING_PERIOD);
+
+        let result = client.try_set_voting_period(&admin, &(MIN_VOTING_PERIOD - 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        let result = client.try_set_voting_period(&member1, &100);
         assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.set_voting_period(&admin, &100);
+        assert_eq!(client.get_config().voting_period, 100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Default"),
+            &content_hash(&env, "Uses the new default"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.ends_at, proposal.created_at + 100);
     }
 
     #[test]
-    fn test_execute_proposal_already_executed() {
+    fn test_extend_voting_period_honors_longer_window() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-
         client.initialize(&admin, &members, &50, &10);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            &text(&env, "Deliberate"),
+            &content_hash(&env, "Needs more time"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
+        let created_at = client.get_proposal(&proposal_id).created_at;
+
+        let result = client.try_extend_voting_period(&member2, &proposal_id, &500);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_extend_voting_period(&member1, &proposal_id, &5);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        let result =
+            client.try_extend_voting_period(&member1, &proposal_id, &(MAX_VOTING_PERIOD + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+
+        client.extend_voting_period(&member1, &proposal_id, &500);
+        assert_eq!(client.get_proposal(&proposal_id).ends_at, created_at + 500);
 
+        // Members can still vote after the default window would have closed
+        env.ledger().set_sequence_number(created_at as u32 + 100);
         client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
+        let result = client.try_finalize(&member1, &proposal_id);
+        assert_eq!(result, Err(Ok(Error::VotingStillActive)));
 
-        env.ledger().set_sequence_number(100);
+        // Once voting has started the window is fixed
+        let result = client.try_extend_voting_period(&member1, &proposal_id, &1_000);
+        assert_eq!(result, Err(Ok(Error::AlreadyVoted)));
+    }
 
-        client.finalize(&member1, &proposal_id);
-        client.execute_proposal(&admin, &proposal_id);
+    #[test]
+    fn test_get_version() {
+        let (env, admin, client) = setup_contract();
+        let members = Vec::from_array(&env, [Address::generate(&env)]);
+        client.initialize(&admin, &members, &50, &1000);
 
-        let result = client.try_execute_proposal(&admin, &proposal_id);
-        assert_eq!(result, Err(Ok(Error::AlreadyExecuted)));
+        assert_eq!(client.get_version(), VERSION);
+
+        // Swap the code in place, as an upgrade would; the data keeps the
+        // version it was written by.
+        env.register_contract(Some(&client.address), GovernanceContract);
+        assert_eq!(client.get_version(), VERSION);
+        let stored: Option<u32> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&DataKey::Version)
+        });
+        assert_eq!(stored, Some(VERSION));
     }
 
     #[test]
-    fn test_execute_proposal_emits_event() {
+    fn test_migrate_adds_quorum_reached_to_old_proposals() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &100);
 
-        client.initialize(&admin, &members, &50, &10);
-
-        let proposal_id = client.create_proposal(
+        let reached = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            &text(&env, "Reached"),
+            &content_hash(&env, "Voted past quorum"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        client.vote(&member1, &reached, &true);
+        let pending = client.create_proposal(
+            &member1,
+            &text(&env, "Pending"),
+            &content_hash(&env, "No votes yet"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
 
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
+        // Rewrite both records in the version 1 layout
+        env.as_contract(&client.address, || {
+            let field = Symbol::new(&env, "quorum_reached");
+            for id in [reached, pending] {
+                let key = DataKey::Proposal(id);
+                let mut record: Map<Symbol, Val> = env.storage().persistent().get(&key).unwrap();
+                record.remove(field.clone());
+                env.storage().persistent().set(&key, &record);
+            }
+            env.storage().instance().remove(&DataKey::ActiveCount);
+            env.storage().instance().set(&DataKey::Version, &1_u32);
+        });
 
-        env.ledger().set_sequence_number(100);
+        let result = client.try_vote(&member2, &pending, &true);
+        assert_eq!(result, Err(Ok(Error::MigrationRequired)));
+        let result = client.try_migrate(&member1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        client.finalize(&member1, &proposal_id);
+        assert_eq!(client.migrate(&admin), VERSION);
+        assert!(client.get_proposal(&reached).quorum_reached);
+        assert!(!client.get_proposal(&pending).quorum_reached);
+        assert_eq!(client.try_migrate(&admin), Err(Ok(Error::AlreadyMigrated)));
 
-        client.execute_proposal(&admin, &proposal_id);
+        // Both old proposals are counted against the active cap
+        client.set_max_active_proposals(&admin, &2);
+        let result = client.try_create_proposal(
+            &member2,
+            &text(&env, "Over cap"),
+            &content_hash(&env, "No slot left"),
+            &ProposalAction::General,
+            &0,
+            &member2,
+            &None,
+        );
+        assert_eq!(result, Err(Ok(Error::TooManyActive)));
+
+        // Normal operation resumes
+        client.vote(&member2, &pending, &true);
+        assert!(client.get_proposal(&pending).quorum_reached);
     }
 
     #[test]
-    fn test_finalize_rejected() {
+    fn test_quorum_override_raises_threshold() {
+        let (env, admin, client) = setup_contract();
+
+        let members = Vec::from_array(
+            &env,
+            [
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+            ],
+        );
+        let proposer = members.get(0).unwrap();
+        client.initialize(&admin, &members, &50, &100);
+
+        let create = |title: &str, quorum_override: Option<u32>| {
+            client.try_create_proposal(
+                &proposer,
+                &text(&env, title),
+                &content_hash(&env, title),
+                &ProposalAction::General,
+                &0,
+                &proposer,
+                &quorum_override,
+            )
+        };
+
+        // The override can tighten quorum, never loosen it
+        assert_eq!(create("Lower", Some(40)), Err(Ok(Error::InvalidProposal)));
+        assert_eq!(create("Over", Some(101)), Err(Ok(Error::InvalidProposal)));
+        let routine = create("Routine", None).unwrap().unwrap();
+        let high_stakes = create("High stakes", Some(75)).unwrap().unwrap();
+        assert_eq!(client.get_quorum_override(&high_stakes), Some(75));
+        assert_eq!(client.get_quorum_override(&routine), None);
+
+        // Two of four votes meet the default quorum but not the override
+        let other = members.get(1).unwrap();
+        for id in [routine, high_stakes] {
+            client.vote(&proposer, &id, &true);
+            client.vote(&other, &id, &true);
+        }
+
+        env.ledger().with_mut(|li| li.sequence_number += 101);
+        assert_eq!(client.finalize(&proposer, &routine), ProposalStatus::Passed);
+        assert_eq!(
+            client.finalize(&proposer, &high_stakes),
+            ProposalStatus::Expired
+        );
+    }
+
+    #[test]
+    fn test_execution_window_expires_passed_proposals() {
         let (env, admin, client) = setup_contract();
+
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-        client.initialize(&admin, &members, &50, &10);
+        client.initialize(&admin, &members, &50, &100);
+        client.set_execution_window(&admin, &50);
+        assert_eq!(client.get_config().execution_window, 50);
+
+        let pass = |title: &str| {
+            let id = client.create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, title),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            );
+            client.vote(&member1, &id, &true);
+            client.vote(&member2, &id, &true);
+            id
+        };
+        let prompt = pass("Prompt");
+        let stale = pass("Stale");
+        env.ledger().with_mut(|li| li.sequence_number += 101);
+        client.finalize(&member1, &prompt);
+        client.finalize(&member1, &stale);
+
+        // Executable up to and including the last ledger of the window
+        env.ledger().with_mut(|li| li.sequence_number += 50);
+        client.execute_proposal(&admin, &prompt);
+        assert_eq!(
+            client.get_proposal(&prompt).status,
+            ProposalStatus::Executed
+        );
 
-        let proposal_id = client.create_proposal(
-            &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
-            &ProposalAction::General,
-            &0,
-            &member1,
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let result = client.try_execute_proposal(&admin, &stale);
+        assert_eq!(result, Err(Ok(Error::ExecutionWindowExpired)));
+        assert_eq!(
+            client.get_proposal(&stale).status,
+            ProposalStatus::ExpiredUnexecuted
         );
 
-        client.vote(&member1, &proposal_id, &false); // Against
-        client.vote(&member2, &proposal_id, &false); // Against
+        let ids = Vec::from_array(&env, [prompt, stale]);
+        assert_eq!(client.sweep_expired(&member1, &ids), 1);
+        assert_eq!(
+            client.get_proposal(&stale).status,
+            ProposalStatus::ExpiredUnexecuted
+        );
+        let result = client.try_execute_proposal(&admin, &stale);
+        assert_eq!(result, Err(Ok(Error::ProposalRejected)));
+    }
 
-        env.ledger().set_sequence_number(100);
+    #[test]
+    fn test_participation_counts_own_votes() {
+        let (env, admin, client) = setup_contract();
 
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Rejected);
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        client.initialize(&admin, &members, &50, &100);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &member1,
+                &text(&env, title),
+                &content_hash(&env, title),
+                &ProposalAction::General,
+                &0,
+                &member1,
+                &None,
+            )
+        };
+        let first = create("First");
+        let second = create("Second");
+        let third = create("Third");
+
+        client.vote(&member1, &first, &true);
+        client.vote(&member1, &second, &false);
+        client.vote_choice(&member1, &third, &VoteChoice::Abstain);
+        client.vote(&member2, &first, &true);
+        client.vote(&member2, &second, &true);
+        client.retract_vote(&member2, &second);
+
+        assert_eq!(client.get_participation(&member1), 3);
+        assert_eq!(client.get_participation(&member2), 1);
+        assert_eq!(client.get_participation(&member3), 0);
+        for member in [&member1, &member2, &member3] {
+            assert_eq!(client.get_proposals_seen(member), 3);
+        }
+
+        // Only members in a proposal's snapshot see it
+        let outsider = Address::generate(&env);
+        assert_eq!(client.get_proposals_seen(&outsider), 0);
+        create("Fourth");
+        assert_eq!(client.get_proposals_seen(&member3), 4);
     }
 
     #[test]
-    fn test_finalize_expired_quorum_not_met() {
+    fn test_is_active_and_pending_finalization_at_deadline() {
         let (env, admin, client) = setup_contract();
+
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-        client.initialize(&admin, &members, &100, &10); // 100% quorum required
+        client.initialize(&admin, &members, &50, &100);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            &text(&env, "Deadline"),
+            &content_hash(&env, "Watch the boundary"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+        let ends_at = client.get_proposal(&proposal_id).ends_at;
+        assert!(!client.is_active(&99));
+
+        // Still open on the last ledger of the voting period
+        env.ledger()
+            .with_mut(|li| li.sequence_number = ends_at as u32);
+        assert!(client.is_active(&proposal_id));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+
+        env.ledger()
+            .with_mut(|li| li.sequence_number = ends_at as u32 + 1);
+        assert!(!client.is_active(&proposal_id));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::PendingFinalization
+        );
+
+        // Reading did not change what is stored
+        assert_eq!(
+            client.finalize(&member1, &proposal_id),
+            ProposalStatus::Passed
+        );
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Passed
+        );
+        assert!(!client.is_active(&proposal_id));
+    }
+
+    #[test]
+    fn test_action_voting_periods_set_ends_at() {
+        let (env, admin, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &100);
+
+        let mut periods = Map::new(&env);
+        periods.set(ProposalAction::PolicyChange, 500);
+        periods.set(ProposalAction::AddMember, 300);
+        client.set_action_voting_periods(&admin, &periods);
+        assert_eq!(
+            client.get_action_voting_period(&ProposalAction::PolicyChange),
+            Some(500)
+        );
+        assert_eq!(
+            client.get_action_voting_period(&ProposalAction::Funding),
+            None
+        );
+
+        let mut invalid = Map::new(&env);
+        invalid.set(ProposalAction::General, MIN_VOTING_PERIOD - 1);
+        let result = client.try_set_action_voting_periods(&admin, &invalid);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        let result = client.try_set_action_voting_periods(&member1, &periods);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        let mut ends = Vec::new(&env);
+        for action in [
+            ProposalAction::PolicyChange,
+            ProposalAction::AddMember,
+            ProposalAction::Funding,
+        ] {
+            let proposal_id = client.create_proposal(
+                &member1,
+                &text(&env, "Periods"),
+                &content_hash(&env, "Action period"),
+                &action,
+                &0,
+                &member1,
+                &None,
+            );
+            let proposal = client.get_proposal(&proposal_id);
+            ends.push_back(proposal.ends_at - proposal.created_at);
+        }
+        // Funding has no override and uses the global period
+        assert_eq!(ends, vec![&env, 500, 300, 100]);
+    }
+
+    #[test]
+    fn test_get_proposals_by_proposer() {
+        let (env, admin, client) = setup_contract();
 
-        client.vote(&member1, &proposal_id, &true);
-        // member2 doesn't vote
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        client.initialize(&admin, &members, &50, &100);
+
+        let mut ids = Vec::new(&env);
+        for proposer in [&member1, &member2, &member1, &member1] {
+            ids.push_back(client.create_proposal(
+                proposer,
+                &text(&env, "Indexed"),
+                &content_hash(&env, "By proposer"),
+                &ProposalAction::General,
+                &0,
+                proposer,
+                &None,
+            ));
+        }
 
-        env.ledger().set_sequence_number(100);
+        let first = ids.get(0).unwrap();
+        let third = ids.get(2).unwrap();
+        let fourth = ids.get(3).unwrap();
+        assert_eq!(
+            client.get_proposals_by_proposer(&member1, &0, &10),
+            vec![&env, first, third, fourth]
+        );
+        assert_eq!(
+            client.get_proposals_by_proposer(&member2, &0, &10),
+            vec![&env, ids.get(1).unwrap()]
+        );
+        assert_eq!(
+            client.get_proposals_by_proposer(&admin, &0, &10),
+            Vec::new(&env)
+        );
 
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Expired);
+        // Pages continue from the given offset
+        assert_eq!(
+            client.get_proposals_by_proposer(&member1, &1, &1),
+            vec![&env, third]
+        );
+        assert_eq!(
+            client.get_proposals_by_proposer(&member1, &5, &10),
+            Vec::new(&env)
+        );
     }
 
     #[test]
-    fn test_finalize_passed_and_emits_event() {
+    fn test_events_carry_schema_version() {
         let (env, admin, client) = setup_contract();
+
         let member1 = Address::generate(&env);
-        let member2 = Address::generate(&env);
-        let member3 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
-        client.initialize(&admin, &members, &50, &10);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &100);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "policy"),
-            &text(&env, "update"),
-            &ProposalAction::PolicyChange,
+            &text(&env, "Versioned"),
+            &content_hash(&env, "Events carry a schema version"),
+            &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-
         client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
-        client.vote(&member3, &proposal_id, &false);
-
-        env.ledger().set_sequence_number(100);
-
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
-
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.status, ProposalStatus::Passed);
+        client.pause(&admin);
 
         let events = env.events().all();
-        let finalize_event = events.get(events.len() - 1).unwrap();
-        let expected_topics: Vec<Val> = vec![
-            &env,
-            symbol_short!("gov").into_val(&env),
-            symbol_short!("finalize").into_val(&env),
-        ];
-        assert_eq!(finalize_event.1, expected_topics);
-
-        let expected_data: Vec<Val> = vec![
-            &env,
-            proposal_id.into_val(&env),
-            ProposalStatus::Passed.into_val(&env),
-        ];
-        let actual_data: Vec<Val> = Vec::try_from_val(&env, &finalize_event.2).unwrap();
-        assert_eq!(actual_data, expected_data);
+        assert!(events.len() >= 4);
+        for (contract, topics, _) in events.iter() {
+            assert_eq!(contract, client.address);
+            let version = u32::try_from_val(&env, &topics.last().unwrap()).unwrap();
+            assert_eq!(version, EVENT_VERSION);
+        }
     }
 
     #[test]
-    fn test_finalize_rejects_when_voting_period_is_still_active() {
+    fn test_suspended_member_cannot_vote_but_stays_member() {
         let (env, admin, client) = setup_contract();
+
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
-        client.initialize(&admin, &members, &50, &50);
+        client.initialize(&admin, &members, &50, &100);
+
+        let result = client.try_suspend_voting(&member1, &member2);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.suspend_voting(&admin, &member2);
+        assert!(client.is_voting_suspended(&member2));
+        assert!(client.get_members().contains(&member2));
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "active"),
-            &text(&env, "wait"),
+            &text(&env, "Suspension"),
+            &content_hash(&env, "Suspended members cannot vote"),
             &ProposalAction::General,
             &0,
             &member1,
+            &None,
         );
-
+        let result = client.try_vote(&member2, &proposal_id, &true);
+        assert_eq!(result, Err(Ok(Error::VotingSuspended)));
+        let result = client.try_vote_quadratic(&member2, &proposal_id, &1, &true);
+        assert_eq!(result, Err(Ok(Error::VotingSuspended)));
         client.vote(&member1, &proposal_id, &true);
 
-        env.ledger().set_sequence_number(25);
-
-        let result = client.try_finalize(&member1, &proposal_id);
-        assert_eq!(result, Err(Ok(Error::VotingStillActive)));
+        client.restore_voting(&admin, &member2);
+        assert!(!client.is_voting_suspended(&member2));
+        client.vote(&member2, &proposal_id, &false);
+        assert_eq!(client.get_proposal(&proposal_id).votes_against, 1);
     }
 
     #[test]
-    fn test_full_governance_workflow_propose_vote_finalize_execute() {
+    fn test_nay_votes_past_rejection_threshold_reject_early() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
-        let existing_member = Address::generate(&env);
-        let new_member = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
         let members = Vec::from_array(
             &env,
-            [member1.clone(), member2.clone(), existing_member.clone()],
+            [
+                member1.clone(),
+                member2.clone(),
+                member3.clone(),
+                member4.clone(),
+            ],
         );
+        client.initialize(&admin, &members, &50, &1000);
 
-        client.initialize(&admin, &members, &66, &10);
+        let result = client.try_set_rejection_threshold(&admin, &101);
+        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+        client.set_rejection_threshold(&admin, &50);
+        assert_eq!(client.get_config().rejection_threshold, 50);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "add_mem"),
-            &text(&env, "expand DAO membership beyond symbol limits"),
-            &ProposalAction::AddMember,
+            &text(&env, "Opposed"),
+            &content_hash(&env, "Rejected by a nay quorum"),
+            &ProposalAction::General,
             &0,
-            &new_member,
+            &member1,
+            &None,
         );
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
-        client.vote(&existing_member, &proposal_id, &false);
-
-        env.ledger().set_sequence_number(100);
-
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
 
-        client.execute_proposal(&admin, &proposal_id);
+        // Half the weight against is not more than the threshold
+        client.vote(&member2, &proposal_id, &false);
+        client.vote(&member3, &proposal_id, &false);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
 
+        client.vote(&member4, &proposal_id, &false);
         let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.status, ProposalStatus::Executed);
-
-        let all_members = client.get_members();
-        assert_eq!(all_members.len(), 4);
-        assert!(all_members.contains(new_member));
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        assert!(env.ledger().sequence() as u64 <= proposal.ends_at);
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 7);
-        assert_eq!(
-            events.get(0).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("init").into_val(&env),
-            ]
-        );
-        assert_eq!(
-            events.get(1).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("propose").into_val(&env),
-            ]
-        );
-        assert_eq!(
-            events.get(2).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("vote").into_val(&env),
-            ]
-        );
-        assert_eq!(
-            events.get(3).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("vote").into_val(&env),
-            ]
-        );
-        assert_eq!(
-            events.get(4).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("vote").into_val(&env),
-            ]
-        );
+        let (_, topics, data) = env.events().all().last().unwrap();
         assert_eq!(
-            events.get(5).unwrap().1,
+            topics,
             vec![
                 &env,
                 symbol_short!("gov").into_val(&env),
                 symbol_short!("finalize").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
-        assert_eq!(
-            events.get(6).unwrap().1,
-            vec![
-                &env,
-                symbol_short!("gov").into_val(&env),
-                symbol_short!("exec").into_val(&env),
-            ]
-        );
+        let payload: (u64, ProposalStatus, u32, u32, u32, u64) =
+            <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload.0, proposal_id);
+        assert_eq!(payload.1, ProposalStatus::Rejected);
+
+        let result = client.try_vote(&member1, &proposal_id, &true);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_vote_closed_period_ended() {
+    fn test_removing_delegate_releases_delegators() {
         let (env, admin, client) = setup_contract();
+
         let member1 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone()]);
-        client.initialize(&admin, &members, &50, &10);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let member4 = Address::generate(&env);
+        let member5 = Address::generate(&env);
+        let members = Vec::from_array(
+            &env,
+            [
+                member1.clone(),
+                member2.clone(),
+                member3.clone(),
+                member4.clone(),
+                member5.clone(),
+            ],
+        );
+        client.initialize(&admin, &members, &40, &10);
 
+        client.delegate(&member1, &member4);
+        client.delegate(&member2, &member4);
+
+        let removal_id = client.create_proposal(
+            &member3,
+            &text(&env, "Remove delegate"),
+            &content_hash(&env, "Remove the shared delegate"),
+            &ProposalAction::RemoveMember,
+            &0,
+            &member4,
+            &None,
+        );
+        client.vote(&member3, &removal_id, &true);
+        client.vote(&member5, &removal_id, &true);
+        env.ledger().set_sequence_number(100);
+        client.finalize(&member3, &removal_id);
+        client.execute_proposal(&member3, &removal_id);
+
+        assert!(!client.get_members().contains(&member4));
+        assert_eq!(client.get_delegate(&member1), None);
+        assert_eq!(client.get_delegate(&member2), None);
+        assert!(client.get_delegators(&member4).is_empty());
+
+        let undeleg_count = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                symbol_short!("undeleg")
+                    == Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap()
+            })
+            .count();
+        assert_eq!(undeleg_count, 2);
+
+        // Former delegators vote directly again
         let proposal_id = client.create_proposal(
-            &member1,
-            &text(&env, "test"),
-            &text(&env, "test"),
+            &member3,
+            &text(&env, "After removal"),
+            &content_hash(&env, "Delegators vote directly"),
             &ProposalAction::General,
             &0,
-            &member1,
+            &member3,
+            &None,
         );
-
-        env.ledger().set_sequence_number(100);
-
-        let result = client.try_vote(&member1, &proposal_id, &true);
-        assert_eq!(result, Err(Ok(Error::VotingClosed)));
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &false);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 1);
+        assert_eq!(proposal.votes_against, 1);
     }
 
     #[test]
-    fn test_transfer_admin() {
+    fn test_membership_requests_are_rate_limited() {
         let (env, admin, client) = setup_contract();
-        client.initialize(&admin, &Vec::new(&env), &50, &10);
-
-        let new_admin = Address::generate(&env);
-        client.transfer_admin(&admin, &new_admin);
 
-        assert_eq!(client.get_config().admin, new_admin);
+        let member1 = Address::generate(&env);
+        let applicant = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &10);
+        client.set_proposal_cooldown(&admin, &100);
+
+        env.ledger().set_sequence_number(1);
+        let request_id = client.request_membership(&applicant);
+        let result = client.try_request_membership(&applicant);
+        assert_eq!(result, Err(Ok(Error::RequestPending)));
+
+        // Once the request is rejected the cooldown still applies
+        client.vote(&member1, &request_id, &false);
+        env.ledger().set_sequence_number(20);
+        client.finalize(&member1, &request_id);
+        let result = client.try_request_membership(&applicant);
+        assert_eq!(result, Err(Ok(Error::CooldownActive)));
+
+        env.ledger().set_sequence_number(101);
+        client.request_membership(&applicant);
     }
 
     #[test]
-    fn test_set_quorum() {
+    fn test_migrate_refuses_unversioned_data() {
         let (env, admin, client) = setup_contract();
-        client.initialize(&admin, &Vec::new(&env), &50, &10);
-
-        client.set_quorum(&admin, &75);
-        assert_eq!(client.get_config().quorum_percent, 75);
-    }
 
-    #[test]
-    fn test_set_quorum_rejects_zero() {
-        let (env, admin, client) = setup_contract();
-        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &100);
+
+        // A proposal and vote as the original, unversioned contract stored them
+        let field = |name: &str| Symbol::new(&env, name);
+        let mut record: Map<Symbol, Val> = Map::new(&env);
+        record.set(field("id"), 1_u64.into_val(&env));
+        record.set(field("title"), text(&env, "Original").into_val(&env));
+        record.set(
+            field("description"),
+            text(&env, "Plain text").into_val(&env),
+        );
+        record.set(field("action"), ProposalAction::General.into_val(&env));
+        record.set(field("proposer"), member1.into_val(&env));
+        record.set(field("votes_for"), 1_u32.into_val(&env));
+        record.set(field("votes_against"), 0_u32.into_val(&env));
+        record.set(field("total_votes"), 1_u32.into_val(&env));
+        record.set(field("status"), ProposalStatus::Active.into_val(&env));
+        record.set(field("created_at"), 0_u32.into_val(&env));
+        record.set(field("ends_at"), 100_u32.into_val(&env));
+        record.set(field("amount"), 0_i128.into_val(&env));
+        record.set(field("target"), member1.into_val(&env));
+        env.as_contract(&client.address, || {
+            env.storage().instance().remove(&DataKey::Version);
+            env.storage()
+                .instance()
+                .set(&DataKey::ProposalCounter, &1_u64);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Proposal(1), &record);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Vote(1, member1.clone()), &true);
+        });
+
+        let result = client.try_vote(&member1, &1, &true);
+        assert_eq!(result, Err(Ok(Error::MigrationRequired)));
+        assert_eq!(
+            client.try_migrate(&admin),
+            Err(Ok(Error::UnsupportedVersion))
+        );
 
-        let result = client.try_set_quorum(&admin, &0);
-        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
+        // The original record is left as it was
+        let stored: Map<Symbol, Val> = env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get(&DataKey::Proposal(1))
+                .unwrap()
+        });
+        assert_eq!(stored, record);
     }
 
     #[test]
-    fn test_set_quorum_rejects_above_100() {
+    fn test_voting_period_bounds_follow_voting_clock() {
         let (env, admin, client) = setup_contract();
-        client.initialize(&admin, &Vec::new(&env), &50, &10);
-
-        let result = client.try_set_quorum(&admin, &101);
-        assert_eq!(result, Err(Ok(Error::InvalidProposal)));
-    }
 
-    #[test]
-    fn test_set_quorum_accepts_boundary_values() {
-        let (env, admin, client) = setup_contract();
-        client.initialize(&admin, &Vec::new(&env), &50, &10);
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        client.initialize(&admin, &members, &50, &MIN_VOTING_PERIOD);
+        let mut periods = Map::new(&env);
+        periods.set(ProposalAction::Funding, 1_000);
+        client.set_action_voting_periods(&admin, &periods);
+
+        // Switching clocks converts the configured periods to seconds
+        client.set_voting_clock(&admin, &VotingClock::Timestamp);
+        let min_seconds = MIN_VOTING_PERIOD * SECONDS_PER_LEDGER;
+        let max_seconds = MAX_VOTING_PERIOD * SECONDS_PER_LEDGER;
+        assert_eq!(client.get_config().voting_period, min_seconds);
+        assert_eq!(
+            client.get_action_voting_period(&ProposalAction::Funding),
+            Some(5_000)
+        );
 
-        // Test minimum valid value
-        client.set_quorum(&admin, &1);
-        assert_eq!(client.get_config().quorum_percent, 1);
+        // The ledger floor is too short a window in seconds
+        let result = client.try_set_voting_period(&admin, &MIN_VOTING_PERIOD);
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        let result = client.try_set_voting_period(&admin, &(max_seconds + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
 
-        // Test maximum valid value
-        client.set_quorum(&admin, &100);
-        assert_eq!(client.get_config().quorum_percent, 100);
+        // A timestamp proposal can be extended up to the maximum in seconds
+        let proposal_id = client.create_proposal(
+            &member1,
+            &text(&env, "Timed"),
+            &content_hash(&env, "Measured in seconds"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+            &None,
+        );
+        let result = client.try_extend_voting_period(&member1, &proposal_id, &(max_seconds + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
+        client.extend_voting_period(&member1, &proposal_id, &max_seconds);
+
+        // And back to ledgers, rounding up
+        client.set_voting_period(&admin, &(min_seconds + 1));
+        client.set_voting_clock(&admin, &VotingClock::Ledger);
+        assert_eq!(client.get_config().voting_period, MIN_VOTING_PERIOD + 1);
+        let result = client.try_set_voting_period(&admin, &(MAX_VOTING_PERIOD + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidVotingPeriod)));
     }
 
     #[test]
-    fn test_execute_add_member_proposal_fails_for_existing_member() {
+    fn test_quadratic_vote_does_not_finalize_early() {
         let (env, admin, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
         client.initialize(&admin, &members, &50, &10);
+        client.set_early_finalize(&admin, &true);
+        client.set_rejection_threshold(&admin, &50);
+        client.grant_credits(&admin, &member1, &9);
+        client.grant_credits(&admin, &member2, &100);
 
         let proposal_id = client.create_proposal(
             &member1,
-            &text(&env, "dup_mem"),
-            &text(&env, "try_add_existing"),
-            &ProposalAction::AddMember,
+            &text(&env, "Quadratic"),
+            &content_hash(&env, "Unspent credits can still swing it"),
+            &ProposalAction::General,
             &0,
-            &member2,
+            &member1,
+            &None,
         );
 
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
-        env.ledger().set_sequence_number(100);
-        let status = client.finalize(&member1, &proposal_id);
-        assert_eq!(status, ProposalStatus::Passed);
-
-        let result = client.try_execute_proposal(&admin, &proposal_id);
-        assert_eq!(result, Err(Ok(Error::MemberAlreadyExists)));
+        // Three yea votes exceed the remaining member weight, but member2's
+        // credits are worth up to ten nay votes
+        client.vote_quadratic(&member1, &proposal_id, &3, &true);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
 
+        client.vote_quadratic(&member2, &proposal_id, &10, &false);
         let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.status, ProposalStatus::Passed);
+        assert_eq!(proposal.status, ProposalStatus::Active);
 
-        let all_members = client.get_members();
-        assert_eq!(all_members.len(), 2);
+        env.ledger().with_mut(|li| li.sequence_number += 11);
+        let status = client.finalize(&member1, &proposal_id);
+        assert_eq!(status, ProposalStatus::Rejected);
     }
 }
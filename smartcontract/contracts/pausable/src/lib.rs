@@ -0,0 +1,122 @@
+#![no_std]
+
+//! Shared emergency pause for the StellarGuard contracts.
+//!
+//! Each contract checks its own admin or owner before calling
+//! [`set_paused`], and gates entry points with [`require_not_paused`], which
+//! returns the contract's own error variant through [`PausableError`]. The
+//! pause flag lives in the calling contract's instance storage and every
+//! change publishes a `(contract, pause, version)` event carrying
+//! `(by, paused)`, where `version` is the caller's event schema version.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+/// Storage key for the pause flag.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseKey {
+    /// Whether the contract is paused.
+    Paused,
+}
+
+/// Implemented by a contract's error enum to name the error returned while
+/// the contract is paused.
+pub trait PausableError {
+    /// Error returned by [`require_not_paused`] while paused.
+    const PAUSED: Self;
+}
+
+/// Check whether the contract is paused.
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&PauseKey::Paused)
+        .unwrap_or(false)
+}
+
+/// Pause or unpause the contract and publish the standard
+/// `(contract, pause, version)` event. Callers must authorize `by` first.
+pub fn set_paused(env: &Env, contract: Symbol, version: u32, by: &Address, paused: bool) {
+    env.storage().instance().set(&PauseKey::Paused, &paused);
+
+    env.events().publish(
+        (contract, symbol_short!("pause"), version),
+        (by.clone(), paused),
+    );
+}
+
+/// Return the contract's paused error if the contract is paused.
+pub fn require_not_paused<E: PausableError>(env: &Env) -> Result<(), E> {
+    if is_paused(env) {
+        return Err(E::PAUSED);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events as _};
+    use soroban_sdk::{contract, vec, IntoVal};
+
+    #[contract]
+    pub struct PausableContract;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum TestError {
+        Paused,
+    }
+
+    impl PausableError for TestError {
+        const PAUSED: Self = TestError::Paused;
+    }
+
+    #[test]
+    fn test_pause_gate() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PausableContract);
+        let admin = Address::generate(&env);
+
+        env.as_contract(&contract_id, || {
+            assert!(!is_paused(&env));
+            assert_eq!(require_not_paused::<TestError>(&env), Ok(()));
+
+            set_paused(&env, symbol_short!("test"), 1, &admin, true);
+            assert!(is_paused(&env));
+            assert_eq!(
+                require_not_paused::<TestError>(&env),
+                Err(TestError::Paused)
+            );
+
+            set_paused(&env, symbol_short!("test"), 1, &admin, false);
+            assert_eq!(require_not_paused::<TestError>(&env), Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_pause_event() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PausableContract);
+        let admin = Address::generate(&env);
+
+        env.as_contract(&contract_id, || {
+            set_paused(&env, symbol_short!("test"), 1, &admin, true);
+        });
+
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id,
+                    (symbol_short!("test"), symbol_short!("pause"), 1_u32).into_val(&env),
+                    (admin, true).into_val(&env),
+                ),
+            ]
+        );
+    }
+}
@@ -5,6 +5,13 @@ use soroban_sdk::{
     Vec,
 };
 
+/// Version of this contract's code, stored by `initialize`.
+pub const VERSION: u32 = 1;
+
+/// Schema version of the events this contract publishes, carried as the last
+/// topic of every event. Bump it whenever an event's payload layout changes.
+pub const EVENT_VERSION: u32 = 1;
+
 // ============================================================================
 // Error Codes
 // ============================================================================
@@ -70,6 +77,8 @@ pub enum DataKey {
     Vesting(u64),
     /// Total locked amount.
     TotalLocked,
+    /// Contract version that last wrote the stored data.
+    Version,
 }
 
 /// A token lock entry.
@@ -156,6 +165,7 @@ impl TokenVaultContract {
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Version, &VERSION);
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
@@ -170,7 +180,7 @@ impl TokenVaultContract {
         env.storage().instance().set(&DataKey::TotalLocked, &0_i128);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("init")),
+            (symbol_short!("vault"), symbol_short!("init"), EVENT_VERSION),
             (admin.clone(), emergency_signers.len()),
         );
 
@@ -244,7 +254,7 @@ impl TokenVaultContract {
             .set(&DataKey::TotalLocked, &(total + amount));
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("lock")),
+            (symbol_short!("vault"), symbol_short!("lock"), EVENT_VERSION),
             (lock_id, owner.clone(), amount, duration),
         );
 
@@ -306,7 +316,11 @@ impl TokenVaultContract {
             .set(&DataKey::TotalLocked, &new_total);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("claim")),
+            (
+                symbol_short!("vault"),
+                symbol_short!("claim"),
+                EVENT_VERSION,
+            ),
             (lock_id, owner.clone(), amount),
         );
 
@@ -381,7 +395,11 @@ impl TokenVaultContract {
             .set(&DataKey::EmergencyApprovals(lock_id), &approvals);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("emrg_ap")),
+            (
+                symbol_short!("vault"),
+                symbol_short!("emrg_ap"),
+                EVENT_VERSION,
+            ),
             (lock_id, signer.clone(), count),
         );
 
@@ -440,7 +458,11 @@ impl TokenVaultContract {
             .set(&DataKey::TotalLocked, &new_total);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("emrg_ex")),
+            (
+                symbol_short!("vault"),
+                symbol_short!("emrg_ex"),
+                EVENT_VERSION,
+            ),
             (lock_id, caller.clone(), amount),
         );
 
@@ -520,7 +542,7 @@ impl TokenVaultContract {
             .set(&DataKey::TotalLocked, &(total + total_amount));
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("vest")),
+            (symbol_short!("vault"), symbol_short!("vest"), EVENT_VERSION),
             (vesting_id, beneficiary.clone(), total_amount, duration),
         );
 
@@ -592,7 +614,11 @@ impl TokenVaultContract {
             .set(&DataKey::TotalLocked, &new_total);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("v_claim")),
+            (
+                symbol_short!("vault"),
+                symbol_short!("v_claim"),
+                EVENT_VERSION,
+            ),
             (vesting_id, beneficiary.clone(), claimable),
         );
 
@@ -640,9 +666,13 @@ impl TokenVaultContract {
             .get(&DataKey::LockCounter)
             .unwrap_or(0);
 
-        let mut current_id = start_after_id.checked_add(1).unwrap_or(u64::MAX);
+        let mut current_id = start_after_id.saturating_add(1);
         while current_id <= max_id && locks.len() < limit {
-            if let Some(lock) = env.storage().persistent().get::<DataKey, TokenLock>(&DataKey::Lock(current_id)) {
+            if let Some(lock) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, TokenLock>(&DataKey::Lock(current_id))
+            {
                 if lock.owner == owner {
                     locks.push_back(lock);
                 }
@@ -674,7 +704,7 @@ impl TokenVaultContract {
             .get(&DataKey::VestingCounter)
             .unwrap_or(0);
 
-        let mut current_id = start_after_id.checked_add(1).unwrap_or(u64::MAX);
+        let mut current_id = start_after_id.saturating_add(1);
         while current_id <= max_id && schedules.len() < limit {
             if let Some(schedule) = env
                 .storage()
@@ -742,7 +772,11 @@ impl TokenVaultContract {
         env.storage().instance().set(&DataKey::Admin, &new_admin);
 
         env.events().publish(
-            (symbol_short!("vault"), symbol_short!("admin")),
+            (
+                symbol_short!("vault"),
+                symbol_short!("admin"),
+                EVENT_VERSION,
+            ),
             (current_admin, new_admin.clone()),
         );
 
@@ -764,6 +798,11 @@ impl TokenVaultContract {
         Ok(())
     }
 
+    /// Get the version of the deployed contract code.
+    pub fn get_version(_env: Env) -> u32 {
+        VERSION
+    }
+
     // ========================================================================
     // Internal Helpers
     // ========================================================================
@@ -793,6 +832,7 @@ impl TokenVaultContract {
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use super::*;
     use soroban_sdk::testutils::Address as _;
@@ -882,6 +922,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("init").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -890,6 +931,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("lock").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -898,6 +940,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("claim").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(events.get(2).unwrap().0, contract_id);
@@ -954,6 +997,7 @@ mod test {
             &env,
             symbol_short!("vault").into_val(&env),
             symbol_short!("vest").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1009,6 +1053,7 @@ mod test {
             &env,
             symbol_short!("vault").into_val(&env),
             symbol_short!("v_claim").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1081,6 +1126,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("init").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1089,6 +1135,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("vest").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1097,6 +1144,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("v_claim").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1105,6 +1153,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("v_claim").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(events.get(3).unwrap().0, contract_id);
@@ -1135,6 +1184,7 @@ mod test {
             &env,
             symbol_short!("vault").into_val(&env),
             symbol_short!("emrg_ap").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1212,6 +1262,7 @@ mod test {
             &env,
             symbol_short!("vault").into_val(&env),
             symbol_short!("emrg_ex").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1255,6 +1306,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("init").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1263,6 +1315,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("lock").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1271,6 +1324,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("emrg_ap").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1279,6 +1333,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("emrg_ap").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(
@@ -1287,6 +1342,7 @@ mod test {
                 &env,
                 symbol_short!("vault").into_val(&env),
                 symbol_short!("emrg_ex").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ]
         );
         assert_eq!(events.get(4).unwrap().0, contract_id);
@@ -1365,7 +1421,8 @@ mod test {
         let other_owner = Address::generate(&env);
 
         client.lock_tokens(&owner, &100_000, &100, &symbol_short!("one"));
-        let second_owner_lock = client.lock_tokens(&other_owner, &200_000, &100, &symbol_short!("oth"));
+        let second_owner_lock =
+            client.lock_tokens(&other_owner, &200_000, &100, &symbol_short!("oth"));
         let third_owner_lock = client.lock_tokens(&owner, &300_000, &100, &symbol_short!("two"));
         let fourth_owner_lock = client.lock_tokens(&owner, &400_000, &100, &symbol_short!("tre"));
 
@@ -1438,4 +1495,22 @@ mod test {
         let empty_page = client.get_vestings_by_beneficiary(&beneficiary, &second_vesting, &0);
         assert_eq!(empty_page.len(), 0);
     }
+
+    #[test]
+    fn test_get_version() {
+        let (env, admin, _contract_id, client) = setup_contract();
+        let signers = Vec::from_array(&env, [Address::generate(&env)]);
+        client.initialize(&admin, &signers, &1);
+
+        assert_eq!(client.get_version(), VERSION);
+
+        // Swap the code in place, as an upgrade would; the data keeps the
+        // version it was written by.
+        env.register_contract(Some(&client.address), TokenVaultContract);
+        assert_eq!(client.get_version(), VERSION);
+        let stored: Option<u32> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&DataKey::Version)
+        });
+        assert_eq!(stored, Some(VERSION));
+    }
 }
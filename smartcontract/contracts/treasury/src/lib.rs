@@ -2,9 +2,32 @@
 #![allow(dead_code)]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, Address, Env,
-    String, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, log, symbol_short, token,
+    Address, Env, Map, String, Vec,
 };
+use stellar_guard_pausable::PausableError;
+
+/// Maximum number of transactions `approve_batch` processes in one call.
+const MAX_BATCH_SIZE: u32 = 25;
+
+/// Maximum length of a withdrawal memo, in bytes.
+const MAX_MEMO_LEN: u32 = 256;
+
+/// Maximum length of an approval or rejection reason, in bytes.
+const MAX_REASON_LEN: u32 = 256;
+
+/// Stellar's all-zero account and contract addresses, which nobody can sign for.
+const ZERO_ADDRESSES: [&str; 2] = [
+    "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+    "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM",
+];
+
+/// Version of this contract's code, stored by `initialize`.
+pub const VERSION: u32 = 1;
+
+/// Schema version of the events this contract publishes, carried as the last
+/// topic of every event. Bump it whenever an event's payload layout changes.
+pub const EVENT_VERSION: u32 = 1;
 
 // ============================================================================
 // Error Codes
@@ -39,6 +62,49 @@ pub enum Error {
     NotASigner = 11,
     /// Cannot remove signer — would breach threshold.
     ThresholdBreach = 12,
+    /// Signer has not approved this transaction.
+    ApprovalNotFound = 13,
+    /// Transaction proposal was cancelled.
+    Cancelled = 14,
+    /// Withdrawal would exceed the spending limit for the current window.
+    SpendLimitExceeded = 15,
+    /// Transaction cannot execute until its unlock time.
+    TimelockActive = 16,
+    /// Too many transactions passed to a batch call.
+    BatchTooLarge = 17,
+    /// Withdrawals are halted by an emergency freeze.
+    Frozen = 18,
+    /// Memo is longer than `MAX_MEMO_LEN` bytes.
+    MemoTooLong = 19,
+    /// Signer weight must be greater than zero.
+    InvalidWeight = 20,
+    /// Transaction was rejected by signers.
+    TransactionRejected = 21,
+    /// Signer has already rejected this transaction.
+    AlreadyRejected = 22,
+    /// Recurring schedule has made all of its payments.
+    ScheduleExhausted = 23,
+    /// Next recurring payment is not due yet.
+    PaymentNotDue = 24,
+    /// Recurring schedule was not found.
+    ScheduleNotFound = 25,
+    /// Fee must be at most 10000 basis points.
+    InvalidFee = 26,
+    /// A guarded entry point was re-entered.
+    Reentrancy = 27,
+    /// Address cannot be used as a signer.
+    InvalidSigner = 28,
+    /// Refund exceeds what the depositor has deposited.
+    RefundTooLarge = 29,
+    /// Delegate is a signer, already backs another signer, or the expiry
+    /// has passed.
+    InvalidDelegation = 30,
+    /// Reason is longer than `MAX_REASON_LEN` bytes.
+    ReasonTooLong = 31,
+}
+
+impl PausableError for Error {
+    const PAUSED: Self = Error::Frozen;
 }
 
 // ============================================================================
@@ -53,11 +119,11 @@ pub enum DataKey {
     Admin,
     /// The asset contract this treasury manages.
     Asset,
-    /// The approval threshold for multi-sig.
+    /// The approval weight required for multi-sig.
     Threshold,
     /// List of authorized signers.
     Signers,
-    /// Native balance held in treasury.
+    /// Balance of the primary asset held in treasury.
     Balance,
     /// Transaction proposal by ID.
     Transaction(u64),
@@ -69,6 +135,139 @@ pub enum DataKey {
     TokenBalance(Address, Address),
     /// Sum of amounts across all pending (non-executed) proposals.
     Reserved,
+    /// Governance contract allowed to release funds without multi-sig approval.
+    Governance,
+    /// Treasury balance of a token other than the primary asset.
+    AssetBalance(Address),
+    /// Reserved amount of a token other than the primary asset.
+    AssetReserved(Address),
+    /// Tokens the treasury has held, primary asset first.
+    HeldTokens,
+    /// Cap on withdrawals per time window.
+    SpendLimit,
+    /// Amount of a token withdrawn in the current window.
+    SpendWindow(Address),
+    /// Approval weight of a signer; 1 unless configured otherwise.
+    SignerWeight(Address),
+    /// Non-signer allowed to propose withdrawals.
+    Requester(Address),
+    /// Lifetime deposits of the primary asset by a depositor.
+    DepositTotal(Address),
+    /// Lifetime deposits of the primary asset across all depositors.
+    TotalDeposited,
+    /// Lifetime outflow of the primary asset, fees included.
+    TotalWithdrawn,
+    /// Whether proposing a withdrawal counts as the proposer's approval.
+    ProposerAutoApproves,
+    /// Combined signer weight of rejections that kills a transaction.
+    RejectThreshold,
+    /// Approval weight needed to execute a refund.
+    RefundThreshold,
+    /// Approval threshold as a percentage of the current signer count.
+    ThresholdPercent,
+    /// Primary asset balance withdrawals may never dip into.
+    MinReserve,
+    /// Backup allowed to approve on a signer's behalf.
+    ApprovalDelegate(Address),
+    /// Signer a backup approves for.
+    DelegatedBy(Address),
+    /// Reason a signer gave for approving or rejecting a transaction.
+    Reason(u64, Address),
+    /// Whether withdrawals a signer proposes to themselves need every signer.
+    StrictSelfWithdrawal,
+    /// Marks a withdrawal proposed to its own proposer under strict mode.
+    SelfDirected(u64),
+    /// Marks a recurring schedule paying its own proposer under strict mode.
+    SelfDirectedSchedule(u64),
+    /// Access-control contract whose admins also act as signers.
+    AccessControl,
+    /// Recurring withdrawal schedule by ID.
+    Schedule(u64),
+    /// Counter for recurring schedule IDs.
+    ScheduleCounter,
+    /// Whether one round of approvals covers every payment of a schedule.
+    ApproveScheduleOnce,
+    /// Fee charged on executed withdrawals.
+    WithdrawalFee,
+    /// Admin proposed by the current admin, awaiting acceptance.
+    PendingAdmin,
+    /// Set while a guarded entry point is running.
+    Locked,
+    /// Contract version that last wrote the stored data.
+    Version,
+}
+
+/// Fee charged on executed withdrawals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalFee {
+    /// Fee in basis points of the withdrawal amount.
+    pub fee_bps: u32,
+    /// Address that receives the fee.
+    pub collector: Address,
+    /// Whether the fee is charged on top of the amount rather than taken
+    /// out of it.
+    pub on_top: bool,
+}
+
+/// A recurring withdrawal paid out once per interval.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurringSchedule {
+    /// Unique identifier.
+    pub id: u64,
+    /// Address that proposed the schedule.
+    pub proposer: Address,
+    /// Destination address for each payment.
+    pub to: Address,
+    /// Token contract each payment is made in.
+    pub token: Address,
+    /// Amount of each payment.
+    pub amount: i128,
+    /// Text description / memo for the schedule.
+    pub memo: String,
+    /// Seconds between payments.
+    pub interval: u64,
+    /// Total number of payments.
+    pub count: u32,
+    /// Payments made so far.
+    pub paid: u32,
+    /// Timestamp from which the next payment can be made.
+    pub next_due: u64,
+    /// Addresses that have approved the current payment.
+    pub approvals: Vec<Address>,
+}
+
+/// Cap on how much of each token can leave the treasury per time window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendLimit {
+    /// Maximum amount per window; zero disables the limit.
+    pub limit: i128,
+    /// Window length in seconds.
+    pub window: u64,
+}
+
+/// Withdrawals of a token in the current spending window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendWindow {
+    /// Timestamp the window started.
+    pub started_at: u64,
+    /// Amount withdrawn since the window started.
+    pub spent: i128,
+}
+
+/// How an execution is paid out, worked out before any funds move.
+struct Payment {
+    /// Amount of the transaction being paid.
+    amount: i128,
+    /// Amount the recipient receives.
+    payout: i128,
+    /// Amount leaving the treasury balance, fee included.
+    debit: i128,
+    fee: Option<WithdrawalFee>,
+    fee_amount: i128,
 }
 
 /// A pending transaction proposal in the multi-sig treasury.
@@ -79,18 +278,70 @@ pub struct Transaction {
     pub id: u64,
     /// Destination address for the withdrawal.
     pub to: Address,
+    /// Token contract the withdrawal is paid in.
+    pub token: Address,
     /// Amount to withdraw (in stroops).
     pub amount: i128,
+    /// Amount paid out so far by `execute_partial`.
+    pub paid: i128,
     /// Text description / memo for the transaction.
     pub memo: String,
     /// Addresses that have approved this transaction.
     pub approvals: Vec<Address>,
     /// Whether the transaction has been executed.
     pub executed: bool,
+    /// Whether the proposal was cancelled before execution.
+    pub cancelled: bool,
+    /// Addresses that have rejected this transaction.
+    pub rejections: Vec<Address>,
+    /// Whether enough signers rejected the transaction to kill it.
+    pub rejected: bool,
     /// Timestamp when the transaction was proposed.
     pub created_at: u64,
+    /// Timestamp before which the transaction cannot execute.
+    pub unlock_at: u64,
     /// Address that proposed the transaction.
     pub proposer: Address,
+    /// Whether this returns a deposit through `request_refund`.
+    pub refund: bool,
+}
+
+/// Lifecycle state of a transaction, derived by `get_transaction_status`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionStatus {
+    /// Awaiting approvals or its unlock time.
+    Pending,
+    /// Approved and unlocked; ready to execute.
+    Executable,
+    /// Paid out in full.
+    Executed,
+    /// Cancelled by its proposer or the admin.
+    Cancelled,
+    /// Killed by signer rejections.
+    Rejected,
+}
+
+/// A page of transactions returned by `list_pending`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionPage {
+    /// Transactions on this page.
+    pub items: Vec<Transaction>,
+    /// `start_id` for the next page, if there is one.
+    pub next_cursor: Option<u64>,
+    /// Whether more pending transactions follow this page.
+    pub has_more: bool,
+}
+
+/// A backup allowed to approve on a signer's behalf until `expires_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalDelegation {
+    /// Address approving for the signer.
+    pub delegate: Address,
+    /// Timestamp from which the delegation no longer applies.
+    pub expires_at: u64,
 }
 
 /// Treasury configuration data.
@@ -99,14 +350,72 @@ pub struct Transaction {
 pub struct TreasuryConfig {
     /// Admin address.
     pub admin: Address,
-    /// Required approval threshold.
+    /// Required approval weight, as currently in effect.
     pub threshold: u32,
+    /// Percentage of signers whose approval is required; zero when the
+    /// threshold is an absolute weight.
+    pub threshold_percent: u32,
+    /// Primary asset balance withdrawals may never dip into.
+    pub min_reserve: i128,
+    /// Whether self-directed withdrawals need every signer's approval.
+    pub strict_self_withdrawal: bool,
     /// Number of signers.
     pub signer_count: u32,
-    /// Current balance (in stroops).
+    /// Current balance of the primary asset (in stroops).
     pub balance: i128,
+    /// Portion of the primary asset balance reserved by pending withdrawal
+    /// proposals.
+    pub reserved: i128,
+    /// Primary asset balance not reserved by pending proposals or held back
+    /// as the minimum reserve.
+    pub available: i128,
+    /// Balance of every token the treasury has held.
+    pub balances: Map<Address, i128>,
     /// Total transactions proposed.
     pub tx_count: u64,
+    /// Lifetime deposits of the primary asset.
+    pub total_deposited: i128,
+    /// Lifetime outflow of the primary asset, fees included.
+    pub total_withdrawn: i128,
+    /// Maximum amount of each token withdrawn per window; zero when disabled.
+    pub spend_limit: i128,
+    /// Spending window length in seconds.
+    pub limit_window: u64,
+    /// Whether withdrawals are halted by an emergency freeze.
+    pub frozen: bool,
+    /// Whether proposing a withdrawal counts as the proposer's approval.
+    pub proposer_auto_approves: bool,
+    /// Combined signer weight of rejections that kills a transaction.
+    pub reject_threshold: u32,
+    /// Approval weight needed to execute a refund.
+    pub refund_threshold: u32,
+    /// Access-control contract whose admins also act as signers, if any.
+    pub access_control: Option<Address>,
+    /// Withdrawal fee in basis points; zero when disabled.
+    pub fee_bps: u32,
+    /// Address that receives withdrawal fees, if a fee is configured.
+    pub fee_collector: Option<Address>,
+}
+
+// ============================================================================
+// External Interfaces
+// ============================================================================
+
+/// Role levels of the access-control contract.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccessRole {
+    Viewer = 1,
+    Member = 2,
+    Admin = 3,
+    Owner = 4,
+}
+
+/// The subset of the access-control contract used for signer authority.
+#[contractclient(name = "AccessControlClient")]
+pub trait AccessControlInterface {
+    fn has_permission(env: Env, address: Address, required_role: AccessRole) -> bool;
 }
 
 // ============================================================================
@@ -156,6 +465,7 @@ impl TreasuryContract {
 
         // Store all initial state
         env.storage().instance().set(&DataKey::Initialized, &true);
+        env.storage().instance().set(&DataKey::Version, &VERSION);
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Asset, &asset);
         env.storage()
@@ -165,10 +475,17 @@ impl TreasuryContract {
         env.storage().instance().set(&DataKey::Balance, &0_i128);
         env.storage().instance().set(&DataKey::TxCounter, &0_u64);
         env.storage().instance().set(&DataKey::Reserved, &0_i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::HeldTokens, &Vec::from_array(&env, [asset]));
 
         // Emit initialization event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("init")),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("init"),
+                EVENT_VERSION,
+            ),
             (admin.clone(), threshold, signer_count),
         );
 
@@ -190,12 +507,13 @@ impl TreasuryContract {
     /// # Arguments
     /// * `env` - The contract environment.
     /// * `from` - The address depositing funds.
+    /// * `token` - The token contract to deposit.
     /// * `amount` - The amount to deposit (in stroops).
     ///
     /// # Errors
     /// * `Error::NotInitialized` - If the contract is not initialized.
     /// * `Error::InvalidAmount` - If the amount is zero or negative.
-    pub fn deposit(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+    pub fn deposit(env: Env, from: Address, token: Address, amount: i128) -> Result<(), Error> {
         Self::require_initialized(&env)?;
 
         if amount <= 0 {
@@ -204,26 +522,21 @@ impl TreasuryContract {
 
         from.require_auth();
 
-        let asset: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Asset)
-            .ok_or(Error::NotInitialized)?;
-
-        let token_client = token::TokenClient::new(&env, &asset);
+        let token_client = token::TokenClient::new(&env, &token);
         token_client.transfer(&from, &env.current_contract_address(), &amount);
 
         // Update balance
-        let current_balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
-        let new_balance = current_balance + amount;
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance, &new_balance);
+        let new_balance = Self::credit(&env, &token, amount);
+        Self::record_contribution(&env, &from, &token, amount);
 
         // Emit deposit event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("deposit")),
-            (from.clone(), amount, new_balance),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("deposit"),
+                EVENT_VERSION,
+            ),
+            (from.clone(), token.clone(), amount, new_balance),
         );
 
         log!(
@@ -236,10 +549,60 @@ impl TreasuryContract {
         Ok(())
     }
 
+    /// Deposit the primary asset on behalf of someone else, e.g. a payroll
+    /// service funding a department.
+    ///
+    /// `payer` provides the tokens, but the deposit counts toward
+    /// `beneficiary`'s total in `get_deposits_by`.
+    ///
+    /// # Errors
+    /// * `Error::NotInitialized` - If the contract is not initialized.
+    /// * `Error::InvalidAmount` - If the amount is zero or negative.
+    pub fn deposit_for(
+        env: Env,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        payer.require_auth();
+
+        let asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(Error::NotInitialized)?;
+        token::TokenClient::new(&env, &asset).transfer(
+            &payer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let new_balance = Self::credit(&env, &asset, amount);
+        Self::record_contribution(&env, &beneficiary, &asset, amount);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("dep_for"),
+                EVENT_VERSION,
+            ),
+            (payer, beneficiary, amount, new_balance),
+        );
+
+        Ok(())
+    }
+
     /// Deposit Soroban tokens (SAC or custom SEP-41 tokens) into the treasury.
     ///
     /// Calls `transfer` on the token contract to move `amount` tokens from
-    /// `from` into this contract, then records the balance indexed by the
+    /// `from` into this contract and credits the treasury balance like
+    /// `deposit`, then also records the balance indexed by the
     /// `(depositor, token_address)` pair in persistent storage so that
     /// different tokens — and different depositors — are always tracked
     /// independently.
@@ -272,6 +635,9 @@ impl TreasuryContract {
         let token_client = token::TokenClient::new(&env, &token_address);
         token_client.transfer(&from, &env.current_contract_address(), &amount);
 
+        Self::credit(&env, &token_address, amount);
+        Self::record_contribution(&env, &from, &token_address, amount);
+
         // Read and update the per-(depositor, token) balance.
         let storage_key = DataKey::TokenBalance(from.clone(), token_address.clone());
         let current_balance: i128 = env.storage().persistent().get(&storage_key).unwrap_or(0);
@@ -281,7 +647,11 @@ impl TreasuryContract {
         // Emit token-deposit event — topics mirror the native deposit but use
         // "dep_tok" to keep it distinct; data includes the token address.
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("dep_tok")),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("dep_tok"),
+                EVENT_VERSION,
+            ),
             (from.clone(), token_address.clone(), amount, new_balance),
         );
 
@@ -301,96 +671,170 @@ impl TreasuryContract {
     // ========================================================================
 
     /// Propose a withdrawal from the treasury.
-    /// Only authorized signers can propose withdrawals.
+    /// Only authorized signers and requesters set via `set_requester` can
+    /// propose withdrawals. A requester's proposal starts with no approvals.
     ///
     /// # Arguments
     /// * `env` - The contract environment.
-    /// * `proposer` - The signer proposing the withdrawal.
+    /// * `proposer` - The signer or requester proposing the withdrawal.
     /// * `to` - The destination address.
+    /// * `token` - The token contract to pay out.
     /// * `amount` - The amount to withdraw.
     /// * `memo` - A description of the withdrawal.
+    /// * `unlock_at` - Timestamp before which the withdrawal cannot execute;
+    ///   zero to allow execution as soon as it is approved.
     ///
     /// # Returns
     /// The ID of the created transaction proposal.
     ///
     /// # Errors
     /// * `Error::NotInitialized` - If the contract is not initialized.
-    /// * `Error::NotASigner` - If the proposer is not an authorized signer.
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If the proposer is neither a signer nor a requester.
     /// * `Error::InvalidAmount` - If the amount is zero or negative.
+    /// * `Error::MemoTooLong` - If the memo exceeds `MAX_MEMO_LEN` bytes.
     /// * `Error::InsufficientFunds` - If treasury balance is less than amount.
     pub fn propose_withdrawal(
         env: Env,
         proposer: Address,
         to: Address,
+        token: Address,
         amount: i128,
         memo: String,
+        unlock_at: u64,
     ) -> Result<u64, Error> {
         Self::require_initialized(&env)?;
-        Self::require_signer(&env, &proposer)?;
+        Self::require_not_frozen(&env)?;
+        let is_signer = Self::require_signer(&env, &proposer).is_ok();
+        if !is_signer && !Self::is_requester(env.clone(), proposer.clone()) {
+            return Err(Error::NotASigner);
+        }
 
         proposer.require_auth();
 
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(Error::MemoTooLong);
+        }
 
-        // Check sufficient *unreserved* balance — multiple pending proposals
-        // must not be able to collectively over-commit the same funds.
-        let balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
-        let reserved: i128 = env.storage().instance().get(&DataKey::Reserved).unwrap_or(0);
-        if balance - reserved < amount {
-            return Err(Error::InsufficientFunds);
+        // Create initial approval list, with a signing proposer unless their
+        // approval must be given explicitly
+        let mut approvals = Vec::new(&env);
+        if is_signer && Self::proposer_auto_approves(&env) {
+            approvals.push_back(proposer.clone());
         }
+        let self_directed = to == proposer && Self::strict_self_withdrawal(&env);
 
-        // Reserve the funds so subsequent proposals cannot reference them.
-        env.storage()
-            .instance()
-            .set(&DataKey::Reserved, &(reserved + amount));
+        let tx_id = Self::open_transaction(
+            &env,
+            Transaction {
+                id: 0,
+                to,
+                token,
+                amount,
+                paid: 0,
+                memo,
+                approvals,
+                executed: false,
+                cancelled: false,
+                rejections: Vec::new(&env),
+                rejected: false,
+                created_at: env.ledger().timestamp(),
+                unlock_at,
+                proposer,
+                refund: false,
+            },
+        )?;
+
+        // Flag self-dealing so signers know it needs all of them
+        if self_directed {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SelfDirected(tx_id), &true);
+            env.events().publish(
+                (
+                    symbol_short!("treasury"),
+                    symbol_short!("self_wd"),
+                    EVENT_VERSION,
+                ),
+                tx_id,
+            );
+        }
 
-        // Get and increment counter
-        let tx_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TxCounter)
-            .unwrap_or(0);
-        let next_id = tx_id + 1;
-        env.storage().instance().set(&DataKey::TxCounter, &next_id);
+        Ok(tx_id)
+    }
 
-        // Create initial approval list with proposer
-        let mut approvals = Vec::new(&env);
-        approvals.push_back(proposer.clone());
+    /// Ask for deposits of the primary asset to be returned.
+    ///
+    /// Creates a withdrawal to the depositor tagged as a refund, which
+    /// signers approve as usual, but against the refund threshold. The
+    /// depositor's lifetime total is reduced straight away and restored if
+    /// the refund is cancelled or rejected.
+    ///
+    /// # Returns
+    /// The ID of the created transaction proposal.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::InvalidAmount` - If the amount is zero or negative.
+    /// * `Error::RefundTooLarge` - If amount exceeds the depositor's total.
+    /// * `Error::InsufficientFunds` - If the unreserved balance is less than amount.
+    pub fn request_refund(env: Env, depositor: Address, amount: i128) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_frozen(&env)?;
 
-        // Build transaction proposal
-        let transaction = Transaction {
-            id: next_id,
-            to: to.clone(),
-            amount,
-            memo: memo.clone(),
-            approvals,
-            executed: false,
-            created_at: env.ledger().timestamp(),
-            proposer: proposer.clone(),
-        };
+        depositor.require_auth();
 
-        // Store transaction
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let total_key = DataKey::DepositTotal(depositor.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        if amount > total {
+            return Err(Error::RefundTooLarge);
+        }
         env.storage()
             .persistent()
-            .set(&DataKey::Transaction(next_id), &transaction);
+            .set(&total_key, &(total - amount));
+
+        let asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(Error::NotInitialized)?;
+        let tx_id = Self::open_transaction(
+            &env,
+            Transaction {
+                id: 0,
+                to: depositor.clone(),
+                token: asset,
+                amount,
+                paid: 0,
+                memo: String::from_str(&env, "refund"),
+                approvals: Vec::new(&env),
+                executed: false,
+                cancelled: false,
+                rejections: Vec::new(&env),
+                rejected: false,
+                created_at: env.ledger().timestamp(),
+                unlock_at: 0,
+                proposer: depositor.clone(),
+                refund: true,
+            },
+        )?;
 
-        // Emit proposal event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("propose")),
-            (next_id, proposer.clone(), to, amount),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("refund"),
+                EVENT_VERSION,
+            ),
+            (tx_id, depositor, amount),
         );
 
-        log!(
-            &env,
-            "Withdrawal proposal #{} created by {:?} for {}",
-            next_id,
-            proposer,
-            amount
-        );
-        Ok(next_id)
+        Ok(tx_id)
     }
 
     // ========================================================================
@@ -398,827 +842,5011 @@ impl TreasuryContract {
     // ========================================================================
 
     /// Approve a pending withdrawal transaction.
-    /// Once the threshold is reached, the transaction can be executed.
+    /// Once the approvers' combined weight reaches the threshold, the
+    /// transaction can be executed.
     ///
     /// # Arguments
     /// * `env` - The contract environment.
-    /// * `signer` - The signer approving the transaction.
+    /// * `signer` - The signer approving the transaction, or a delegate set
+    ///   via `set_approval_delegate`, whose approval counts as the signer's.
     /// * `tx_id` - The ID of the transaction to approve.
     ///
     /// # Returns
-    /// The current approval count.
+    /// The combined weight of the current approvals.
     ///
     /// # Errors
-    /// * `Error::NotASigner` - If the caller is not a signer.
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If the caller is neither a signer nor an
+    ///   active delegate.
     /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
     /// * `Error::AlreadyApproved` - If signer already approved.
+    /// * `Error::AlreadyRejected` - If signer already rejected.
+    /// * `Error::Cancelled` - If the transaction was cancelled.
+    /// * `Error::TransactionRejected` - If the transaction was rejected.
     /// * `Error::AlreadyExecuted` - If transaction is already executed.
     pub fn approve(env: Env, signer: Address, tx_id: u64) -> Result<u32, Error> {
         Self::require_initialized(&env)?;
-        Self::require_signer(&env, &signer)?;
+        Self::require_not_frozen(&env)?;
+        let approver = Self::approver_for(&env, &signer)?;
 
         signer.require_auth();
 
-        // Load transaction
-        let mut transaction: Transaction = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Transaction(tx_id))
-            .ok_or(Error::TransactionNotFound)?;
+        Self::record_approval(&env, &approver, tx_id)
+    }
 
-        if transaction.executed {
-            return Err(Error::AlreadyExecuted);
+    /// Approve a pending withdrawal like `approve`, recording why.
+    ///
+    /// The reason is kept for auditors and returned by `get_reason`. It
+    /// does not affect the approval's weight.
+    ///
+    /// # Errors
+    /// * `Error::ReasonTooLong` - If the reason exceeds `MAX_REASON_LEN` bytes.
+    /// * Any error returned by `approve`.
+    pub fn approve_with_reason(
+        env: Env,
+        signer: Address,
+        tx_id: u64,
+        reason: String,
+    ) -> Result<u32, Error> {
+        if reason.len() > MAX_REASON_LEN {
+            return Err(Error::ReasonTooLong);
         }
 
-        // Check if already approved by this signer
-        for i in 0..transaction.approvals.len() {
-            if transaction.approvals.get(i).unwrap() == signer {
-                return Err(Error::AlreadyApproved);
-            }
-        }
+        let approval_count = Self::approve(env.clone(), signer.clone(), tx_id)?;
+        let approver = Self::approver_for(&env, &signer)?;
+        Self::record_reason(&env, tx_id, &approver, reason);
+        Ok(approval_count)
+    }
 
-        // Add approval
-        transaction.approvals.push_back(signer.clone());
-        let approval_count = transaction.approvals.len();
+    /// Let `delegate` approve on the signer's behalf until `expires_at`.
+    ///
+    /// The delegate's approvals are credited to the signer, so the signer
+    /// and delegate together still count once. Setting a new delegate
+    /// replaces the previous one.
+    ///
+    /// # Errors
+    /// * `Error::NotASigner` - If the caller is not a signer.
+    /// * `Error::InvalidDelegation` - If the delegate is a signer or already
+    ///   backs another signer, or `expires_at` has passed.
+    pub fn set_approval_delegate(
+        env: Env,
+        signer: Address,
+        delegate: Address,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_signer(&env, &signer)?;
 
-        // Save updated transaction
+        signer.require_auth();
+
+        if expires_at <= env.ledger().timestamp() || Self::require_signer(&env, &delegate).is_ok() {
+            return Err(Error::InvalidDelegation);
+        }
+        let delegated_by: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegatedBy(delegate.clone()));
+        if delegated_by.is_some_and(|other| other != signer) {
+            return Err(Error::InvalidDelegation);
+        }
+
+        Self::clear_approval_delegate(&env, &signer);
+        env.storage().persistent().set(
+            &DataKey::ApprovalDelegate(signer.clone()),
+            &ApprovalDelegation {
+                delegate: delegate.clone(),
+                expires_at,
+            },
+        );
         env.storage()
             .persistent()
-            .set(&DataKey::Transaction(tx_id), &transaction);
+            .set(&DataKey::DelegatedBy(delegate.clone()), &signer);
 
-        // Emit approval event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("approve")),
-            (tx_id, signer.clone(), approval_count),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("delegate"),
+                EVENT_VERSION,
+            ),
+            (signer, delegate, expires_at),
         );
 
-        log!(
-            &env,
-            "Transaction #{} approved by {:?} ({} approvals)",
-            tx_id,
+        Ok(())
+    }
+
+    /// Stop a signer's delegate from approving on their behalf.
+    pub fn revoke_approval_delegate(env: Env, signer: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        signer.require_auth();
+
+        Self::clear_approval_delegate(&env, &signer);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("undeleg"),
+                EVENT_VERSION,
+            ),
             signer,
-            approval_count
         );
-        Ok(approval_count)
+
+        Ok(())
     }
 
-    /// Execute a fully approved withdrawal transaction.
-    /// Requires the approval count to meet or exceed the threshold.
+    /// Get a signer's approval delegate, if one is set.
+    pub fn get_approval_delegate(env: Env, signer: Address) -> Option<ApprovalDelegation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ApprovalDelegate(signer))
+    }
+
+    /// Approve several pending withdrawal transactions at once.
     ///
-    /// # Arguments
-    /// * `env` - The contract environment.
-    /// * `executor` - The signer executing the transaction.
-    /// * `tx_id` - The ID of the transaction to execute.
+    /// Transactions that cannot be approved — missing, cancelled, executed or
+    /// already approved by `signer` — are skipped instead of failing the
+    /// whole batch.
     ///
     /// # Returns
-    /// Ok(()) on successful execution.
+    /// The combined approval weight of each listed transaction after the
+    /// call, in order, or 0 for skipped transactions.
     ///
     /// # Errors
-    /// * `Error::NotASigner` - If executor is not a signer.
-    /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
-    /// * `Error::AlreadyExecuted` - If already executed.
-    /// * `Error::Unauthorized` - If approval threshold not met.
-    /// * `Error::InsufficientFunds` - If treasury has insufficient balance.
-    pub fn execute(env: Env, executor: Address, tx_id: u64) -> Result<(), Error> {
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If the caller is not a signer.
+    /// * `Error::BatchTooLarge` - If more than `MAX_BATCH_SIZE` ids are passed.
+    pub fn approve_batch(env: Env, signer: Address, tx_ids: Vec<u64>) -> Result<Vec<u32>, Error> {
         Self::require_initialized(&env)?;
-        Self::require_signer(&env, &executor)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_signer(&env, &signer)?;
 
-        executor.require_auth();
+        if tx_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
 
-        let mut transaction: Transaction = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Transaction(tx_id))
-            .ok_or(Error::TransactionNotFound)?;
+        signer.require_auth();
 
-        if transaction.executed {
-            return Err(Error::AlreadyExecuted);
+        let mut counts = Vec::new(&env);
+        for tx_id in tx_ids.iter() {
+            counts.push_back(Self::record_approval(&env, &signer, tx_id).unwrap_or(0));
         }
+        Ok(counts)
+    }
 
-        // Check threshold
-        let threshold: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Threshold)
-            .unwrap_or(1);
-        if transaction.approvals.len() < threshold {
-            return Err(Error::Unauthorized);
-        }
+    /// Withdraw a previous approval of a pending transaction.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment.
+    /// * `signer` - The signer revoking their approval.
+    /// * `tx_id` - The ID of the transaction.
+    ///
+    /// # Returns
+    /// The combined weight of the remaining approvals.
+    ///
+    /// # Errors
+    /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
+    /// * `Error::Cancelled` - If the transaction was cancelled.
+    /// * `Error::TransactionRejected` - If the transaction was rejected.
+    /// * `Error::AlreadyExecuted` - If transaction is already executed.
+    /// * `Error::ApprovalNotFound` - If the signer has not approved it.
+    pub fn revoke_approval(env: Env, signer: Address, tx_id: u64) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
 
-        // Deduct balance
-        let current_balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
-        if current_balance < transaction.amount {
-            return Err(Error::InsufficientFunds);
-        }
+        signer.require_auth();
 
-        let asset: Address = env
+        let mut transaction: Transaction = env
             .storage()
-            .instance()
-            .get(&DataKey::Asset)
-            .ok_or(Error::NotInitialized)?;
-        let token_client = token::TokenClient::new(&env, &asset);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &transaction.to,
-            &transaction.amount,
-        );
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)?;
 
-        let new_balance = current_balance - transaction.amount;
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance, &new_balance);
+        if transaction.cancelled {
+            return Err(Error::Cancelled);
+        }
+        if transaction.rejected {
+            return Err(Error::TransactionRejected);
+        }
+        if transaction.executed {
+            return Err(Error::AlreadyExecuted);
+        }
 
-        // Release the reservation that was placed at proposal time.
-        let reserved: i128 = env.storage().instance().get(&DataKey::Reserved).unwrap_or(0);
+        let index = transaction
+            .approvals
+            .first_index_of(&signer)
+            .ok_or(Error::ApprovalNotFound)?;
+        transaction.approvals.remove(index);
+        let approval_count = Self::approval_weight(&env, &transaction.approvals);
         env.storage()
-            .instance()
-            .set(&DataKey::Reserved, &(reserved - transaction.amount));
+            .persistent()
+            .remove(&DataKey::Reason(tx_id, signer.clone()));
 
-        // Mark as executed
-        transaction.executed = true;
         env.storage()
             .persistent()
             .set(&DataKey::Transaction(tx_id), &transaction);
 
-        // Emit execution event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("execute")),
             (
-                tx_id,
-                transaction.to.clone(),
-                transaction.amount,
-                new_balance,
-                true,
+                symbol_short!("treasury"),
+                symbol_short!("revoke"),
+                EVENT_VERSION,
             ),
+            (tx_id, signer.clone(), approval_count),
         );
 
         log!(
             &env,
-            "Transaction #{} executed: {} to {:?}",
+            "Approval of transaction #{} revoked by {:?} ({} approval weight)",
             tx_id,
-            transaction.amount,
-            transaction.to
+            signer,
+            approval_count
         );
-        Ok(())
+        Ok(approval_count)
     }
 
-    // ========================================================================
-    // Signer Management
-    // ========================================================================
-
-    /// Add a new signer to the multi-sig treasury.
-    /// Only the admin can add signers.
-    pub fn add_signer(env: Env, admin: Address, new_signer: Address) -> Result<(), Error> {
+    /// Reject a pending withdrawal transaction.
+    ///
+    /// Once the rejecting signers' combined weight reaches the reject
+    /// threshold, the transaction is permanently dead and its reserved funds
+    /// are released. A signer cannot both approve and reject a transaction.
+    ///
+    /// # Returns
+    /// The combined weight of the rejections.
+    ///
+    /// # Errors
+    /// * `Error::NotASigner` - If the caller is not a signer.
+    /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
+    /// * `Error::Cancelled` - If the transaction was cancelled.
+    /// * `Error::TransactionRejected` - If the transaction was already rejected.
+    /// * `Error::AlreadyExecuted` - If transaction is already executed.
+    /// * `Error::AlreadyApproved` - If signer approved the transaction.
+    /// * `Error::AlreadyRejected` - If signer already rejected.
+    pub fn reject(env: Env, signer: Address, tx_id: u64) -> Result<u32, Error> {
         Self::require_initialized(&env)?;
-        Self::require_admin(&env, &admin)?;
+        Self::require_signer(&env, &signer)?;
 
-        admin.require_auth();
+        signer.require_auth();
 
-        let mut signers: Vec<Address> = env
+        let mut transaction: Transaction = env
             .storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(&env));
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)?;
 
-        // Check if already a signer
-        for i in 0..signers.len() {
-            if signers.get(i).unwrap() == new_signer {
-                return Err(Error::AlreadySigner);
-            }
+        if transaction.cancelled {
+            return Err(Error::Cancelled);
+        }
+        if transaction.rejected {
+            return Err(Error::TransactionRejected);
+        }
+        if transaction.executed {
+            return Err(Error::AlreadyExecuted);
+        }
+        if transaction.approvals.contains(&signer) {
+            return Err(Error::AlreadyApproved);
+        }
+        if transaction.rejections.contains(&signer) {
+            return Err(Error::AlreadyRejected);
         }
 
-        signers.push_back(new_signer.clone());
-        env.storage().instance().set(&DataKey::Signers, &signers);
+        transaction.rejections.push_back(signer.clone());
+        let rejection_weight = Self::approval_weight(&env, &transaction.rejections);
+        if rejection_weight >= Self::reject_threshold(&env) {
+            transaction.rejected = true;
+            Self::close_unpaid(&env, &transaction);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(tx_id), &transaction);
 
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("add_sig")),
-            (new_signer.clone(), signers.len()),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("reject"),
+                EVENT_VERSION,
+            ),
+            (
+                tx_id,
+                signer.clone(),
+                rejection_weight,
+                transaction.rejected,
+            ),
         );
 
-        Ok(())
+        log!(
+            &env,
+            "Transaction #{} rejected by {:?} ({} rejection weight)",
+            tx_id,
+            signer,
+            rejection_weight
+        );
+        Ok(rejection_weight)
     }
 
-    /// Remove a signer from the multi-sig treasury.
-    /// Only the admin can remove signers. Cannot reduce below threshold.
-    pub fn remove_signer(env: Env, admin: Address, signer: Address) -> Result<(), Error> {
-        Self::require_initialized(&env)?;
-        Self::require_admin(&env, &admin)?;
-
-        admin.require_auth();
-
-        let signers: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(&env));
-
-        let threshold: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Threshold)
-            .unwrap_or(1);
-
-        // Cannot remove if it would breach threshold
-        if signers.len() <= threshold {
-            return Err(Error::ThresholdBreach);
-        }
-
-        // Find and remove the signer
-        let mut new_signers = Vec::new(&env);
-        let mut found = false;
-        for i in 0..signers.len() {
-            let s = signers.get(i).unwrap();
-            if s == signer {
-                found = true;
-            } else {
-                new_signers.push_back(s);
-            }
+    /// Reject a pending withdrawal like `reject`, recording why.
+    ///
+    /// # Errors
+    /// * `Error::ReasonTooLong` - If the reason exceeds `MAX_REASON_LEN` bytes.
+    /// * Any error returned by `reject`.
+    pub fn reject_with_reason(
+        env: Env,
+        signer: Address,
+        tx_id: u64,
+        reason: String,
+    ) -> Result<u32, Error> {
+        if reason.len() > MAX_REASON_LEN {
+            return Err(Error::ReasonTooLong);
         }
 
-        if !found {
-            return Err(Error::NotASigner);
-        }
+        let rejection_weight = Self::reject(env.clone(), signer.clone(), tx_id)?;
+        Self::record_reason(&env, tx_id, &signer, reason);
+        Ok(rejection_weight)
+    }
 
+    /// Get the reason a signer gave for approving or rejecting a
+    /// transaction, if any.
+    pub fn get_reason(env: Env, tx_id: u64, signer: Address) -> Option<String> {
         env.storage()
-            .instance()
-            .set(&DataKey::Signers, &new_signers);
+            .persistent()
+            .get(&DataKey::Reason(tx_id, signer))
+    }
 
-        env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("rem_sig")),
-            (signer.clone(), new_signers.len()),
-        );
+    /// Execute a fully approved withdrawal transaction.
+    /// Requires the approvers' combined weight to meet or exceed the
+    /// threshold.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment.
+    /// * `executor` - The signer executing the transaction.
+    /// * `tx_id` - The ID of the transaction to execute.
+    ///
+    /// # Returns
+    /// Ok(()) on successful execution.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If executor is not a signer.
+    /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
+    /// * `Error::Cancelled` - If the transaction was cancelled.
+    /// * `Error::TransactionRejected` - If the transaction was rejected.
+    /// * `Error::AlreadyExecuted` - If already executed.
+    /// * `Error::Unauthorized` - If approval threshold not met.
+    /// * `Error::TimelockActive` - If the unlock time has not been reached.
+    /// * `Error::InsufficientFunds` - If treasury has insufficient balance.
+    /// * `Error::SpendLimitExceeded` - If the spending limit would be exceeded.
+    pub fn execute(env: Env, executor: Address, tx_id: u64) -> Result<(), Error> {
+        Self::execute_amount(env, executor, tx_id, None)
+    }
+
+    /// Check whether `execute` would currently succeed for a transaction,
+    /// without moving funds or changing state.
+    ///
+    /// Runs the same checks as `execute` apart from the executor's, so a UI
+    /// can show a transaction as ready or why it is blocked.
+    ///
+    /// # Errors
+    /// The first error `execute` would return, e.g. `Error::Frozen`,
+    /// `Error::Unauthorized`, `Error::TimelockActive`,
+    /// `Error::InsufficientFunds` or `Error::SpendLimitExceeded`.
+    pub fn can_execute(env: Env, tx_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_frozen(&env)?;
 
+        let transaction = Self::get_transaction(env.clone(), tx_id)?;
+        Self::check_execution(&env, &transaction, None)?;
         Ok(())
     }
 
-    /// Update the approval threshold.
-    /// Only the admin can change the threshold.
-    pub fn set_threshold(env: Env, admin: Address, new_threshold: u32) -> Result<(), Error> {
+    /// Pay out part of an approved withdrawal, e.g. when an invoice comes in
+    /// below the approved amount.
+    ///
+    /// The same checks as `execute` apply. The transaction stays open for the
+    /// remainder, with its reservation reduced by `amount`, and is marked
+    /// executed once nothing remains.
+    ///
+    /// # Errors
+    /// * `Error::InvalidAmount` - If amount is not positive or exceeds what
+    ///   remains unpaid.
+    /// * Any error returned by `execute`.
+    pub fn execute_partial(
+        env: Env,
+        executor: Address,
+        tx_id: u64,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::execute_amount(env, executor, tx_id, Some(amount))
+    }
+
+    /// Pay `amount` of a transaction, or all that remains unpaid when `None`.
+    fn execute_amount(
+        env: Env,
+        executor: Address,
+        tx_id: u64,
+        amount: Option<i128>,
+    ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
-        Self::require_admin(&env, &admin)?;
+        Self::lock(&env)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_signer(&env, &executor)?;
 
-        admin.require_auth();
+        executor.require_auth();
 
-        let signers: Vec<Address> = env
+        let mut transaction: Transaction = env
             .storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(&env));
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)?;
 
-        if new_threshold == 0 || new_threshold > signers.len() {
-            return Err(Error::InvalidThreshold);
+        let Payment {
+            amount,
+            payout,
+            debit,
+            fee,
+            fee_amount,
+        } = Self::check_execution(&env, &transaction, amount)?;
+        Self::record_spend(&env, &transaction.token, debit)?;
+        let balance_key = Self::balance_key(&env, &transaction.token);
+        let current_balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
+
+        let token_client = token::TokenClient::new(&env, &transaction.token);
+        token_client.transfer(&env.current_contract_address(), &transaction.to, &payout);
+        if let (Some(fee), true) = (fee, fee_amount > 0) {
+            token_client.transfer(&env.current_contract_address(), &fee.collector, &fee_amount);
+            env.events().publish(
+                (
+                    symbol_short!("treasury"),
+                    symbol_short!("fee"),
+                    EVENT_VERSION,
+                ),
+                (tx_id, fee.collector, fee_amount),
+            );
         }
 
+        let new_balance = current_balance - debit;
+        env.storage().instance().set(&balance_key, &new_balance);
+        Self::record_outflow(&env, &transaction.token, debit);
+
+        // Release the part of the reservation placed at proposal time that
+        // this payment covers.
+        Self::release_reservation(&env, &transaction.token, amount);
+
+        // Mark as executed once nothing remains
+        transaction.paid += amount;
+        transaction.executed = transaction.paid == transaction.amount;
         env.storage()
-            .instance()
-            .set(&DataKey::Threshold, &new_threshold);
+            .persistent()
+            .set(&DataKey::Transaction(tx_id), &transaction);
 
+        // Emit execution event
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("thresh")),
-            new_threshold,
+            (
+                symbol_short!("treasury"),
+                symbol_short!("execute"),
+                EVENT_VERSION,
+            ),
+            (
+                tx_id,
+                transaction.to.clone(),
+                transaction.token.clone(),
+                amount,
+                new_balance,
+                transaction.executed,
+            ),
         );
 
+        log!(
+            &env,
+            "Transaction #{} executed: {} to {:?}",
+            tx_id,
+            amount,
+            transaction.to
+        );
+        Self::unlock(&env);
         Ok(())
     }
 
-    // ========================================================================
-    // Query Functions
-    // ========================================================================
-
-    /// Get the current treasury balance.
-    pub fn get_balance(env: Env) -> i128 {
-        env.storage().instance().get(&DataKey::Balance).unwrap_or(0)
-    }
-
-    /// Get the token balance for a specific depositor and token contract.
-    ///
-    /// Returns `0` if no deposit has been made for this pair.
+    /// Cancel a pending withdrawal proposal and release its reserved funds.
     ///
     /// # Arguments
     /// * `env` - The contract environment.
-    /// * `depositor` - The address whose balance to query.
-    /// * `token_address` - The token contract address.
-    pub fn get_token_balance(env: Env, depositor: Address, token_address: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::TokenBalance(depositor, token_address))
-            .unwrap_or(0)
-    }
-
-    /// Get the treasury configuration.
-    pub fn get_config(env: Env) -> Result<TreasuryConfig, Error> {
+    /// * `caller` - The original proposer or the admin.
+    /// * `tx_id` - The ID of the transaction to cancel.
+    ///
+    /// # Errors
+    /// * `Error::TransactionNotFound` - If the transaction doesn't exist.
+    /// * `Error::Unauthorized` - If caller is neither proposer nor admin.
+    /// * `Error::Cancelled` - If the transaction was already cancelled.
+    /// * `Error::TransactionRejected` - If the transaction was rejected.
+    /// * `Error::AlreadyExecuted` - If transaction is already executed.
+    pub fn cancel_withdrawal(env: Env, caller: Address, tx_id: u64) -> Result<(), Error> {
         Self::require_initialized(&env)?;
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        let threshold: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Threshold)
-            .unwrap_or(1);
-        let signers: Vec<Address> = env
+        let mut transaction: Transaction = env
             .storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(&env));
-        let balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
-        let tx_count: u64 = env
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)?;
+
+        if caller != transaction.proposer {
+            Self::require_admin(&env, &caller)?;
+        }
+
+        caller.require_auth();
+
+        if transaction.cancelled {
+            return Err(Error::Cancelled);
+        }
+        if transaction.rejected {
+            return Err(Error::TransactionRejected);
+        }
+        if transaction.executed {
+            return Err(Error::AlreadyExecuted);
+        }
+
+        Self::close_unpaid(&env, &transaction);
+
+        transaction.cancelled = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(tx_id), &transaction);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("cancel"),
+                EVENT_VERSION,
+            ),
+            (tx_id, caller.clone()),
+        );
+
+        log!(&env, "Transaction #{} cancelled by {:?}", tx_id, caller);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Recurring Withdrawals
+    // ========================================================================
+
+    /// Propose a withdrawal paid out `count` times, once every `interval`
+    /// seconds. The first payment is due immediately.
+    ///
+    /// Each payment needs threshold approvals of its own, unless the admin
+    /// has enabled approving a schedule once for all of its payments. Under
+    /// strict mode a schedule paying its own proposer needs every signer
+    /// instead of the threshold. Funds
+    /// are not reserved ahead of time; each payment needs an available
+    /// balance when executed.
+    ///
+    /// # Returns
+    /// The ID of the created schedule.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If the proposer is not an authorized signer.
+    /// * `Error::InvalidAmount` - If amount, interval or count is zero.
+    /// * `Error::MemoTooLong` - If the memo exceeds `MAX_MEMO_LEN` bytes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_recurring(
+        env: Env,
+        proposer: Address,
+        to: Address,
+        token: Address,
+        amount: i128,
+        memo: String,
+        interval: u64,
+        count: u32,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_signer(&env, &proposer)?;
+
+        proposer.require_auth();
+
+        if amount <= 0 || interval == 0 || count == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(Error::MemoTooLong);
+        }
+
+        let schedule_id: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::TxCounter)
+            .get(&DataKey::ScheduleCounter)
             .unwrap_or(0);
+        let next_id = schedule_id + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::ScheduleCounter, &next_id);
 
-        Ok(TreasuryConfig {
-            admin,
-            threshold,
-            signer_count: signers.len(),
-            balance,
-            tx_count,
-        })
-    }
+        let mut approvals = Vec::new(&env);
+        if Self::proposer_auto_approves(&env) {
+            approvals.push_back(proposer.clone());
+        }
+        let self_directed = to == proposer && Self::strict_self_withdrawal(&env);
 
-    /// Get a specific transaction by ID.
-    pub fn get_transaction(env: Env, tx_id: u64) -> Result<Transaction, Error> {
+        let schedule = RecurringSchedule {
+            id: next_id,
+            proposer: proposer.clone(),
+            to: to.clone(),
+            token,
+            amount,
+            memo,
+            interval,
+            count,
+            paid: 0,
+            next_due: env.ledger().timestamp(),
+            approvals,
+        };
         env.storage()
             .persistent()
-            .get(&DataKey::Transaction(tx_id))
-            .ok_or(Error::TransactionNotFound)
+            .set(&DataKey::Schedule(next_id), &schedule);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("prop_rec"),
+                EVENT_VERSION,
+            ),
+            (next_id, proposer, to, amount, interval, count),
+        );
+
+        // Flag self-dealing so signers know it needs all of them
+        if self_directed {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SelfDirectedSchedule(next_id), &true);
+            env.events().publish(
+                (
+                    symbol_short!("treasury"),
+                    symbol_short!("self_rec"),
+                    EVENT_VERSION,
+                ),
+                next_id,
+            );
+        }
+
+        Ok(next_id)
     }
 
-    /// Get the list of current signers.
-    pub fn get_signers(env: Env) -> Vec<Address> {
+    /// Approve the current payment of a recurring schedule.
+    ///
+    /// # Returns
+    /// The combined weight of the current approvals.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If the caller is not a signer.
+    /// * `Error::ScheduleNotFound` - If the schedule doesn't exist.
+    /// * `Error::ScheduleExhausted` - If every payment has been made.
+    /// * `Error::AlreadyApproved` - If signer already approved.
+    pub fn approve_recurring(env: Env, signer: Address, schedule_id: u64) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_signer(&env, &signer)?;
+
+        signer.require_auth();
+
+        let mut schedule: RecurringSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(Error::ScheduleNotFound)?;
+
+        if schedule.paid >= schedule.count {
+            return Err(Error::ScheduleExhausted);
+        }
+        if schedule.approvals.contains(&signer) {
+            return Err(Error::AlreadyApproved);
+        }
+
+        schedule.approvals.push_back(signer.clone());
+        let approval_count = Self::approval_weight(&env, &schedule.approvals);
         env.storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(&env))
+            .persistent()
+            .set(&DataKey::Schedule(schedule_id), &schedule);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("appr_rec"),
+                EVENT_VERSION,
+            ),
+            (schedule_id, signer, approval_count),
+        );
+
+        Ok(approval_count)
     }
 
-    // ========================================================================
-    // Admin Functions
-    // ========================================================================
+    /// Make the next payment of a recurring schedule once it is due and
+    /// approved, and advance the schedule by one interval. No withdrawal fee
+    /// is charged.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::NotASigner` - If executor is not a signer.
+    /// * `Error::ScheduleNotFound` - If the schedule doesn't exist.
+    /// * `Error::ScheduleExhausted` - If every payment has been made.
+    /// * `Error::PaymentNotDue` - If the next payment is not due yet.
+    /// * `Error::Unauthorized` - If approval threshold not met.
+    /// * `Error::InsufficientFunds` - If the available balance is too low.
+    /// * `Error::SpendLimitExceeded` - If the spending limit would be exceeded.
+    pub fn execute_recurring(env: Env, executor: Address, schedule_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::lock(&env)?;
+        Self::require_not_frozen(&env)?;
+        Self::require_signer(&env, &executor)?;
 
-    /// Transfer admin role to a new address.
-    pub fn transfer_admin(
+        executor.require_auth();
+
+        let mut schedule: RecurringSchedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(Error::ScheduleNotFound)?;
+
+        if schedule.paid >= schedule.count {
+            return Err(Error::ScheduleExhausted);
+        }
+        if env.ledger().timestamp() < schedule.next_due {
+            return Err(Error::PaymentNotDue);
+        }
+
+        let threshold = if Self::is_self_directed_schedule(env.clone(), schedule_id) {
+            Self::all_signers_weight(&env)
+        } else {
+            Self::approval_threshold(&env)
+        };
+        if Self::approval_weight(&env, &schedule.approvals) < threshold {
+            return Err(Error::Unauthorized);
+        }
+
+        // Pending one-off proposals keep their reservations
+        let balance_key = Self::balance_key(&env, &schedule.token);
+        let current_balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&Self::reserved_key(&env, &schedule.token))
+            .unwrap_or(0);
+        let min_reserve = Self::min_reserve(&env, &schedule.token);
+        if current_balance - reserved - min_reserve < schedule.amount {
+            return Err(Error::InsufficientFunds);
+        }
+        Self::record_spend(&env, &schedule.token, schedule.amount)?;
+
+        token::TokenClient::new(&env, &schedule.token).transfer(
+            &env.current_contract_address(),
+            &schedule.to,
+            &schedule.amount,
+        );
+
+        let new_balance = current_balance - schedule.amount;
+        env.storage().instance().set(&balance_key, &new_balance);
+        Self::record_outflow(&env, &schedule.token, schedule.amount);
+
+        schedule.paid += 1;
+        schedule.next_due = schedule.next_due.saturating_add(schedule.interval);
+        let approve_once: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ApproveScheduleOnce)
+            .unwrap_or(false);
+        if !approve_once {
+            schedule.approvals = Vec::new(&env);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule_id), &schedule);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("exec_rec"),
+                EVENT_VERSION,
+            ),
+            (
+                schedule_id,
+                schedule.to.clone(),
+                schedule.amount,
+                new_balance,
+                schedule.paid,
+            ),
+        );
+
+        log!(
+            &env,
+            "Recurring schedule #{} paid {} of {}",
+            schedule_id,
+            schedule.paid,
+            schedule.count
+        );
+        Self::unlock(&env);
+        Ok(())
+    }
+
+    /// Release funds on behalf of the governance contract.
+    ///
+    /// Lets a passed governance funding proposal pay out directly. Only the
+    /// governance contract configured via `set_governance` may call this, and
+    /// funds reserved for pending multi-sig proposals cannot be released.
+    /// No withdrawal fee is charged.
+    ///
+    /// # Errors
+    /// * `Error::Frozen` - If withdrawals are frozen.
+    /// * `Error::Unauthorized` - If `governance` is not the configured contract.
+    /// * `Error::InvalidAmount` - If amount <= 0.
+    /// * `Error::InsufficientFunds` - If the unreserved balance is less than amount.
+    /// * `Error::SpendLimitExceeded` - If the spending limit would be exceeded.
+    pub fn release_funds(
         env: Env,
-        current_admin: Address,
-        new_admin: Address,
+        governance: Address,
+        to: Address,
+        amount: i128,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
-        Self::require_admin(&env, &current_admin)?;
+        Self::lock(&env)?;
+        Self::require_not_frozen(&env)?;
 
-        current_admin.require_auth();
+        let stored_governance: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Governance)
+            .ok_or(Error::Unauthorized)?;
+        if governance != stored_governance {
+            return Err(Error::Unauthorized);
+        }
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        governance.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let current_balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Reserved)
+            .unwrap_or(0);
+        let min_reserve: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinReserve)
+            .unwrap_or(0);
+        if current_balance - reserved - min_reserve < amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        let asset: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(Error::NotInitialized)?;
+        Self::record_spend(&env, &asset, amount)?;
+        token::TokenClient::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+
+        let new_balance = current_balance - amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::Balance, &new_balance);
+        Self::record_outflow(&env, &asset, amount);
 
         env.events().publish(
-            (symbol_short!("treasury"), symbol_short!("admin")),
-            (current_admin, new_admin.clone()),
+            (
+                symbol_short!("treasury"),
+                symbol_short!("release"),
+                EVENT_VERSION,
+            ),
+            (to, amount, new_balance),
         );
 
+        Self::unlock(&env);
         Ok(())
     }
 
-    /// Upgrade the contract WASM. Admin only.
-    pub fn upgrade(
-        env: Env,
-        admin: Address,
-        new_wasm_hash: soroban_sdk::BytesN<32>,
-    ) -> Result<(), Error> {
+    /// Set the governance contract allowed to call `release_funds`.
+    /// Only the admin can change it.
+    pub fn set_governance(env: Env, admin: Address, governance: Address) -> Result<(), Error> {
         Self::require_initialized(&env)?;
         Self::require_admin(&env, &admin)?;
 
         admin.require_auth();
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
-        Ok(())
-    }
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("gov"),
+                EVENT_VERSION,
+            ),
+            governance,
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Signer Management
+    // ========================================================================
+
+    /// Add a new signer to the multi-sig treasury.
+    /// Only the admin can add signers.
+    pub fn add_signer(env: Env, admin: Address, new_signer: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if Self::is_zero_address(&env, &new_signer) {
+            return Err(Error::InvalidSigner);
+        }
+
+        let mut signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+
+        // Check if already a signer
+        for i in 0..signers.len() {
+            if signers.get(i).unwrap() == new_signer {
+                return Err(Error::AlreadySigner);
+            }
+        }
+
+        signers.push_back(new_signer.clone());
+        env.storage().instance().set(&DataKey::Signers, &signers);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("add_sig"),
+                EVENT_VERSION,
+            ),
+            (new_signer.clone(), signers.len()),
+        );
+
+        Ok(())
+    }
+
+    /// Remove a signer from the multi-sig treasury.
+    /// Only the admin can remove signers. Cannot reduce below threshold.
+    pub fn remove_signer(env: Env, admin: Address, signer: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(1);
+
+        // Find and remove the signer
+        let mut new_signers = Vec::new(&env);
+        let mut found = false;
+        for i in 0..signers.len() {
+            let s = signers.get(i).unwrap();
+            if s == signer {
+                found = true;
+            } else {
+                new_signers.push_back(s);
+            }
+        }
+
+        if !found {
+            return Err(Error::NotASigner);
+        }
+
+        // Cannot remove if the remaining signers could not meet the threshold
+        if Self::approval_weight(&env, &new_signers) < threshold {
+            return Err(Error::ThresholdBreach);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Signers, &new_signers);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SignerWeight(signer.clone()));
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("rem_sig"),
+                EVENT_VERSION,
+            ),
+            (signer.clone(), new_signers.len()),
+        );
+
+        Ok(())
+    }
+
+    /// Update the approval threshold, as a combined signer weight.
+    /// Only the admin can change the threshold.
+    pub fn set_threshold(env: Env, admin: Address, new_threshold: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+
+        if new_threshold == 0 || new_threshold > Self::approval_weight(&env, &signers) {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &new_threshold);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("thresh"),
+                EVENT_VERSION,
+            ),
+            new_threshold,
+        );
+
+        Ok(())
+    }
+
+    /// Allow or disallow a non-signer to propose withdrawals.
+    /// Requesters cannot approve, so their proposals still need signers.
+    /// Only the admin can manage requesters.
+    pub fn set_requester(
+        env: Env,
+        admin: Address,
+        requester: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let key = DataKey::Requester(requester.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("requester"),
+                EVENT_VERSION,
+            ),
+            (requester, allowed),
+        );
+
+        Ok(())
+    }
+
+    /// Check whether an address may propose withdrawals as a requester.
+    pub fn is_requester(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Requester(address))
+            .unwrap_or(false)
+    }
+
+    /// Set how much a signer's approval counts toward the threshold.
+    /// Only the admin can change signer weights.
+    pub fn set_signer_weight(
+        env: Env,
+        admin: Address,
+        signer: Address,
+        weight: u32,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        Self::require_signer(&env, &signer)?;
+        if weight == 0 {
+            return Err(Error::InvalidWeight);
+        }
+
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(1);
+        let remaining_weight =
+            Self::approval_weight(&env, &signers) - Self::signer_weight(&env, &signer);
+        if remaining_weight.saturating_add(weight) < threshold {
+            return Err(Error::ThresholdBreach);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SignerWeight(signer.clone()), &weight);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("weight"),
+                EVENT_VERSION,
+            ),
+            (signer, weight),
+        );
+
+        Ok(())
+    }
+
+    /// Cap how much of each token can be withdrawn per window of `window`
+    /// seconds. A limit of zero disables the cap.
+    /// Only the admin can change the limit.
+    pub fn set_spend_limit(
+        env: Env,
+        admin: Address,
+        limit: i128,
+        window: u64,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if limit < 0 || (limit > 0 && window == 0) {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SpendLimit, &SpendLimit { limit, window });
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("limit"),
+                EVENT_VERSION,
+            ),
+            (limit, window),
+        );
+
+        Ok(())
+    }
+
+    /// Let holders of the `Admin` role in an access-control contract act as
+    /// signers, in addition to the local signer list.
+    /// Only the admin can change the access-control contract.
+    pub fn set_access_control(
+        env: Env,
+        admin: Address,
+        access_control: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AccessControl, &access_control);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("acl"),
+                EVENT_VERSION,
+            ),
+            access_control,
+        );
+
+        Ok(())
+    }
+
+    /// Set the combined signer weight of rejections that kills a
+    /// transaction. Defaults to the approval threshold.
+    /// Only the admin can change the reject threshold.
+    pub fn set_reject_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+        if threshold == 0 || threshold > Self::approval_weight(&env, &signers) {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RejectThreshold, &threshold);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("rej_thr"),
+                EVENT_VERSION,
+            ),
+            threshold,
+        );
+
+        Ok(())
+    }
+
+    /// Require approval from a percentage of the current signers instead of
+    /// a fixed weight, so adding signers raises the bar. Zero switches back
+    /// to the fixed threshold set by `set_threshold`.
+    /// Only the admin can change the mode.
+    ///
+    /// # Errors
+    /// * `Error::InvalidThreshold` - If `percent` is above 100.
+    pub fn set_threshold_percent(env: Env, admin: Address, percent: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if percent > 100 {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ThresholdPercent, &percent);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("thr_pct"),
+                EVENT_VERSION,
+            ),
+            percent,
+        );
+
+        Ok(())
+    }
+
+    /// Set the approval weight needed to execute a refund requested through
+    /// `request_refund`. Defaults to the approval threshold.
+    /// Only the admin can change the refund threshold.
+    pub fn set_refund_threshold(env: Env, admin: Address, threshold: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+        if threshold == 0 || threshold > Self::approval_weight(&env, &signers) {
+            return Err(Error::InvalidThreshold);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RefundThreshold, &threshold);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("ref_thr"),
+                EVENT_VERSION,
+            ),
+            threshold,
+        );
+
+        Ok(())
+    }
+
+    /// Keep at least `min_reserve` of the primary asset in the treasury.
+    /// Proposals and executions that would leave less are rejected with
+    /// `Error::InsufficientFunds`. Zero removes the reserve.
+    /// Only the admin can change the reserve.
+    ///
+    /// # Errors
+    /// * `Error::InvalidAmount` - If `min_reserve` is negative.
+    pub fn set_min_reserve(env: Env, admin: Address, min_reserve: i128) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if min_reserve < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinReserve, &min_reserve);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("min_rsv"),
+                EVENT_VERSION,
+            ),
+            min_reserve,
+        );
+
+        Ok(())
+    }
+
+    /// Charge a fee of `fee_bps` basis points on executed withdrawals, paid
+    /// to `collector`. With `on_top` the recipient gets the full amount and
+    /// the fee is drawn from the treasury in addition; otherwise the fee is
+    /// taken out of the amount. A zero fee disables it. Recurring payments
+    /// and governance releases are paid in full without a fee.
+    /// Only the admin can change the fee.
+    pub fn set_withdrawal_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        collector: Address,
+        on_top: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if fee_bps > 10_000 {
+            return Err(Error::InvalidFee);
+        }
+
+        if fee_bps == 0 {
+            env.storage().instance().remove(&DataKey::WithdrawalFee);
+        } else {
+            env.storage().instance().set(
+                &DataKey::WithdrawalFee,
+                &WithdrawalFee {
+                    fee_bps,
+                    collector: collector.clone(),
+                    on_top,
+                },
+            );
+        }
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("set_fee"),
+                EVENT_VERSION,
+            ),
+            (fee_bps, collector, on_top),
+        );
+
+        Ok(())
+    }
+
+    /// Choose whether one round of approvals covers every payment of a
+    /// recurring schedule, instead of each payment needing its own.
+    /// Only the admin can change this.
+    pub fn set_approve_schedule_once(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ApproveScheduleOnce, &enabled);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("appr_once"),
+                EVENT_VERSION,
+            ),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Choose whether proposing a withdrawal also counts as the proposer's
+    /// approval (the default). When disabled, proposers must call `approve`
+    /// like any other signer. Applies to proposals created after the change.
+    /// Only the admin can change this.
+    pub fn set_proposer_auto_approves(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposerAutoApproves, &enabled);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("auto_appr"),
+                EVENT_VERSION,
+            ),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Choose whether a withdrawal or recurring schedule a signer proposes
+    /// to themselves needs approval from every signer instead of the
+    /// threshold. Applies to proposals created after the change. Only the admin can change this.
+    pub fn set_strict_self_withdrawal(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::StrictSelfWithdrawal, &enabled);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("strict_wd"),
+                EVENT_VERSION,
+            ),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Whether a withdrawal was flagged as self-directed and so needs every
+    /// signer's approval.
+    pub fn is_self_directed(env: Env, tx_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SelfDirected(tx_id))
+            .unwrap_or(false)
+    }
+
+    /// Whether a recurring schedule was flagged as paying its own proposer
+    /// and so needs every signer's approval for each payment.
+    pub fn is_self_directed_schedule(env: Env, schedule_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SelfDirectedSchedule(schedule_id))
+            .unwrap_or(false)
+    }
+
+    /// Halt all withdrawals, e.g. after a suspected signer key compromise.
+    /// Deposits stay open. Only the admin can freeze the treasury.
+    pub fn freeze(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        stellar_guard_pausable::set_paused(
+            &env,
+            symbol_short!("treasury"),
+            EVENT_VERSION,
+            &admin,
+            true,
+        );
+
+        Ok(())
+    }
+
+    /// Resume withdrawals after a freeze.
+    /// Only the admin can unfreeze the treasury.
+    pub fn unfreeze(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        stellar_guard_pausable::set_paused(
+            &env,
+            symbol_short!("treasury"),
+            EVENT_VERSION,
+            &admin,
+            false,
+        );
+
+        Ok(())
+    }
+
+    /// Check whether withdrawals are frozen.
+    pub fn is_paused(env: Env) -> bool {
+        stellar_guard_pausable::is_paused(&env)
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get the treasury balance of a token.
+    pub fn get_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Self::balance_key(&env, &token))
+            .unwrap_or(0)
+    }
+
+    /// Get the balance of a token not reserved by pending withdrawal
+    /// proposals or held back as the minimum reserve, i.e. what new
+    /// proposals can still spend.
+    pub fn get_available(env: Env, token: Address) -> i128 {
+        let balance: i128 = env
+            .storage()
+            .instance()
+            .get(&Self::balance_key(&env, &token))
+            .unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&Self::reserved_key(&env, &token))
+            .unwrap_or(0);
+        (balance - reserved - Self::min_reserve(&env, &token)).max(0)
+    }
+
+    /// Get the token balance for a specific depositor and token contract.
+    ///
+    /// Returns `0` if no deposit has been made for this pair.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment.
+    /// * `depositor` - The address whose balance to query.
+    /// * `token_address` - The token contract address.
+    pub fn get_token_balance(env: Env, depositor: Address, token_address: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenBalance(depositor, token_address))
+            .unwrap_or(0)
+    }
+
+    /// Get the total amount of the primary asset an address has ever
+    /// deposited, for auditing who funded the treasury.
+    pub fn get_deposits_by(env: Env, from: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DepositTotal(from))
+            .unwrap_or(0)
+    }
+
+    /// Get the treasury configuration.
+    pub fn get_config(env: Env) -> Result<TreasuryConfig, Error> {
+        Self::require_initialized(&env)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        let threshold = Self::approval_threshold(&env);
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env));
+        let balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Reserved)
+            .unwrap_or(0);
+        let tx_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TxCounter)
+            .unwrap_or(0);
+        let mut balances = Map::new(&env);
+        for token in Self::held_tokens(&env).iter() {
+            let token_balance: i128 = env
+                .storage()
+                .instance()
+                .get(&Self::balance_key(&env, &token))
+                .unwrap_or(0);
+            balances.set(token, token_balance);
+        }
+
+        let spend_limit = Self::spend_limit(&env);
+        let fee: Option<WithdrawalFee> = env.storage().instance().get(&DataKey::WithdrawalFee);
+        let min_reserve: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinReserve)
+            .unwrap_or(0);
+
+        Ok(TreasuryConfig {
+            admin,
+            threshold,
+            threshold_percent: Self::threshold_percent(&env),
+            min_reserve,
+            strict_self_withdrawal: Self::strict_self_withdrawal(&env),
+            signer_count: signers.len(),
+            balance,
+            reserved,
+            available: (balance - reserved - min_reserve).max(0),
+            balances,
+            tx_count,
+            total_deposited: env
+                .storage()
+                .instance()
+                .get(&DataKey::TotalDeposited)
+                .unwrap_or(0),
+            total_withdrawn: Self::get_total_withdrawn(env.clone()),
+            spend_limit: spend_limit.limit,
+            limit_window: spend_limit.window,
+            frozen: stellar_guard_pausable::is_paused(&env),
+            proposer_auto_approves: Self::proposer_auto_approves(&env),
+            reject_threshold: Self::reject_threshold(&env),
+            refund_threshold: Self::refund_threshold(&env),
+            access_control: env.storage().instance().get(&DataKey::AccessControl),
+            fee_bps: fee.as_ref().map(|fee| fee.fee_bps).unwrap_or(0),
+            fee_collector: fee.map(|fee| fee.collector),
+        })
+    }
+
+    /// Get the lifetime outflow of the primary asset, fees included.
+    ///
+    /// Unlike the balance, this only ever grows: deposits do not offset it.
+    pub fn get_total_withdrawn(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalWithdrawn)
+            .unwrap_or(0)
+    }
+
+    /// Get a specific transaction by ID.
+    pub fn get_transaction(env: Env, tx_id: u64) -> Result<Transaction, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)
+    }
+
+    /// Get the lifecycle state of a transaction.
+    ///
+    /// A transaction is `Executable` once it has enough approval weight and
+    /// its unlock time has passed. Balance and spending limits are not
+    /// considered; use `can_execute` for a full pre-flight check.
+    pub fn get_transaction_status(env: Env, tx_id: u64) -> Result<TransactionStatus, Error> {
+        let transaction = Self::get_transaction(env.clone(), tx_id)?;
+        let status = if transaction.executed {
+            TransactionStatus::Executed
+        } else if transaction.cancelled {
+            TransactionStatus::Cancelled
+        } else if transaction.rejected {
+            TransactionStatus::Rejected
+        } else if Self::approval_weight(&env, &transaction.approvals)
+            >= Self::required_approvals(&env, &transaction)
+            && env.ledger().timestamp() >= transaction.unlock_at
+        {
+            TransactionStatus::Executable
+        } else {
+            TransactionStatus::Pending
+        };
+        Ok(status)
+    }
+
+    /// Get the signers that have approved a transaction, in approval order.
+    pub fn get_approvals(env: Env, tx_id: u64) -> Result<Vec<Address>, Error> {
+        Ok(Self::get_transaction(env, tx_id)?.approvals)
+    }
+
+    /// Get how many signers have approved a transaction.
+    /// This is a head count; see `get_signer_weight` for approval weights.
+    pub fn get_approval_count(env: Env, tx_id: u64) -> Result<u32, Error> {
+        Ok(Self::get_transaction(env, tx_id)?.approvals.len())
+    }
+
+    /// Get the current signers that have not yet approved a transaction, in
+    /// signer-list order.
+    pub fn get_pending_signers(env: Env, tx_id: u64) -> Result<Vec<Address>, Error> {
+        let approvals = Self::get_transaction(env.clone(), tx_id)?.approvals;
+        let mut pending = Vec::new(&env);
+        for signer in Self::get_signers(env).iter() {
+            if !approvals.contains(&signer) {
+                pending.push_back(signer);
+            }
+        }
+        Ok(pending)
+    }
+
+    /// List transactions that are not executed, cancelled or rejected.
+    ///
+    /// Scans IDs from `start_id` up to the latest transaction and returns at
+    /// most `limit` records. To fetch the next page, pass `next_cursor` as
+    /// `start_id`.
+    pub fn list_pending(env: Env, start_id: u64, limit: u32) -> TransactionPage {
+        let mut page = TransactionPage {
+            items: Vec::new(&env),
+            next_cursor: None,
+            has_more: false,
+        };
+        if limit == 0 {
+            return page;
+        }
+
+        let max_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TxCounter)
+            .unwrap_or(0);
+
+        let mut current_id = start_id.max(1);
+        while current_id <= max_id {
+            if let Some(transaction) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Transaction>(&DataKey::Transaction(current_id))
+            {
+                if !transaction.executed && !transaction.cancelled && !transaction.rejected {
+                    // One match past the limit means another page exists
+                    if page.items.len() >= limit {
+                        page.next_cursor = Some(current_id);
+                        page.has_more = true;
+                        break;
+                    }
+                    page.items.push_back(transaction);
+                }
+            }
+            current_id = current_id.saturating_add(1);
+        }
+
+        page
+    }
+
+    /// Get the approval weight of a signer (1 unless configured otherwise).
+    pub fn get_signer_weight(env: Env, signer: Address) -> u32 {
+        Self::signer_weight(&env, &signer)
+    }
+
+    /// Get a recurring withdrawal schedule by ID.
+    pub fn get_schedule(env: Env, schedule_id: u64) -> Result<RecurringSchedule, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(Error::ScheduleNotFound)
+    }
+
+    /// Get the list of current signers.
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Transfer admin role to a new address.
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &current_admin)?;
+
+        current_admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("admin"),
+                EVENT_VERSION,
+            ),
+            (current_admin, new_admin.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Propose a new admin, who must call `accept_admin` to take over.
+    /// The current admin stays in control until then, and can replace the
+    /// pending admin by proposing again.
+    pub fn propose_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &current_admin)?;
+
+        current_admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("adm_prop"),
+                EVENT_VERSION,
+            ),
+            (current_admin, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal and take over the admin role.
+    ///
+    /// # Errors
+    /// * `Error::Unauthorized` - If `new_admin` is not the pending admin.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let pending: Option<Address> = env.storage().instance().get(&DataKey::PendingAdmin);
+        if pending != Some(new_admin.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        new_admin.require_auth();
+
+        let previous_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("admin"),
+                EVENT_VERSION,
+            ),
+            (previous_admin, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Get the admin proposed by `propose_admin`, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Upgrade the contract WASM.
+    /// The admin or the governance contract set via `set_governance` can upgrade.
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        let governance: Option<Address> = env.storage().instance().get(&DataKey::Governance);
+        if governance.as_ref() != Some(&caller) {
+            Self::require_admin(&env, &caller)?;
+        }
+
+        caller.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    /// Get the version of the deployed contract code.
+    pub fn get_version(_env: Env) -> u32 {
+        VERSION
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    fn require_initialized(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+
+    /// The signer an approval by `caller` is credited to: the caller itself
+    /// if a signer, otherwise the signer it is an unexpired delegate for.
+    fn approver_for(env: &Env, caller: &Address) -> Result<Address, Error> {
+        if Self::require_signer(env, caller).is_ok() {
+            return Ok(caller.clone());
+        }
+
+        let signer: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegatedBy(caller.clone()))
+            .ok_or(Error::NotASigner)?;
+        let delegation: ApprovalDelegation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ApprovalDelegate(signer.clone()))
+            .ok_or(Error::NotASigner)?;
+        if delegation.delegate != *caller || env.ledger().timestamp() >= delegation.expires_at {
+            return Err(Error::NotASigner);
+        }
+        Self::require_signer(env, &signer)?;
+        Ok(signer)
+    }
+
+    fn record_reason(env: &Env, tx_id: u64, signer: &Address, reason: String) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reason(tx_id, signer.clone()), &reason);
+
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("reason"),
+                EVENT_VERSION,
+            ),
+            (tx_id, signer.clone(), reason),
+        );
+    }
+
+    fn clear_approval_delegate(env: &Env, signer: &Address) {
+        let key = DataKey::ApprovalDelegate(signer.clone());
+        if let Some(delegation) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ApprovalDelegation>(&key)
+        {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DelegatedBy(delegation.delegate));
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Add `signer`'s approval to a pending transaction.
+    fn record_approval(env: &Env, signer: &Address, tx_id: u64) -> Result<u32, Error> {
+        // Load transaction
+        let mut transaction: Transaction = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .ok_or(Error::TransactionNotFound)?;
+
+        if transaction.cancelled {
+            return Err(Error::Cancelled);
+        }
+        if transaction.rejected {
+            return Err(Error::TransactionRejected);
+        }
+        if transaction.executed {
+            return Err(Error::AlreadyExecuted);
+        }
+
+        // Check if already approved by this signer
+        for i in 0..transaction.approvals.len() {
+            if transaction.approvals.get(i).unwrap() == *signer {
+                return Err(Error::AlreadyApproved);
+            }
+        }
+        if transaction.rejections.contains(signer) {
+            return Err(Error::AlreadyRejected);
+        }
+
+        // Add approval
+        let previous_count = Self::approval_weight(env, &transaction.approvals);
+        transaction.approvals.push_back(signer.clone());
+        let approval_count = Self::approval_weight(env, &transaction.approvals);
+
+        // Save updated transaction
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(tx_id), &transaction);
+
+        // Emit approval event
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("approve"),
+                EVENT_VERSION,
+            ),
+            (tx_id, signer.clone(), approval_count),
+        );
+
+        // Announce the approval that makes the transaction executable
+        let threshold = Self::required_approvals(env, &transaction);
+        if previous_count < threshold && approval_count >= threshold {
+            env.events().publish(
+                (
+                    symbol_short!("treasury"),
+                    symbol_short!("ready"),
+                    EVENT_VERSION,
+                ),
+                tx_id,
+            );
+        }
+
+        log!(
+            env,
+            "Transaction #{} approved by {:?} ({} approval weight)",
+            tx_id,
+            signer,
+            approval_count
+        );
+        Ok(approval_count)
+    }
+
+    /// Storage key of a token's treasury balance. The primary asset keeps
+    /// the original single-balance key so existing deployments carry over.
+    fn balance_key(env: &Env, token: &Address) -> DataKey {
+        let asset: Option<Address> = env.storage().instance().get(&DataKey::Asset);
+        if asset.as_ref() == Some(token) {
+            DataKey::Balance
+        } else {
+            DataKey::AssetBalance(token.clone())
+        }
+    }
+
+    /// Storage key of a token's reserved amount, mirroring `balance_key`.
+    fn reserved_key(env: &Env, token: &Address) -> DataKey {
+        let asset: Option<Address> = env.storage().instance().get(&DataKey::Asset);
+        if asset.as_ref() == Some(token) {
+            DataKey::Reserved
+        } else {
+            DataKey::AssetReserved(token.clone())
+        }
+    }
+
+    /// Tokens the treasury has held. Deployments from before multi-asset
+    /// support only ever held the primary asset.
+    fn held_tokens(env: &Env) -> Vec<Address> {
+        if let Some(tokens) = env.storage().instance().get(&DataKey::HeldTokens) {
+            return tokens;
+        }
+        let mut tokens = Vec::new(env);
+        if let Some(asset) = env.storage().instance().get(&DataKey::Asset) {
+            tokens.push_back(asset);
+        }
+        tokens
+    }
+
+    /// Add `amount` of `token` to the treasury balance and return the new
+    /// balance.
+    fn credit(env: &Env, token: &Address, amount: i128) -> i128 {
+        let mut tokens = Self::held_tokens(env);
+        if !tokens.contains(token) {
+            tokens.push_back(token.clone());
+        }
+        env.storage().instance().set(&DataKey::HeldTokens, &tokens);
+
+        let balance_key = Self::balance_key(env, token);
+        let current_balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
+        let new_balance = current_balance + amount;
+        env.storage().instance().set(&balance_key, &new_balance);
+        new_balance
+    }
+
+    fn spend_limit(env: &Env) -> SpendLimit {
+        env.storage()
+            .instance()
+            .get(&DataKey::SpendLimit)
+            .unwrap_or(SpendLimit {
+                limit: 0,
+                window: 0,
+            })
+    }
+
+    /// Run the execute-time checks on a transaction and work out the
+    /// payment, without changing any state.
+    fn check_execution(
+        env: &Env,
+        transaction: &Transaction,
+        amount: Option<i128>,
+    ) -> Result<Payment, Error> {
+        if transaction.cancelled {
+            return Err(Error::Cancelled);
+        }
+        if transaction.rejected {
+            return Err(Error::TransactionRejected);
+        }
+        if transaction.executed {
+            return Err(Error::AlreadyExecuted);
+        }
+
+        // Check threshold
+        let threshold = Self::required_approvals(env, transaction);
+        if Self::approval_weight(env, &transaction.approvals) < threshold {
+            return Err(Error::Unauthorized);
+        }
+
+        if env.ledger().timestamp() < transaction.unlock_at {
+            return Err(Error::TimelockActive);
+        }
+
+        let remaining = transaction.amount - transaction.paid;
+        let amount = amount.unwrap_or(remaining);
+        if amount <= 0 || amount > remaining {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Split off the withdrawal fee, if any
+        let fee: Option<WithdrawalFee> = env.storage().instance().get(&DataKey::WithdrawalFee);
+        let (fee_amount, on_top) = match &fee {
+            Some(fee) => (
+                amount
+                    .checked_mul(fee.fee_bps as i128)
+                    .ok_or(Error::InvalidAmount)?
+                    / 10_000,
+                fee.on_top,
+            ),
+            None => (0, false),
+        };
+        let (payout, debit) = if on_top {
+            (amount, amount + fee_amount)
+        } else {
+            (amount - fee_amount, amount)
+        };
+
+        // Check balance. Only this transaction's own reservation may be
+        // spent, so a fee on top cannot dip into funds held for others.
+        let balance_key = Self::balance_key(env, &transaction.token);
+        let current_balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
+        let reserved: i128 = env
+            .storage()
+            .instance()
+            .get(&Self::reserved_key(env, &transaction.token))
+            .unwrap_or(0);
+        let held_for_others = (reserved - remaining).max(0);
+        if current_balance - held_for_others - Self::min_reserve(env, &transaction.token) < debit {
+            return Err(Error::InsufficientFunds);
+        }
+        Self::next_spend_window(env, &transaction.token, debit)?;
+
+        Ok(Payment {
+            amount,
+            payout,
+            debit,
+            fee,
+            fee_amount,
+        })
+    }
+
+    /// Count a withdrawal against the spending limit.
+    fn record_spend(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+        if let Some(window) = Self::next_spend_window(env, token, amount)? {
+            env.storage()
+                .instance()
+                .set(&DataKey::SpendWindow(token.clone()), &window);
+        }
+        Ok(())
+    }
+
+    /// The spending window after a withdrawal of `amount`, starting a new
+    /// window once the current one has elapsed. `None` when no limit is set.
+    fn next_spend_window(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+    ) -> Result<Option<SpendWindow>, Error> {
+        let spend_limit = Self::spend_limit(env);
+        if spend_limit.limit == 0 {
+            return Ok(None);
+        }
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::SpendWindow(token.clone());
+        let mut window: SpendWindow = env.storage().instance().get(&key).unwrap_or(SpendWindow {
+            started_at: now,
+            spent: 0,
+        });
+        if now >= window.started_at.saturating_add(spend_limit.window) {
+            window = SpendWindow {
+                started_at: now,
+                spent: 0,
+            };
+        }
+
+        if window.spent + amount > spend_limit.limit {
+            return Err(Error::SpendLimitExceeded);
+        }
+        window.spent += amount;
+        Ok(Some(window))
+    }
+
+    /// Add a deposit of the primary asset to the depositor's lifetime total.
+    fn record_contribution(env: &Env, from: &Address, token: &Address, amount: i128) {
+        let asset: Option<Address> = env.storage().instance().get(&DataKey::Asset);
+        if asset.as_ref() != Some(token) {
+            return;
+        }
+        let key = DataKey::DepositTotal(from.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(total + amount));
+
+        let total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalDeposited)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalDeposited, &(total + amount));
+    }
+
+    /// Add funds of the primary asset leaving the treasury to the lifetime
+    /// outflow.
+    fn record_outflow(env: &Env, token: &Address, amount: i128) {
+        let asset: Option<Address> = env.storage().instance().get(&DataKey::Asset);
+        if asset.as_ref() != Some(token) {
+            return;
+        }
+        let total = Self::get_total_withdrawn(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalWithdrawn, &(total + amount));
+    }
+
+    /// Reserve funds for a new transaction, assign its ID and store it.
+    fn open_transaction(env: &Env, mut transaction: Transaction) -> Result<u64, Error> {
+        // Check sufficient *unreserved* balance — multiple pending proposals
+        // must not be able to collectively over-commit the same funds.
+        let balance_key = Self::balance_key(env, &transaction.token);
+        let reserved_key = Self::reserved_key(env, &transaction.token);
+        let balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
+        let reserved: i128 = env.storage().instance().get(&reserved_key).unwrap_or(0);
+        let min_reserve = Self::min_reserve(env, &transaction.token);
+        if balance - reserved - min_reserve < transaction.amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        // Reserve the funds so subsequent proposals cannot reference them.
+        env.storage()
+            .instance()
+            .set(&reserved_key, &(reserved + transaction.amount));
+
+        // Get and increment counter
+        let tx_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TxCounter)
+            .unwrap_or(0);
+        let next_id = tx_id + 1;
+        env.storage().instance().set(&DataKey::TxCounter, &next_id);
+
+        // Store transaction
+        transaction.id = next_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(next_id), &transaction);
+
+        // Emit proposal event
+        env.events().publish(
+            (
+                symbol_short!("treasury"),
+                symbol_short!("propose"),
+                EVENT_VERSION,
+            ),
+            (
+                next_id,
+                transaction.proposer.clone(),
+                transaction.to,
+                transaction.token,
+                transaction.amount,
+            ),
+        );
+
+        log!(
+            env,
+            "Withdrawal proposal #{} created by {:?} for {}",
+            next_id,
+            transaction.proposer,
+            transaction.amount
+        );
+        Ok(next_id)
+    }
+
+    /// Release what a cancelled or rejected transaction has not paid out,
+    /// and give it back to the depositor's total if it was a refund.
+    fn close_unpaid(env: &Env, transaction: &Transaction) {
+        let unpaid = transaction.amount - transaction.paid;
+        Self::release_reservation(env, &transaction.token, unpaid);
+        if transaction.refund {
+            let key = DataKey::DepositTotal(transaction.to.clone());
+            let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(total + unpaid));
+        }
+    }
+
+    /// Return funds reserved by a pending proposal to the available balance.
+    fn release_reservation(env: &Env, token: &Address, amount: i128) {
+        let reserved_key = Self::reserved_key(env, token);
+        let reserved: i128 = env.storage().instance().get(&reserved_key).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&reserved_key, &(reserved - amount));
+    }
+
+    fn signer_weight(env: &Env, signer: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SignerWeight(signer.clone()))
+            .unwrap_or(1)
+    }
+
+    /// Combined weight of a list of signers.
+    fn approval_weight(env: &Env, signers: &Vec<Address>) -> u32 {
+        let mut weight: u32 = 0;
+        for signer in signers.iter() {
+            weight = weight.saturating_add(Self::signer_weight(env, &signer));
+        }
+        weight
+    }
+
+    fn reject_threshold(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RejectThreshold)
+            .unwrap_or_else(|| Self::approval_threshold(env))
+    }
+
+    fn refund_threshold(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RefundThreshold)
+            .unwrap_or_else(|| Self::approval_threshold(env))
+    }
+
+    /// Approval weight a transaction needs before it can execute.
+    /// Self-directed withdrawals need the weight of every signer.
+    fn required_approvals(env: &Env, transaction: &Transaction) -> u32 {
+        if transaction.refund {
+            Self::refund_threshold(env)
+        } else if Self::is_self_directed(env.clone(), transaction.id) {
+            Self::all_signers_weight(env)
+        } else {
+            Self::approval_threshold(env)
+        }
+    }
+
+    /// Combined weight of every current signer.
+    fn all_signers_weight(env: &Env) -> u32 {
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(env));
+        Self::approval_weight(env, &signers)
+    }
+
+    fn strict_self_withdrawal(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::StrictSelfWithdrawal)
+            .unwrap_or(false)
+    }
+
+    /// Minimum balance to keep of a token. Only the primary asset has one.
+    fn min_reserve(env: &Env, token: &Address) -> i128 {
+        let asset: Option<Address> = env.storage().instance().get(&DataKey::Asset);
+        if asset.as_ref() != Some(token) {
+            return 0;
+        }
+        env.storage()
+            .instance()
+            .get(&DataKey::MinReserve)
+            .unwrap_or(0)
+    }
+
+    fn threshold_percent(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ThresholdPercent)
+            .unwrap_or(0)
+    }
+
+    /// Approval weight needed to execute a withdrawal. In percentage mode
+    /// this is recomputed from the live signer count, rounding up.
+    fn approval_threshold(env: &Env) -> u32 {
+        let percent = Self::threshold_percent(env);
+        if percent == 0 {
+            return env
+                .storage()
+                .instance()
+                .get(&DataKey::Threshold)
+                .unwrap_or(1);
+        }
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(env));
+        (signers.len() * percent).div_ceil(100).max(1)
+    }
+
+    fn proposer_auto_approves(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProposerAutoApproves)
+            .unwrap_or(true)
+    }
+
+    fn require_not_frozen(env: &Env) -> Result<(), Error> {
+        stellar_guard_pausable::require_not_paused(env)
+    }
+
+    /// Reentrancy guard for entry points that call out to other contracts:
+    /// `execute`, `execute_recurring` and `release_funds`. The Soroban host
+    /// already rejects re-entry into a running contract; this keeps the
+    /// invariant explicit in the contract itself. On an error return the
+    /// lock is rolled back with the rest of the call.
+    fn lock(env: &Env) -> Result<(), Error> {
+        if env
+            .storage()
+            .instance()
+            .get(&DataKey::Locked)
+            .unwrap_or(false)
+        {
+            return Err(Error::Reentrancy);
+        }
+        env.storage().instance().set(&DataKey::Locked, &true);
+        Ok(())
+    }
+
+    fn unlock(env: &Env) {
+        env.storage().instance().remove(&DataKey::Locked);
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn require_signer(env: &Env, caller: &Address) -> Result<(), Error> {
+        let signers: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(env));
+
+        for i in 0..signers.len() {
+            if signers.get(i).unwrap() == *caller {
+                return Ok(());
+            }
+        }
+
+        // Fall back to the access-control contract's admins, if configured
+        if let Some(access_control) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::AccessControl)
+        {
+            if AccessControlClient::new(env, &access_control)
+                .has_permission(caller, &AccessRole::Admin)
+            {
+                return Ok(());
+            }
+        }
+        Err(Error::NotASigner)
+    }
+
+    fn is_zero_address(env: &Env, address: &Address) -> bool {
+        ZERO_ADDRESSES
+            .iter()
+            .any(|zero| *address == Address::from_string(&String::from_str(env, zero)))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::Events as _;
+    use soroban_sdk::testutils::Ledger as _;
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+    use soroban_sdk::{vec, Env, IntoVal, TryFromVal, Val};
+
+    // ── helpers shared by all tests ──────────────────────────────────────────
+
+    fn setup_contract() -> (Env, Address, Address, TreasuryContractClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, TreasuryContract);
+        let client = TreasuryContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        (env, admin, contract_id, client)
+    }
+
+    fn setup_asset(env: &Env) -> (Address, StellarAssetClient<'_>, TokenClient<'_>) {
+        let token_admin = Address::generate(env);
+        let sac_data = env.register_stellar_asset_contract_v2(token_admin.clone());
+        let token_address = sac_data.address();
+        let sac = StellarAssetClient::new(env, &token_address);
+        let token = TokenClient::new(env, &token_address);
+        (token_address, sac, token)
+    }
+
+    fn initialize_treasury(
+        client: &TreasuryContractClient<'_>,
+        env: &Env,
+        admin: &Address,
+        threshold: u32,
+        signers: &Vec<Address>,
+    ) -> Address {
+        let (asset, _sac, _token) = setup_asset(env);
+        client.initialize(admin, &threshold, signers, &asset);
+        asset
+    }
+
+    fn mint_asset(env: &Env, asset: &Address, recipient: &Address, amount: i128) {
+        StellarAssetClient::new(env, asset).mint(recipient, &amount);
+    }
+
+    /// Token whose `transfer` tries to call back into the treasury's
+    /// `execute`, standing in for a malicious token contract.
+    #[contract]
+    pub struct ReentrantToken;
+
+    #[contractimpl]
+    impl ReentrantToken {
+        pub fn arm(env: Env, treasury: Address, executor: Address, tx_id: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("target"), &(treasury, executor, tx_id));
+        }
+
+        pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+            let target: Option<(Address, Address, u64)> =
+                env.storage().instance().get(&symbol_short!("target"));
+            if let Some((treasury, executor, tx_id)) = target {
+                let result =
+                    TreasuryContractClient::new(&env, &treasury).try_execute(&executor, &tx_id);
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("blocked"), &result.is_err());
+            }
+        }
+
+        pub fn reentry_blocked(env: Env) -> bool {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("blocked"))
+                .unwrap_or(false)
+        }
+    }
+
+    /// Events published by the treasury itself, skipping the SAC `mint` and
+    /// `transfer` events that real token movement produces.
+    fn contract_events(env: &Env, contract_id: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+        let mut events = Vec::new(env);
+        for event in env.events().all().iter() {
+            if event.0 == *contract_id {
+                events.push_back(event);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+
+        let _asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+
+        let config = client.get_config();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.threshold, 2);
+        assert_eq!(config.signer_count, 3);
+        assert_eq!(config.balance, 0);
+    }
+
+    #[test]
+    fn test_deposit() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let depositor = Address::generate(&env);
+        mint_asset(&env, &asset, &depositor, 1_000_000);
+        client.deposit(&depositor, &asset, &1_000_000);
+
+        assert_eq!(client.get_balance(&asset), 1_000_000);
+    }
+
+    /// Integration test for SC-26: verify `deposit` actually moves the
+    /// underlying SAC tokens from the depositor into the treasury contract
+    /// rather than only incrementing the bookkeeping balance.
+    #[test]
+    fn test_deposit_moves_real_tokens_on_chain() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let depositor = Address::generate(&env);
+        mint_asset(&env, &asset, &depositor, 5_000_000);
+
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&depositor), 5_000_000);
+        assert_eq!(token.balance(&contract_id), 0);
+
+        client.deposit(&depositor, &asset, &2_000_000);
+
+        assert_eq!(token.balance(&contract_id), 2_000_000);
+        assert_eq!(token.balance(&depositor), 3_000_000);
+        assert_eq!(client.get_balance(&asset), 2_000_000);
+
+        client.deposit(&depositor, &asset, &1_500_000);
+        assert_eq!(token.balance(&contract_id), 3_500_000);
+        assert_eq!(token.balance(&depositor), 1_500_000);
+        assert_eq!(client.get_balance(&asset), 3_500_000);
+    }
+
+    /// A deposit whose token transfer fails must not credit the balance.
+    #[test]
+    fn test_deposit_without_tokens_reverts() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let depositor = Address::generate(&env);
+        mint_asset(&env, &asset, &depositor, 1_000);
+
+        let result = client.try_deposit(&depositor, &asset, &5_000);
+        assert!(result.is_err());
+
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(client.get_balance(&asset), 0);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert_eq!(token.balance(&depositor), 1_000);
+    }
+
+    #[test]
+    fn test_propose_and_approve() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+
+        // Deposit some funds
+        mint_asset(&env, &asset, &signer1, 5_000_000);
+        client.deposit(&signer1, &asset, &5_000_000);
+
+        // Propose withdrawal
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_000_000,
+            &String::from_str(&env, "rent"),
+            &0,
+        );
+        assert_eq!(tx_id, 1);
+
+        // Second signer approves
+        let approval_count = client.approve(&signer2, &tx_id);
+        assert_eq!(approval_count, 2);
+
+        // Execute
+        client.execute(&signer1, &tx_id);
+
+        // Check balance deducted
+        assert_eq!(client.get_balance(&asset), 4_000_000);
+        assert_eq!(
+            TokenClient::new(&env, &asset).balance(&recipient),
+            1_000_000
+        );
+
+        // Check transaction marked as executed
+        let tx = client.get_transaction(&tx_id);
+        assert_eq!(tx.executed, true);
+    }
+
+    #[test]
+    fn test_execute_transfers_tokens_to_recipient() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 3_000);
+        client.deposit(&signer1, &asset, &3_000);
+
+        let recipient = Address::generate(&env);
+        mint_asset(&env, &asset, &recipient, 250);
+
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "vendor"),
+            &0,
+        );
+        client.execute(&signer1, &tx_id);
+
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&recipient), 1_250);
+        assert_eq!(token.balance(&contract_id), 2_000);
+        assert_eq!(client.get_balance(&asset), 2_000);
+    }
+
+    #[test]
+    fn test_full_treasury_workflow_emits_expected_events() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+
+        mint_asset(&env, &asset, &signer1, 8_000_000);
+        client.deposit(&signer1, &asset, &8_000_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &3_000_000,
+            &String::from_str(&env, "ops"),
+            &0,
+        );
+
+        let approvals = client.approve(&signer2, &tx_id);
+        assert_eq!(approvals, 2);
+
+        client.execute(&signer3, &tx_id);
+
+        let config = client.get_config();
+        assert_eq!(config.balance, 5_000_000);
+
+        let transaction = client.get_transaction(&tx_id);
+        assert_eq!(transaction.executed, true);
+        assert_eq!(transaction.amount, 3_000_000);
+        assert_eq!(transaction.to, recipient);
+        assert_eq!(
+            TokenClient::new(&env, &asset).balance(&recipient),
+            3_000_000
+        );
+
+        let events = contract_events(&env, &contract_id);
+        assert_eq!(events.len(), 6);
+
+        let init_event = events.get(0).unwrap();
+        assert_eq!(init_event.0, contract_id);
+        assert_eq!(
+            init_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("init").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+
+        let deposit_event = events.get(1).unwrap();
+        assert_eq!(deposit_event.0, contract_id);
+        assert_eq!(
+            deposit_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("deposit").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+
+        let propose_event = events.get(2).unwrap();
+        assert_eq!(propose_event.0, contract_id);
+        assert_eq!(
+            propose_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("propose").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+
+        let approve_event = events.get(3).unwrap();
+        assert_eq!(approve_event.0, contract_id);
+        assert_eq!(
+            approve_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("approve").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+
+        let ready_event = events.get(4).unwrap();
+        assert_eq!(
+            ready_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("ready").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+
+        let execute_event = events.get(events.len() - 1).unwrap();
+        let actual_data: Vec<Val> = Vec::try_from_val(&env, &execute_event.2).unwrap();
+        let expected_data: Vec<Val> = vec![
+            &env,
+            tx_id.into_val(&env),
+            recipient.into_val(&env),
+            asset.into_val(&env),
+            3_000_000_i128.into_val(&env),
+            5_000_000_i128.into_val(&env),
+            true.into_val(&env),
+        ];
+        assert_eq!(
+            execute_event.1,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("execute").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        assert_eq!(actual_data, expected_data);
+    }
+
+    #[test]
+    fn test_get_signers() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+
+        let _asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+
+        let fetched = client.get_signers();
+        assert_eq!(fetched.len(), 3);
+        assert_eq!(fetched.get(0).unwrap(), signer1);
+        assert_eq!(fetched.get(1).unwrap(), signer2);
+        assert_eq!(fetched.get(2).unwrap(), signer3);
+    }
+
+    #[test]
+    fn test_get_transaction() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+
+        mint_asset(&env, &asset, &signer1, 5_000_000);
+        client.deposit(&signer1, &asset, &5_000_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_000_000,
+            &String::from_str(&env, "rent"),
+            &0,
+        );
+
+        let tx = client.get_transaction(&tx_id);
+        assert_eq!(tx.id, tx_id);
+        assert_eq!(tx.to, recipient);
+        assert_eq!(tx.amount, 1_000_000);
+        assert_eq!(tx.memo, String::from_str(&env, "rent"));
+        assert_eq!(tx.proposer, signer1);
+        assert_eq!(tx.approvals.len(), 1);
+        assert_eq!(tx.approvals.get(0).unwrap(), signer1);
+        assert_eq!(tx.executed, false);
+    }
+
+    #[test]
+    fn test_propose_rejects_non_signer() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 5_000_000);
+        client.deposit(&signer, &asset, &5_000_000);
+
+        let non_signer = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        let result = client.try_propose_withdrawal(
+            &non_signer,
+            &recipient,
+            &asset,
+            &1_000_000,
+            &String::from_str(&env, "rent"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
+    }
+
+    #[test]
+    fn test_propose_rejects_insufficient_funds() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let recipient = Address::generate(&env);
+
+        let result = client.try_propose_withdrawal(
+            &signer,
+            &recipient,
+            &asset,
+            &1_000_000,
+            &String::from_str(&env, "rent"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+    }
+
+    /// Regression test: two proposals that together exceed the balance must be
+    /// rejected at proposal time, not silently allowed to race to execute.
+    #[test]
+    fn test_propose_reserves_funds_prevents_double_spend() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        // Deposit exactly 1_000_000
+        mint_asset(&env, &asset, &signer1, 1_000_000);
+        client.deposit(&signer1, &asset, &1_000_000);
+
+        let recipient = Address::generate(&env);
+
+        // First proposal for the full balance — should succeed
+        let _tx1 = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_000_000,
+            &String::from_str(&env, "first"),
+            &0,
+        );
+
+        // Second proposal for any amount — must be rejected because all funds
+        // are already reserved by the first pending proposal.
+        let result = client.try_propose_withdrawal(
+            &signer2,
+            &recipient,
+            &asset,
+            &1,
+            &String::from_str(&env, "second"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+    }
+
+    #[test]
+    fn test_propose_limited_to_unreserved_balance() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx1 = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &600,
+            &String::from_str(&env, "first"),
+            &0,
+        );
+        assert_eq!(client.get_config().reserved, 600);
+
+        // 600 fits the balance on its own but not alongside the first proposal
+        let result = client.try_propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &600,
+            &String::from_str(&env, "second"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+        client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &400,
+            &String::from_str(&env, "second"),
+            &0,
+        );
+        assert_eq!(client.get_config().reserved, 1_000);
+
+        client.execute(&signer1, &tx1);
+        let config = client.get_config();
+        assert_eq!(config.balance, 400);
+        assert_eq!(config.reserved, 400);
+    }
+
+    #[test]
+    fn test_deposit_and_withdraw_two_tokens_independently() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let xlm = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let (usdc, _sac, usdc_token) = setup_asset(&env);
+        mint_asset(&env, &xlm, &signer1, 1_000);
+        mint_asset(&env, &usdc, &signer1, 500);
+
+        client.deposit(&signer1, &xlm, &1_000);
+        client.deposit(&signer1, &usdc, &500);
+        assert_eq!(client.get_balance(&xlm), 1_000);
+        assert_eq!(client.get_balance(&usdc), 500);
+
+        // USDC reservations do not touch the XLM balance
+        let recipient = Address::generate(&env);
+        let result = client.try_propose_withdrawal(
+            &signer1,
+            &recipient,
+            &usdc,
+            &600,
+            &String::from_str(&env, "too much"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &usdc,
+            &200,
+            &String::from_str(&env, "usdc"),
+            &0,
+        );
+        assert_eq!(client.get_transaction(&tx_id).token, usdc);
+        assert_eq!(client.get_config().reserved, 0);
+        client.execute(&signer1, &tx_id);
+
+        assert_eq!(client.get_balance(&usdc), 300);
+        assert_eq!(client.get_balance(&xlm), 1_000);
+        assert_eq!(usdc_token.balance(&recipient), 200);
+        assert_eq!(usdc_token.balance(&contract_id), 300);
+
+        let config = client.get_config();
+        assert_eq!(config.balance, 1_000);
+        assert_eq!(config.balances.len(), 2);
+        assert_eq!(config.balances.get(xlm), Some(1_000));
+        assert_eq!(config.balances.get(usdc), Some(300));
+    }
+
+    #[test]
+    fn test_revoke_approval_blocks_execute() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &400,
+            &String::from_str(&env, "vendor"),
+            &0,
+        );
+        assert_eq!(client.approve(&signer2, &tx_id), 2);
+
+        assert_eq!(client.revoke_approval(&signer2, &tx_id), 1);
+        let events = contract_events(&env, &contract_id);
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            topics,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("revoke").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        let payload: (u64, Address, u32) = <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload, (tx_id, signer2.clone(), 1));
+        assert_eq!(client.get_transaction(&tx_id).approvals.len(), 1);
+
+        let result = client.try_revoke_approval(&signer2, &tx_id);
+        assert_eq!(result, Err(Ok(Error::ApprovalNotFound)));
+
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        // Approving again restores the threshold
+        client.approve(&signer2, &tx_id);
+        client.execute(&signer1, &tx_id);
+        let result = client.try_revoke_approval(&signer2, &tx_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyExecuted)));
+    }
+
+    #[test]
+    fn test_proposer_cancels_withdrawal_and_releases_reservation() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "mistake"),
+            &0,
+        );
+        assert_eq!(client.get_config().reserved, 1_000);
+
+        let result = client.try_cancel_withdrawal(&signer2, &tx_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.cancel_withdrawal(&signer1, &tx_id);
+        assert!(client.get_transaction(&tx_id).cancelled);
+        assert_eq!(client.get_config().reserved, 0);
+        let events = contract_events(&env, &contract_id);
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            topics,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("cancel").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        let payload: (u64, Address) = <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload, (tx_id, signer1.clone()));
+
+        assert_eq!(
+            client.try_approve(&signer2, &tx_id),
+            Err(Ok(Error::Cancelled))
+        );
+        assert_eq!(
+            client.try_execute(&signer1, &tx_id),
+            Err(Ok(Error::Cancelled))
+        );
+        assert_eq!(
+            client.try_cancel_withdrawal(&signer1, &tx_id),
+            Err(Ok(Error::Cancelled))
+        );
+
+        // The released funds can back a new proposal
+        client.propose_withdrawal(
+            &signer2,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "corrected"),
+            &0,
+        );
+    }
+
+    #[test]
+    fn test_admin_cancels_withdrawal() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &500,
+            &String::from_str(&env, "suspicious"),
+            &0,
+        );
+        client.cancel_withdrawal(&admin, &tx_id);
+        assert!(client.get_transaction(&tx_id).cancelled);
+        assert_eq!(client.get_config().reserved, 0);
+        assert_eq!(client.get_balance(&asset), 1_000);
+    }
+
+    #[test]
+    fn test_spend_limit_caps_withdrawals_per_window() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 10_000);
+        client.deposit(&signer1, &asset, &10_000);
+        client.set_spend_limit(&admin, &1_000, &3_600);
+        let config = client.get_config();
+        assert_eq!((config.spend_limit, config.limit_window), (1_000, 3_600));
+
+        let recipient = Address::generate(&env);
+        let mut tx_ids = Vec::new(&env);
+        for _ in 0..3 {
+            tx_ids.push_back(client.propose_withdrawal(
+                &signer1,
+                &recipient,
+                &asset,
+                &400,
+                &String::from_str(&env, "payroll"),
+                &0,
+            ));
+        }
+
+        env.ledger().set_timestamp(10_000);
+        client.execute(&signer1, &tx_ids.get(0).unwrap());
+        client.execute(&signer1, &tx_ids.get(1).unwrap());
+        let result = client.try_execute(&signer1, &tx_ids.get(2).unwrap());
+        assert_eq!(result, Err(Ok(Error::SpendLimitExceeded)));
+        assert!(!client.get_transaction(&tx_ids.get(2).unwrap()).executed);
+
+        // Still inside the window
+        env.ledger().set_timestamp(13_599);
+        let result = client.try_execute(&signer1, &tx_ids.get(2).unwrap());
+        assert_eq!(result, Err(Ok(Error::SpendLimitExceeded)));
+
+        env.ledger().set_timestamp(13_600);
+        client.execute(&signer1, &tx_ids.get(2).unwrap());
+        assert_eq!(client.get_balance(&asset), 8_800);
+    }
+
+    #[test]
+    fn test_timelocked_withdrawal_waits_for_unlock() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        env.ledger().set_timestamp(1_000);
+        let vendor = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &vendor,
+            &asset,
+            &300,
+            &String::from_str(&env, "scheduled"),
+            &5_000,
+        );
+        assert_eq!(client.get_transaction(&tx_id).unlock_at, 5_000);
+
+        // Approvals can be gathered while locked
+        client.approve(&signer2, &tx_id);
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::TimelockActive)));
+
+        env.ledger().set_timestamp(4_999);
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::TimelockActive)));
+
+        env.ledger().set_timestamp(5_000);
+        client.execute(&signer1, &tx_id);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&vendor), 300);
+    }
+
+    #[test]
+    fn test_approve_batch_skips_unapprovable_transactions() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let mut tx_ids = Vec::new(&env);
+        for _ in 0..3 {
+            tx_ids.push_back(client.propose_withdrawal(
+                &signer1,
+                &recipient,
+                &asset,
+                &100,
+                &String::from_str(&env, "batch"),
+                &0,
+            ));
+        }
+        let (pending, approved, executed) = (
+            tx_ids.get(0).unwrap(),
+            tx_ids.get(1).unwrap(),
+            tx_ids.get(2).unwrap(),
+        );
+        client.approve(&signer2, &approved);
+        client.approve(&signer3, &executed);
+        client.execute(&signer1, &executed);
+
+        let counts = client.approve_batch(
+            &signer2,
+            &Vec::from_array(&env, [pending, approved, executed, 99]),
+        );
+        assert_eq!(counts, Vec::from_array(&env, [2, 0, 0, 0]));
+        assert_eq!(client.get_transaction(&pending).approvals.len(), 2);
+        assert_eq!(client.get_transaction(&approved).approvals.len(), 2);
+
+        let mut too_many = Vec::new(&env);
+        for id in 0..26 {
+            too_many.push_back(id);
+        }
+        let result = client.try_approve_batch(&signer3, &too_many);
+        assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
+    }
+
+    #[test]
+    fn test_list_pending_skips_executed_and_cancelled() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        for _ in 0..5 {
+            client.propose_withdrawal(
+                &signer1,
+                &recipient,
+                &asset,
+                &100,
+                &String::from_str(&env, "pending"),
+                &0,
+            );
+        }
+        client.execute(&signer1, &2);
+        client.cancel_withdrawal(&signer1, &4);
+
+        let ids = |page: TransactionPage| {
+            let mut ids = Vec::new(&env);
+            for transaction in page.items.iter() {
+                ids.push_back(transaction.id);
+            }
+            ids
+        };
+        assert_eq!(
+            ids(client.list_pending(&0, &10)),
+            Vec::from_array(&env, [1, 3, 5])
+        );
+        assert_eq!(
+            ids(client.list_pending(&0, &2)),
+            Vec::from_array(&env, [1, 3])
+        );
+        assert_eq!(
+            ids(client.list_pending(&4, &10)),
+            Vec::from_array(&env, [5])
+        );
+        assert_eq!(client.list_pending(&1, &0).items.len(), 0);
+
+        // The limit truncates the first page and the cursor resumes after it
+        let page = client.list_pending(&0, &2);
+        assert!(page.has_more);
+        assert_eq!(page.next_cursor, Some(5));
+        let page = client.list_pending(&page.next_cursor.unwrap(), &2);
+        assert_eq!(ids(page.clone()), Vec::from_array(&env, [5]));
+        assert!(!page.has_more);
+        assert_eq!(page.next_cursor, None);
+        assert!(!client.list_pending(&0, &3).has_more);
+    }
+
+    #[test]
+    fn test_freeze_blocks_withdrawals_but_not_deposits() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 2_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &500,
+            &String::from_str(&env, "before"),
+            &0,
+        );
+
+        let result = client.try_freeze(&signer1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.freeze(&admin);
+        assert!(client.get_config().frozen);
+        assert!(client.is_paused());
+
+        let result = client.try_propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "during"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(Error::Frozen)));
+        assert_eq!(client.try_approve(&signer2, &tx_id), Err(Ok(Error::Frozen)));
+        assert_eq!(
+            client.try_approve_batch(&signer2, &Vec::from_array(&env, [tx_id])),
+            Err(Ok(Error::Frozen))
+        );
+
+        // Deposits are still accepted
+        client.deposit(&signer1, &asset, &1_000);
+        assert_eq!(client.get_balance(&asset), 2_000);
+
+        client.unfreeze(&admin);
+        client.approve(&signer2, &tx_id);
+        client.freeze(&admin);
+        assert_eq!(client.try_execute(&signer1, &tx_id), Err(Ok(Error::Frozen)));
+
+        client.unfreeze(&admin);
+        assert!(!client.get_config().frozen);
+        client.execute(&signer1, &tx_id);
+        assert_eq!(client.get_balance(&asset), 1_500);
+    }
+
+    #[test]
+    fn test_propose_with_multi_word_memo() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "rent Q1 2025 for the Lagos office");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        assert_eq!(client.get_transaction(&tx_id).memo, memo);
+
+        let long_memo = String::from_bytes(&env, &[b'a'; 257]);
+        let result =
+            client.try_propose_withdrawal(&signer1, &recipient, &asset, &100, &long_memo, &0);
+        assert_eq!(result, Err(Ok(Error::MemoTooLong)));
+
+        let max_memo = String::from_bytes(&env, &[b'a'; 256]);
+        client.propose_withdrawal(&signer1, &recipient, &asset, &100, &max_memo, &0);
+    }
+
+    #[test]
+    fn test_weighted_signer_approvals() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let cfo = Address::generate(&env);
+        let junior1 = Address::generate(&env);
+        let junior2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [cfo.clone(), junior1.clone(), junior2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        client.set_signer_weight(&admin, &cfo, &3);
+        client.set_threshold(&admin, &4);
+        assert_eq!(client.get_signer_weight(&cfo), 3);
+        assert_eq!(client.get_signer_weight(&junior1), 1);
+        mint_asset(&env, &asset, &junior1, 1_000);
+        client.deposit(&junior1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let light = client.propose_withdrawal(
+            &junior1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "light"),
+            &0,
+        );
+        assert_eq!(client.approve(&junior2, &light), 2);
+        let result = client.try_execute(&junior1, &light);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        let heavy = client.propose_withdrawal(
+            &junior1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "heavy"),
+            &0,
+        );
+        assert_eq!(client.approve(&cfo, &heavy), 4);
+        client.execute(&junior1, &heavy);
+        assert!(client.get_transaction(&heavy).executed);
+
+        // Weights must stay positive and the threshold reachable
+        let result = client.try_set_signer_weight(&admin, &junior1, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidWeight)));
+        let result = client.try_set_signer_weight(&admin, &cfo, &1);
+        assert_eq!(result, Err(Ok(Error::ThresholdBreach)));
+        let result = client.try_remove_signer(&admin, &cfo);
+        assert_eq!(result, Err(Ok(Error::ThresholdBreach)));
+        let result = client.try_set_threshold(&admin, &6);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+    }
+
+    #[test]
+    fn test_deposits_tracked_per_depositor() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let (other_token, _sac, _token) = setup_asset(&env);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        mint_asset(&env, &asset, &alice, 1_000);
+        mint_asset(&env, &asset, &bob, 1_000);
+        mint_asset(&env, &other_token, &bob, 1_000);
+
+        client.deposit(&alice, &asset, &300);
+        client.deposit(&bob, &asset, &200);
+        client.deposit(&alice, &asset, &150);
+        client.deposit_token(&bob, &asset, &50);
+        client.deposit(&bob, &other_token, &900);
+
+        assert_eq!(client.get_deposits_by(&alice), 450);
+        assert_eq!(client.get_deposits_by(&bob), 250);
+        assert_eq!(client.get_deposits_by(&signer1), 0);
+        assert_eq!(
+            client.get_balance(&asset),
+            client.get_deposits_by(&alice) + client.get_deposits_by(&bob)
+        );
+    }
+
+    #[test]
+    fn test_proposer_auto_approval_modes() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+        assert!(client.get_config().proposer_auto_approves);
+
+        // Default: proposing counts as the first of two approvals
+        let recipient = Address::generate(&env);
+        let auto = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "auto"),
+            &0,
+        );
+        assert_eq!(client.get_transaction(&auto).approvals.len(), 1);
+        assert_eq!(client.approve(&signer2, &auto), 2);
+        client.execute(&signer1, &auto);
+
+        client.set_proposer_auto_approves(&admin, &false);
+        assert!(!client.get_config().proposer_auto_approves);
+        let explicit = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "explicit"),
+            &0,
+        );
+        assert_eq!(client.get_transaction(&explicit).approvals.len(), 0);
+        assert_eq!(client.approve(&signer2, &explicit), 1);
+        let result = client.try_execute(&signer1, &explicit);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(client.approve(&signer1, &explicit), 2);
+        client.execute(&signer1, &explicit);
+        assert_eq!(client.get_balance(&asset), 800);
+    }
+
+    #[test]
+    fn test_rejections_kill_approvable_transaction() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signer4 = Address::generate(&env);
+        let signers = Vec::from_array(
+            &env,
+            [
+                signer1.clone(),
+                signer2.clone(),
+                signer3.clone(),
+                signer4.clone(),
+            ],
+        );
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        client.set_reject_threshold(&admin, &2);
+        assert_eq!(client.get_config().reject_threshold, 2);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &600,
+            &String::from_str(&env, "disputed"),
+            &0,
+        );
+
+        // Approvers cannot also reject, and vice versa
+        assert_eq!(
+            client.try_reject(&signer1, &tx_id),
+            Err(Ok(Error::AlreadyApproved))
+        );
+        assert_eq!(client.reject(&signer3, &tx_id), 1);
+        assert_eq!(
+            client.try_approve(&signer3, &tx_id),
+            Err(Ok(Error::AlreadyRejected))
+        );
+        assert_eq!(
+            client.try_reject(&signer3, &tx_id),
+            Err(Ok(Error::AlreadyRejected))
+        );
+
+        // One more approval would meet the threshold, but a second rejection
+        // kills the transaction first
+        assert_eq!(client.reject(&signer4, &tx_id), 2);
+        let transaction = client.get_transaction(&tx_id);
+        assert!(transaction.rejected);
+        assert_eq!(transaction.rejections.len(), 2);
+        assert_eq!(client.get_config().reserved, 0);
+
+        assert_eq!(
+            client.try_approve(&signer2, &tx_id),
+            Err(Ok(Error::TransactionRejected))
+        );
+        assert_eq!(
+            client.try_execute(&signer1, &tx_id),
+            Err(Ok(Error::TransactionRejected))
+        );
+        assert_eq!(client.list_pending(&0, &10).items.len(), 0);
+    }
+
+    #[test]
+    fn test_access_control_admin_can_approve() {
+        use stellar_guard_access_control::{
+            AccessControlContract, AccessControlContractClient, Role,
+        };
+
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let owner = Address::generate(&env);
+        let access_control_id = env.register_contract(None, AccessControlContract);
+        let access_control = AccessControlContractClient::new(&env, &access_control_id);
+        access_control.initialize(&owner);
+        client.set_access_control(&admin, &access_control_id);
+        assert_eq!(client.get_config().access_control, Some(access_control_id));
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &400,
+            &String::from_str(&env, "acl"),
+            &0,
+        );
+
+        let officer = Address::generate(&env);
+        assert_eq!(
+            client.try_approve(&officer, &tx_id),
+            Err(Ok(Error::NotASigner))
+        );
+        access_control.assign_role(&owner, &officer, &Role::Member);
+        assert_eq!(
+            client.try_approve(&officer, &tx_id),
+            Err(Ok(Error::NotASigner))
+        );
+
+        access_control.assign_role(&owner, &officer, &Role::Admin);
+        assert_eq!(client.approve(&officer, &tx_id), 2);
+        client.execute(&officer, &tx_id);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&recipient), 400);
+    }
+
+    #[test]
+    fn test_balance_reserved_and_available_stay_consistent() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 5_000);
+        client.deposit(&signer1, &asset, &5_000);
+
+        let assert_totals = |balance: i128, reserved: i128, available: i128| {
+            let config = client.get_config();
+            assert_eq!(
+                (config.balance, config.reserved, config.available),
+                (balance, reserved, available)
+            );
+            assert_eq!(client.get_available(&asset), available);
+        };
+        assert_totals(5_000, 0, 5_000);
+        client.set_min_reserve(&admin, &1_000);
+        assert_totals(5_000, 0, 4_000);
+
+        let recipient = Address::generate(&env);
+        let executed = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &1_500,
+            &String::from_str(&env, "paid"),
+            &0,
+        );
+        let cancelled = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &500,
+            &String::from_str(&env, "dropped"),
+            &0,
+        );
+        assert_totals(5_000, 2_000, 2_000);
+
+        client.cancel_withdrawal(&signer1, &cancelled);
+        assert_totals(5_000, 1_500, 2_500);
+
+        client.execute(&signer1, &executed);
+        assert_totals(3_500, 0, 2_500);
+
+        // A reserve above the balance leaves nothing available
+        client.set_min_reserve(&admin, &4_000);
+        assert_totals(3_500, 0, 0);
+    }
+
+    #[test]
+    fn test_recurring_withdrawal_interval_and_exhaustion() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 10_000);
+        client.deposit(&signer1, &asset, &10_000);
+
+        env.ledger().set_timestamp(1_000);
+        let vendor = Address::generate(&env);
+        let month = 30 * 24 * 60 * 60;
+        let schedule_id = client.propose_recurring(
+            &signer1,
+            &vendor,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "monthly retainer"),
+            &month,
+            &2,
+        );
+
+        // First payment is due now but needs a second approval
+        let result = client.try_execute_recurring(&signer1, &schedule_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.approve_recurring(&signer2, &schedule_id), 2);
+        client.execute_recurring(&signer1, &schedule_id);
+        let schedule = client.get_schedule(&schedule_id);
+        assert_eq!(schedule.paid, 1);
+        assert_eq!(schedule.next_due, 1_000 + month);
+        assert_eq!(schedule.approvals.len(), 0);
+
+        // Each cycle needs fresh approvals and must wait for the interval
+        client.approve_recurring(&signer1, &schedule_id);
+        client.approve_recurring(&signer2, &schedule_id);
+        let result = client.try_execute_recurring(&signer1, &schedule_id);
+        assert_eq!(result, Err(Ok(Error::PaymentNotDue)));
+
+        env.ledger().set_timestamp(1_000 + month);
+        client.execute_recurring(&signer1, &schedule_id);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&vendor), 2_000);
+        assert_eq!(client.get_balance(&asset), 8_000);
+
+        env.ledger().set_timestamp(1_000 + 3 * month);
+        let result = client.try_approve_recurring(&signer1, &schedule_id);
+        assert_eq!(result, Err(Ok(Error::ScheduleExhausted)));
+        let result = client.try_execute_recurring(&signer1, &schedule_id);
+        assert_eq!(result, Err(Ok(Error::ScheduleExhausted)));
+    }
+
+    #[test]
+    fn test_recurring_withdrawal_approved_once() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        client.set_approve_schedule_once(&admin, &true);
+        mint_asset(&env, &asset, &signer1, 10_000);
+        client.deposit(&signer1, &asset, &10_000);
+
+        let vendor = Address::generate(&env);
+        let schedule_id = client.propose_recurring(
+            &signer1,
+            &vendor,
+            &asset,
+            &500,
+            &String::from_str(&env, "weekly"),
+            &604_800,
+            &3,
+        );
+        client.approve_recurring(&signer2, &schedule_id);
+
+        for week in 0..3 {
+            env.ledger().set_timestamp(week * 604_800);
+            client.execute_recurring(&signer1, &schedule_id);
+        }
+        assert_eq!(client.get_schedule(&schedule_id).paid, 3);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&vendor), 1_500);
+    }
+
+    #[test]
+    fn test_withdrawal_fee_split() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 10_000);
+        client.deposit(&signer, &asset, &10_000);
+
+        let collector = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let token = TokenClient::new(&env, &asset);
+        let result = client.try_set_withdrawal_fee(&admin, &10_001, &collector, &false);
+        assert_eq!(result, Err(Ok(Error::InvalidFee)));
+
+        // 2.5% taken out of the amount
+        client.set_withdrawal_fee(&admin, &250, &collector, &false);
+        let tx_id = client.propose_withdrawal(
+            &signer,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "fee out of amount"),
+            &0,
+        );
+        client.execute(&signer, &tx_id);
+        assert_eq!(token.balance(&recipient), 975);
+        assert_eq!(token.balance(&collector), 25);
+        assert_eq!(client.get_balance(&asset), 9_000);
+
+        // 2.5% charged on top of the amount
+        client.set_withdrawal_fee(&admin, &250, &collector, &true);
+        let tx_id = client.propose_withdrawal(
+            &signer,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "fee on top"),
+            &0,
+        );
+        client.execute(&signer, &tx_id);
+        assert_eq!(token.balance(&recipient), 1_975);
+        assert_eq!(token.balance(&collector), 50);
+        assert_eq!(client.get_balance(&asset), 7_975);
+        assert_eq!(token.balance(&client.address), 7_975);
+
+        let config = client.get_config();
+        assert_eq!(config.fee_bps, 250);
+        assert_eq!(config.fee_collector, Some(collector));
+    }
+
+    #[test]
+    fn test_fee_on_top_cannot_spend_other_reservations() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 1_100);
+        client.deposit(&signer, &asset, &1_000);
+
+        let collector = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        client.set_withdrawal_fee(&admin, &1_000, &collector, &true);
+        let memo = String::from_str(&env, "fee on top");
+        let first = client.propose_withdrawal(&signer, &recipient, &asset, &900, &memo, &0);
+        let second = client.propose_withdrawal(&signer, &recipient, &asset, &100, &memo, &0);
+
+        // The 90 fee would come out of the second proposal's reservation
+        let result = client.try_execute(&signer, &first);
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+
+        // Each proposal's own fee also needs unreserved funds
+        client.deposit(&signer, &asset, &90);
+        client.execute(&signer, &first);
+        let result = client.try_execute(&signer, &second);
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+        client.deposit(&signer, &asset, &10);
+        client.execute(&signer, &second);
+        assert_eq!(client.get_balance(&asset), 0);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&collector), 100);
+    }
+
+    #[test]
+    fn test_recurring_and_governance_payments_skip_withdrawal_fee() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 1_000);
+        client.deposit(&signer, &asset, &1_000);
+
+        let collector = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let governance = Address::generate(&env);
+        let token = TokenClient::new(&env, &asset);
+        client.set_withdrawal_fee(&admin, &1_000, &collector, &true);
+        client.set_governance(&admin, &governance);
+
+        let schedule_id = client.propose_recurring(
+            &signer,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "retainer"),
+            &60,
+            &1,
+        );
+        client.execute_recurring(&signer, &schedule_id);
+        client.release_funds(&governance, &recipient, &200);
+
+        assert_eq!(token.balance(&recipient), 300);
+        assert_eq!(token.balance(&collector), 0);
+        assert_eq!(client.get_balance(&asset), 700);
+    }
+
+    #[test]
+    fn test_zero_withdrawal_fee_changes_nothing() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 10_000);
+        client.deposit(&signer, &asset, &10_000);
+
+        let collector = Address::generate(&env);
+        client.set_withdrawal_fee(&admin, &0, &collector, &true);
+        assert_eq!(client.get_config().fee_collector, None);
+
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer,
+            &recipient,
+            &asset,
+            &1_000,
+            &String::from_str(&env, "no fee"),
+            &0,
+        );
+        client.execute(&signer, &tx_id);
+
+        let events = contract_events(&env, &contract_id);
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            topics,
+            vec![
+                &env,
+                symbol_short!("treasury").into_val(&env),
+                symbol_short!("execute").into_val(&env),
+                EVENT_VERSION.into_val(&env),
+            ]
+        );
+        let payload: (u64, Address, Address, i128, i128, bool) =
+            <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(
+            payload,
+            (tx_id, recipient.clone(), asset.clone(), 1_000, 9_000, true)
+        );
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&recipient), 1_000);
+        assert_eq!(token.balance(&collector), 0);
+        assert_eq!(client.get_balance(&asset), 9_000);
+    }
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let new_admin = Address::generate(&env);
+        client.propose_admin(&admin, &new_admin);
+        assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+        // The current admin keeps control until the handoff is accepted
+        assert_eq!(client.get_config().admin, admin);
+        client.set_threshold(&admin, &1);
+
+        client.accept_admin(&new_admin);
+        assert_eq!(client.get_config().admin, new_admin);
+        assert_eq!(client.get_pending_admin(), None);
+        let result = client.try_set_threshold(&admin, &1);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_accept_admin_wrong_address_fails() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let result = client.try_accept_admin(&signer);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.propose_admin(&admin, &Address::generate(&env));
+        let result = client.try_accept_admin(&signer);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_config().admin, admin);
+    }
+
+    #[test]
+    fn test_repropose_admin_cancels_pending() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        // A typo'd address is replaced by proposing again
+        let typo = Address::generate(&env);
+        client.propose_admin(&admin, &typo);
+        client.propose_admin(&admin, &admin);
+
+        let result = client.try_accept_admin(&typo);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_pending_admin(), Some(admin.clone()));
+    }
+
+    #[test]
+    fn test_execute_blocks_reentrant_token() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        let token_id = env.register_contract(None, ReentrantToken);
+        let token = ReentrantTokenClient::new(&env, &token_id);
+        client.deposit(&signer, &token_id, &1_000);
+        let tx_id = client.propose_withdrawal(
+            &signer,
+            &Address::generate(&env),
+            &token_id,
+            &600,
+            &String::from_str(&env, "drain"),
+            &0,
+        );
+
+        token.arm(&contract_id, &signer, &tx_id);
+        client.execute(&signer, &tx_id);
+
+        // The nested execute was refused, so the withdrawal was paid once
+        assert!(token.reentry_blocked());
+        assert!(client.get_transaction(&tx_id).executed);
+        assert_eq!(client.get_balance(&token_id), 400);
+    }
+
+    #[test]
+    fn test_guarded_entry_points_reject_while_locked() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer, 1_000);
+        client.deposit(&signer, &asset, &1_000);
+        let tx_id = client.propose_withdrawal(
+            &signer,
+            &Address::generate(&env),
+            &asset,
+            &100,
+            &String::from_str(&env, "locked"),
+            &0,
+        );
+
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&DataKey::Locked, &true);
+        });
+        let result = client.try_execute(&signer, &tx_id);
+        assert_eq!(result, Err(Ok(Error::Reentrancy)));
+
+        env.as_contract(&contract_id, || {
+            env.storage().instance().remove(&DataKey::Locked);
+        });
+        client.execute(&signer, &tx_id);
+        assert_eq!(client.get_balance(&asset), 900);
+    }
+
+    #[test]
+    fn test_requester_proposal_needs_signer_approvals() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let department_head = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "team offsite");
 
-    // ========================================================================
-    // Internal Helpers
-    // ========================================================================
+        // Not a signer and not yet a requester
+        let result =
+            client.try_propose_withdrawal(&department_head, &recipient, &asset, &300, &memo, &0);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
 
-    fn require_initialized(env: &Env) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(Error::NotInitialized);
-        }
-        Ok(())
-    }
+        client.set_requester(&admin, &department_head, &true);
+        assert!(client.is_requester(&department_head));
+        let tx_id =
+            client.propose_withdrawal(&department_head, &recipient, &asset, &300, &memo, &0);
+        let transaction = client.get_transaction(&tx_id);
+        assert_eq!(transaction.proposer, department_head);
+        assert_eq!(transaction.approvals.len(), 0);
 
-    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if *caller != admin {
-            return Err(Error::Unauthorized);
-        }
-        Ok(())
-    }
+        // Requesters cannot approve their own proposals
+        let result = client.try_approve(&department_head, &tx_id);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
 
-    fn require_signer(env: &Env, caller: &Address) -> Result<(), Error> {
-        let signers: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Signers)
-            .unwrap_or(Vec::new(env));
+        assert_eq!(client.approve(&signer1, &tx_id), 1);
+        assert_eq!(client.approve(&signer2, &tx_id), 2);
+        client.execute(&signer1, &tx_id);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&recipient), 300);
 
-        for i in 0..signers.len() {
-            if signers.get(i).unwrap() == *caller {
-                return Ok(());
-            }
-        }
-        Err(Error::NotASigner)
+        client.set_requester(&admin, &department_head, &false);
+        assert!(!client.is_requester(&department_head));
+        let result =
+            client.try_propose_withdrawal(&department_head, &recipient, &asset, &300, &memo, &0);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[test]
+    fn test_get_approvals_lists_approvers() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::testutils::Events as _;
-    use soroban_sdk::token::{StellarAssetClient, TokenClient};
-    use soroban_sdk::{vec, Env, IntoVal, TryFromVal, Val};
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 3, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-    // ── helpers shared by all tests ──────────────────────────────────────────
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &100,
+            &String::from_str(&env, "polled"),
+            &0,
+        );
+        client.approve(&signer3, &tx_id);
+        client.approve(&signer2, &tx_id);
 
-    fn setup_contract() -> (Env, Address, Address, TreasuryContractClient<'static>) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, TreasuryContract);
-        let client = TreasuryContractClient::new(&env, &contract_id);
-        let admin = Address::generate(&env);
-        (env, admin, contract_id, client)
-    }
+        assert_eq!(
+            client.get_approvals(&tx_id),
+            Vec::from_array(&env, [signer1, signer3, signer2])
+        );
+        assert_eq!(client.get_approval_count(&tx_id), 3);
 
-    fn setup_asset(env: &Env) -> (Address, StellarAssetClient<'_>, TokenClient<'_>) {
-        let token_admin = Address::generate(env);
-        let sac_data = env.register_stellar_asset_contract_v2(token_admin.clone());
-        let token_address = sac_data.address();
-        let sac = StellarAssetClient::new(env, &token_address);
-        let token = TokenClient::new(env, &token_address);
-        (token_address, sac, token)
+        assert_eq!(
+            client.try_get_approvals(&99),
+            Err(Ok(Error::TransactionNotFound))
+        );
+        assert_eq!(
+            client.try_get_approval_count(&99),
+            Err(Ok(Error::TransactionNotFound))
+        );
     }
 
-    fn initialize_treasury(
-        client: &TreasuryContractClient<'_>,
-        env: &Env,
-        admin: &Address,
-        threshold: u32,
-        signers: &Vec<Address>,
-    ) -> Address {
-        let (asset, _sac, _token) = setup_asset(env);
-        client.initialize(admin, &threshold, signers, &asset);
-        asset
-    }
+    #[test]
+    fn test_add_signer_rejects_zero_address() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
-    fn mint_asset(env: &Env, asset: &Address, recipient: &Address, amount: i128) {
-        StellarAssetClient::new(env, asset).mint(recipient, &amount);
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        for zero in ZERO_ADDRESSES {
+            let zero = Address::from_string(&String::from_str(&env, zero));
+            let result = client.try_add_signer(&admin, &zero);
+            assert_eq!(result, Err(Ok(Error::InvalidSigner)));
+        }
+        assert_eq!(client.get_signers().len(), 1);
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_execute_partial_pays_remainder_later() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
         let signer2 = Address::generate(&env);
-        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let vendor = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &signer1,
+            &vendor,
+            &asset,
+            &600,
+            &String::from_str(&env, "invoice"),
+            &0,
+        );
+        assert_eq!(client.get_config().reserved, 600);
 
-        let _asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        // Threshold rules still apply to partial payments
+        let result = client.try_execute_partial(&signer1, &tx_id, &200);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.approve(&signer2, &tx_id);
+
+        let result = client.try_execute_partial(&signer1, &tx_id, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let result = client.try_execute_partial(&signer1, &tx_id, &601);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
 
+        client.execute_partial(&signer1, &tx_id, &450);
+        let transaction = client.get_transaction(&tx_id);
+        assert_eq!(transaction.amount - transaction.paid, 150);
+        assert!(!transaction.executed);
         let config = client.get_config();
-        assert_eq!(config.admin, admin);
-        assert_eq!(config.threshold, 2);
-        assert_eq!(config.signer_count, 3);
-        assert_eq!(config.balance, 0);
+        assert_eq!(config.balance, 550);
+        assert_eq!(config.reserved, 150);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&vendor), 450);
+
+        client.execute_partial(&signer1, &tx_id, &150);
+        let transaction = client.get_transaction(&tx_id);
+        assert_eq!(transaction.paid, 600);
+        assert!(transaction.executed);
+        let config = client.get_config();
+        assert_eq!(config.balance, 400);
+        assert_eq!(config.reserved, 0);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&vendor), 600);
+
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyExecuted)));
     }
 
     #[test]
-    fn test_deposit() {
+    fn test_request_refund_returns_deposit() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer1.clone()]);
-        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
         let depositor = Address::generate(&env);
-        mint_asset(&env, &asset, &depositor, 1_000_000);
-        client.deposit(&depositor, &1_000_000);
+        mint_asset(&env, &asset, &depositor, 500);
+        client.deposit(&depositor, &asset, &500);
+        assert_eq!(client.get_deposits_by(&depositor), 500);
+
+        let result = client.try_request_refund(&depositor, &501);
+        assert_eq!(result, Err(Ok(Error::RefundTooLarge)));
+        let result = client.try_request_refund(&depositor, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        // Refunds need only one signer once a lower threshold is set
+        client.set_refund_threshold(&admin, &1);
+        assert_eq!(client.get_config().refund_threshold, 1);
+        let tx_id = client.request_refund(&depositor, &300);
+        let transaction = client.get_transaction(&tx_id);
+        assert!(transaction.refund);
+        assert_eq!(transaction.to, depositor);
+        assert_eq!(transaction.approvals.len(), 0);
+        assert_eq!(client.get_deposits_by(&depositor), 200);
+        assert_eq!(client.get_config().reserved, 300);
+
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.approve(&signer1, &tx_id);
+        client.execute(&signer1, &tx_id);
 
-        assert_eq!(client.get_balance(), 1_000_000);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&depositor), 300);
+        let config = client.get_config();
+        assert_eq!(config.balance, 1_200);
+        assert_eq!(config.reserved, 0);
+        assert_eq!(client.get_deposits_by(&depositor), 200);
     }
 
-    /// Integration test for SC-26: verify `deposit` actually moves the
-    /// underlying SAC tokens from the depositor into the treasury contract
-    /// rather than only incrementing the bookkeeping balance.
     #[test]
-    fn test_deposit_moves_real_tokens_on_chain() {
-        let (env, admin, contract_id, client) = setup_contract();
+    fn test_cancelled_refund_restores_deposit_total() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
-        let signer = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer]);
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
         let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
 
         let depositor = Address::generate(&env);
-        mint_asset(&env, &asset, &depositor, 5_000_000);
-
-        let token = TokenClient::new(&env, &asset);
-        assert_eq!(token.balance(&depositor), 5_000_000);
-        assert_eq!(token.balance(&contract_id), 0);
-
-        client.deposit(&depositor, &2_000_000);
-
-        assert_eq!(token.balance(&contract_id), 2_000_000);
-        assert_eq!(token.balance(&depositor), 3_000_000);
-        assert_eq!(client.get_balance(), 2_000_000);
-
-        client.deposit(&depositor, &1_500_000);
-        assert_eq!(token.balance(&contract_id), 3_500_000);
-        assert_eq!(token.balance(&depositor), 1_500_000);
-        assert_eq!(client.get_balance(), 3_500_000);
+        mint_asset(&env, &asset, &depositor, 500);
+        client.deposit(&depositor, &asset, &500);
+
+        let tx_id = client.request_refund(&depositor, &400);
+        // The pending refund counts against the total, so it cannot be
+        // requested twice
+        let result = client.try_request_refund(&depositor, &400);
+        assert_eq!(result, Err(Ok(Error::RefundTooLarge)));
+
+        client.cancel_withdrawal(&depositor, &tx_id);
+        assert_eq!(client.get_deposits_by(&depositor), 500);
+        assert_eq!(client.get_config().reserved, 0);
+        assert_eq!(client.get_balance(&asset), 500);
     }
 
     #[test]
-    fn test_propose_and_approve() {
+    fn test_approval_delegate_approves_for_signer() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
         let signer2 = Address::generate(&env);
         let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
         let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        // Deposit some funds
-        mint_asset(&env, &asset, &signer1, 5_000_000);
-        client.deposit(&signer1, &5_000_000);
-
-        // Propose withdrawal
+        let backup = Address::generate(&env);
         let recipient = Address::generate(&env);
-        let tx_id = client.propose_withdrawal(
-            &signer1,
-            &recipient,
-            &1_000_000,
-            &String::from_str(&env, "rent"),
-        );
-        assert_eq!(tx_id, 1);
+        let memo = String::from_str(&env, "delegated");
+        env.ledger().set_timestamp(1_000);
 
-        // Second signer approves
-        let approval_count = client.approve(&signer2, &tx_id);
-        assert_eq!(approval_count, 2);
+        // Not a delegate yet
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        let result = client.try_approve(&backup, &tx_id);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
 
-        // Execute
-        client.execute(&signer1, &tx_id);
+        let result = client.try_set_approval_delegate(&signer2, &backup, &1_000);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+        let result = client.try_set_approval_delegate(&signer2, &signer1, &2_000);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
+        client.set_approval_delegate(&signer2, &backup, &2_000);
+        assert_eq!(
+            client.get_approval_delegate(&signer2),
+            Some(ApprovalDelegation {
+                delegate: backup.clone(),
+                expires_at: 2_000,
+            })
+        );
+        let result = client.try_set_approval_delegate(&signer1, &backup, &2_000);
+        assert_eq!(result, Err(Ok(Error::InvalidDelegation)));
 
-        // Check balance deducted
-        assert_eq!(client.get_balance(), 4_000_000);
+        // The delegate's approval is credited to signer2
+        assert_eq!(client.approve(&backup, &tx_id), 2);
         assert_eq!(
-            TokenClient::new(&env, &asset).balance(&recipient),
-            1_000_000
+            client.get_approvals(&tx_id),
+            Vec::from_array(&env, [signer1.clone(), signer2.clone()])
         );
+        let result = client.try_approve(&signer2, &tx_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+        client.execute(&signer1, &tx_id);
 
-        // Check transaction marked as executed
-        let tx = client.get_transaction(&tx_id);
-        assert_eq!(tx.executed, true);
+        // The signer approving first blocks the delegate too
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        client.approve(&signer2, &tx_id);
+        let result = client.try_approve(&backup, &tx_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+
+        // Delegations lapse at their expiry
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        env.ledger().set_timestamp(2_000);
+        let result = client.try_approve(&backup, &tx_id);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
+
+        // And can be revoked early
+        client.set_approval_delegate(&signer2, &backup, &5_000);
+        client.revoke_approval_delegate(&signer2);
+        assert_eq!(client.get_approval_delegate(&signer2), None);
+        let result = client.try_approve(&backup, &tx_id);
+        assert_eq!(result, Err(Ok(Error::NotASigner)));
     }
 
     #[test]
-    fn test_full_treasury_workflow_emits_expected_events() {
-        let (env, admin, contract_id, client) = setup_contract();
+    fn test_lifetime_totals_across_cycles() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
-        let signer2 = Address::generate(&env);
-        let signer3 = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
-        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
-
-        mint_asset(&env, &asset, &signer1, 8_000_000);
-        client.deposit(&signer1, &8_000_000);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 10_000);
 
         let recipient = Address::generate(&env);
-        let tx_id = client.propose_withdrawal(
-            &signer1,
-            &recipient,
-            &3_000_000,
-            &String::from_str(&env, "ops"),
-        );
-
-        let approvals = client.approve(&signer2, &tx_id);
-        assert_eq!(approvals, 2);
+        let memo = String::from_str(&env, "cycle");
+        assert_eq!(client.get_total_withdrawn(), 0);
+
+        for (deposit, withdrawal) in [(1_000, 400), (500, 1_100), (2_000, 0), (300, 2_300)] {
+            client.deposit(&signer1, &asset, &deposit);
+            if withdrawal > 0 {
+                let tx_id =
+                    client.propose_withdrawal(&signer1, &recipient, &asset, &withdrawal, &memo, &0);
+                client.execute(&signer1, &tx_id);
+            }
+        }
 
-        client.execute(&signer3, &tx_id);
+        // Everything deposited has been paid out, yet the totals remain
+        let config = client.get_config();
+        assert_eq!(config.balance, 0);
+        assert_eq!(config.total_deposited, 3_800);
+        assert_eq!(config.total_withdrawn, 3_800);
+        assert_eq!(client.get_total_withdrawn(), 3_800);
 
+        client.deposit(&signer1, &asset, &700);
         let config = client.get_config();
-        assert_eq!(config.balance, 5_000_000);
+        assert_eq!(config.balance, 700);
+        assert_eq!(config.total_deposited, 4_500);
+        assert_eq!(config.total_withdrawn, 3_800);
+    }
 
-        let transaction = client.get_transaction(&tx_id);
-        assert_eq!(transaction.executed, true);
-        assert_eq!(transaction.amount, 3_000_000);
-        assert_eq!(transaction.to, recipient);
-        assert_eq!(
-            TokenClient::new(&env, &asset).balance(&recipient),
-            3_000_000
-        );
+    #[test]
+    fn test_can_execute_reports_blocking_error() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 5);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "dry run");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &1_000, &memo, &100);
 
-        let init_event = events.get(0).unwrap();
-        assert_eq!(init_event.0, contract_id);
         assert_eq!(
-            init_event.1,
-            vec![
-                &env,
-                symbol_short!("treasury").into_val(&env),
-                symbol_short!("init").into_val(&env),
-            ]
+            client.try_can_execute(&99),
+            Err(Ok(Error::TransactionNotFound))
+        );
+        assert_eq!(client.try_can_execute(&tx_id), Err(Ok(Error::Unauthorized)));
+        client.approve(&signer2, &tx_id);
+        assert_eq!(
+            client.try_can_execute(&tx_id),
+            Err(Ok(Error::TimelockActive))
         );
+        env.ledger().set_timestamp(100);
+        assert_eq!(client.try_can_execute(&tx_id), Ok(Ok(())));
 
-        let deposit_event = events.get(1).unwrap();
-        assert_eq!(deposit_event.0, contract_id);
+        client.set_spend_limit(&admin, &500, &3_600);
         assert_eq!(
-            deposit_event.1,
-            vec![
-                &env,
-                symbol_short!("treasury").into_val(&env),
-                symbol_short!("deposit").into_val(&env),
-            ]
+            client.try_can_execute(&tx_id),
+            Err(Ok(Error::SpendLimitExceeded))
         );
+        client.set_spend_limit(&admin, &0, &0);
 
-        let propose_event = events.get(2).unwrap();
-        assert_eq!(propose_event.0, contract_id);
+        let collector = Address::generate(&env);
+        client.set_withdrawal_fee(&admin, &100, &collector, &true);
         assert_eq!(
-            propose_event.1,
-            vec![
-                &env,
-                symbol_short!("treasury").into_val(&env),
-                symbol_short!("propose").into_val(&env),
-            ]
+            client.try_can_execute(&tx_id),
+            Err(Ok(Error::InsufficientFunds))
         );
+        client.set_withdrawal_fee(&admin, &0, &collector, &true);
 
-        let approve_event = events.get(3).unwrap();
-        assert_eq!(approve_event.0, contract_id);
+        client.freeze(&admin);
+        assert_eq!(client.try_can_execute(&tx_id), Err(Ok(Error::Frozen)));
+        client.unfreeze(&admin);
+
+        // The dry runs left nothing behind
+        assert_eq!(client.try_can_execute(&tx_id), Ok(Ok(())));
+        assert_eq!(client.get_transaction(&tx_id).paid, 0);
+        assert_eq!(client.get_balance(&asset), 1_000);
+
+        client.execute(&signer1, &tx_id);
         assert_eq!(
-            approve_event.1,
-            vec![
-                &env,
-                symbol_short!("treasury").into_val(&env),
-                symbol_short!("approve").into_val(&env),
-            ]
+            client.try_can_execute(&tx_id),
+            Err(Ok(Error::AlreadyExecuted))
         );
+    }
 
-        let execute_event = events.get(events.len() - 1).unwrap();
-        let actual_data: Vec<Val> = Vec::try_from_val(&env, &execute_event.2).unwrap();
-        let expected_data: Vec<Val> = vec![
+    #[test]
+    fn test_get_version() {
+        let (env, admin, _contract_id, client) = setup_contract();
+        let signers = Vec::from_array(&env, [Address::generate(&env)]);
+        initialize_treasury(&client, &env, &admin, 1, &signers);
+
+        assert_eq!(client.get_version(), VERSION);
+
+        // Swap the code in place, as an upgrade would; the data keeps the
+        // version it was written by.
+        env.register_contract(Some(&client.address), TreasuryContract);
+        assert_eq!(client.get_version(), VERSION);
+        let stored: Option<u32> = env.as_contract(&client.address, || {
+            env.storage().instance().get(&DataKey::Version)
+        });
+        assert_eq!(stored, Some(VERSION));
+    }
+
+    #[test]
+    fn test_get_pending_signers() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signer4 = Address::generate(&env);
+        let signers = Vec::from_array(
             &env,
-            tx_id.into_val(&env),
-            recipient.into_val(&env),
-            3_000_000_i128.into_val(&env),
-            5_000_000_i128.into_val(&env),
-            true.into_val(&env),
-        ];
+            [
+                signer1.clone(),
+                signer2.clone(),
+                signer3.clone(),
+                signer4.clone(),
+            ],
+        );
+        let asset = initialize_treasury(&client, &env, &admin, 3, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "nudge");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        client.approve(&signer3, &tx_id);
+
         assert_eq!(
-            execute_event.1,
-            vec![
-                &env,
-                symbol_short!("treasury").into_val(&env),
-                symbol_short!("execute").into_val(&env),
-            ]
+            client.get_pending_signers(&tx_id),
+            Vec::from_array(&env, [signer2.clone(), signer4.clone()])
+        );
+
+        // Follows changes to the signer list
+        client.remove_signer(&admin, &signer4);
+        assert_eq!(
+            client.get_pending_signers(&tx_id),
+            Vec::from_array(&env, [signer2])
+        );
+        assert_eq!(
+            client.try_get_pending_signers(&99),
+            Err(Ok(Error::TransactionNotFound))
         );
-        assert_eq!(actual_data, expected_data);
     }
 
     #[test]
-    fn test_get_signers() {
+    fn test_approval_and_rejection_reasons() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
         let signer2 = Address::generate(&env);
         let signer3 = Address::generate(&env);
         let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        let _asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "audit");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+
+        let too_long = String::from_str(&env, &"x".repeat(MAX_REASON_LEN as usize + 1));
+        let result = client.try_approve_with_reason(&signer2, &tx_id, &too_long);
+        assert_eq!(result, Err(Ok(Error::ReasonTooLong)));
+
+        let approved = String::from_str(&env, "Matches invoice #42");
+        let rejected = String::from_str(&env, "Recipient not on allowlist");
+        assert_eq!(client.approve_with_reason(&signer2, &tx_id, &approved), 2);
+        assert_eq!(client.reject_with_reason(&signer3, &tx_id, &rejected), 1);
+        assert_eq!(client.get_reason(&tx_id, &signer2), Some(approved));
+        assert_eq!(client.get_reason(&tx_id, &signer3), Some(rejected));
+        assert_eq!(client.get_reason(&tx_id, &signer1), None);
+
+        // The approval counts toward the threshold like any other
+        client.execute(&signer1, &tx_id);
+        assert!(client.get_transaction(&tx_id).executed);
+    }
 
-        let fetched = client.get_signers();
-        assert_eq!(fetched.len(), 3);
-        assert_eq!(fetched.get(0).unwrap(), signer1);
-        assert_eq!(fetched.get(1).unwrap(), signer2);
-        assert_eq!(fetched.get(2).unwrap(), signer3);
+    #[test]
+    fn test_deposit_for_attributes_to_beneficiary() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signers = Vec::from_array(&env, [Address::generate(&env)]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let payroll = Address::generate(&env);
+        let department = Address::generate(&env);
+        mint_asset(&env, &asset, &payroll, 1_000);
+
+        let result = client.try_deposit_for(&payroll, &department, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        client.deposit_for(&payroll, &department, &600);
+
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&payroll), 400);
+        assert_eq!(token.balance(&department), 0);
+        assert_eq!(client.get_balance(&asset), 600);
+        assert_eq!(client.get_deposits_by(&department), 600);
+        assert_eq!(client.get_deposits_by(&payroll), 0);
+
+        let (_, topics, data) = contract_events(&env, &contract_id).last().unwrap();
+        assert_eq!(
+            topics,
+            (
+                symbol_short!("treasury"),
+                symbol_short!("dep_for"),
+                EVENT_VERSION
+            )
+                .into_val(&env)
+        );
+        let payload: (Address, Address, i128, i128) = <_>::try_from_val(&env, &data).unwrap();
+        assert_eq!(payload, (payroll, department.clone(), 600, 600));
+
+        // The beneficiary can have the deposit refunded to them
+        client.request_refund(&department, &600);
     }
 
     #[test]
-    fn test_get_transaction() {
+    fn test_threshold_percent_tracks_signer_count() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
         let signer2 = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
         let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        mint_asset(&env, &asset, &signer1, 5_000_000);
-        client.deposit(&signer1, &5_000_000);
+        let result = client.try_set_threshold_percent(&admin, &101);
+        assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+
+        // 60% of three signers rounds up to two
+        client.set_threshold_percent(&admin, &60);
+        let config = client.get_config();
+        assert_eq!(config.threshold_percent, 60);
+        assert_eq!(config.threshold, 2);
 
         let recipient = Address::generate(&env);
-        let tx_id = client.propose_withdrawal(
-            &signer1,
-            &recipient,
-            &1_000_000,
-            &String::from_str(&env, "rent"),
+        let memo = String::from_str(&env, "percent");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        client.approve(&signer2, &tx_id);
+        assert_eq!(client.try_can_execute(&tx_id), Ok(Ok(())));
+
+        // Two more signers make it three of five
+        client.add_signer(&admin, &Address::generate(&env));
+        client.add_signer(&admin, &Address::generate(&env));
+        assert_eq!(client.get_config().threshold, 3);
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.approve(&signer3, &tx_id);
+        client.execute(&signer1, &tx_id);
+
+        // Switching back restores the fixed threshold
+        client.set_threshold_percent(&admin, &0);
+        assert_eq!(client.get_config().threshold, 2);
+    }
+
+    #[test]
+    fn test_ready_event_fires_once_threshold_is_met() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signer4 = Address::generate(&env);
+        let signers = Vec::from_array(
+            &env,
+            [
+                signer1.clone(),
+                signer2.clone(),
+                signer3.clone(),
+                signer4.clone(),
+            ],
         );
+        let asset = initialize_treasury(&client, &env, &admin, 3, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        let tx = client.get_transaction(&tx_id);
-        assert_eq!(tx.id, tx_id);
-        assert_eq!(tx.to, recipient);
-        assert_eq!(tx.amount, 1_000_000);
-        assert_eq!(tx.memo, String::from_str(&env, "rent"));
-        assert_eq!(tx.proposer, signer1);
-        assert_eq!(tx.approvals.len(), 1);
-        assert_eq!(tx.approvals.get(0).unwrap(), signer1);
-        assert_eq!(tx.executed, false);
+        let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "ready");
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+
+        let ready_topics = vec![
+            &env,
+            symbol_short!("treasury").into_val(&env),
+            symbol_short!("ready").into_val(&env),
+            EVENT_VERSION.into_val(&env),
+        ];
+        let ready_count = |env: &Env| {
+            contract_events(env, &contract_id)
+                .iter()
+                .filter(|(_, topics, _)| *topics == ready_topics)
+                .count()
+        };
+
+        client.approve(&signer2, &tx_id);
+        assert_eq!(ready_count(&env), 0);
+
+        client.approve(&signer3, &tx_id);
+        let events = contract_events(&env, &contract_id);
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(topics, ready_topics);
+        assert_eq!(u64::try_from_val(&env, &data).unwrap(), tx_id);
+
+        // Approvals past the threshold do not announce it again
+        client.approve(&signer4, &tx_id);
+        assert_eq!(ready_count(&env), 1);
     }
 
     #[test]
-    fn test_propose_rejects_non_signer() {
+    fn test_min_reserve_limits_withdrawals() {
         let (env, admin, _contract_id, client) = setup_contract();
 
-        let signer = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer.clone()]);
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
         let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
-        mint_asset(&env, &asset, &signer, 5_000_000);
-        client.deposit(&signer, &5_000_000);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let result = client.try_set_min_reserve(&admin, &-1);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        client.set_min_reserve(&admin, &400);
+        assert_eq!(client.get_config().min_reserve, 400);
+        assert_eq!(client.get_available(&asset), 600);
 
-        let non_signer = Address::generate(&env);
         let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "reserve");
+        let result = client.try_propose_withdrawal(&signer1, &recipient, &asset, &700, &memo, &0);
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
 
-        let result = client.try_propose_withdrawal(
-            &non_signer,
-            &recipient,
-            &1_000_000,
-            &String::from_str(&env, "rent"),
-        );
-        assert_eq!(result, Err(Ok(Error::NotASigner)));
+        let tx_id = client.propose_withdrawal(&signer1, &recipient, &asset, &600, &memo, &0);
+
+        // Raising the reserve blocks execution of the pending withdrawal
+        client.set_min_reserve(&admin, &500);
+        let result = client.try_execute(&signer1, &tx_id);
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+
+        client.set_min_reserve(&admin, &400);
+        client.execute(&signer1, &tx_id);
+        assert_eq!(client.get_balance(&asset), 400);
+        assert_eq!(client.get_available(&asset), 0);
     }
 
     #[test]
-    fn test_propose_rejects_insufficient_funds() {
+    fn test_events_carry_schema_version() {
+        let (env, admin, contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+        let memo = String::from_str(&env, "versioned");
+        let tx_id =
+            client.propose_withdrawal(&signer1, &Address::generate(&env), &asset, &100, &memo, &0);
+        client.execute(&signer1, &tx_id);
+        client.freeze(&admin);
+
+        let events = contract_events(&env, &contract_id);
+        assert!(events.len() >= 5);
+        for (_, topics, _) in events.iter() {
+            assert_eq!(topics.len(), 3);
+            let version = u32::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
+            assert_eq!(version, EVENT_VERSION);
+        }
+    }
+
+    #[test]
+    fn test_get_transaction_status() {
         let (env, admin, _contract_id, client) = setup_contract();
 
-        let signer = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer.clone()]);
-        let _asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
+        env.ledger().set_timestamp(1_000);
         let recipient = Address::generate(&env);
+        let memo = String::from_str(&env, "status");
+        let paid = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &2_000);
+        assert_eq!(
+            client.get_transaction_status(&paid),
+            TransactionStatus::Pending
+        );
 
-        let result = client.try_propose_withdrawal(
-            &signer,
-            &recipient,
-            &1_000_000,
-            &String::from_str(&env, "rent"),
+        // Approved but still timelocked
+        client.approve(&signer2, &paid);
+        assert_eq!(
+            client.get_transaction_status(&paid),
+            TransactionStatus::Pending
         );
-        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+
+        env.ledger().set_timestamp(2_000);
+        assert_eq!(
+            client.get_transaction_status(&paid),
+            TransactionStatus::Executable
+        );
+        client.execute(&signer1, &paid);
+        assert_eq!(
+            client.get_transaction_status(&paid),
+            TransactionStatus::Executed
+        );
+
+        let cancelled = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        client.cancel_withdrawal(&signer1, &cancelled);
+        assert_eq!(
+            client.get_transaction_status(&cancelled),
+            TransactionStatus::Cancelled
+        );
+
+        client.set_reject_threshold(&admin, &1);
+        let rejected = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        client.reject(&signer2, &rejected);
+        assert_eq!(
+            client.get_transaction_status(&rejected),
+            TransactionStatus::Rejected
+        );
+
+        let result = client.try_get_transaction_status(&99);
+        assert_eq!(result, Err(Ok(Error::TransactionNotFound)));
     }
 
-    /// Regression test: two proposals that together exceed the balance must be
-    /// rejected at proposal time, not silently allowed to race to execute.
     #[test]
-    fn test_propose_reserves_funds_prevents_double_spend() {
+    fn test_strict_self_withdrawal_needs_every_signer() {
         let (env, admin, _contract_id, client) = setup_contract();
 
         let signer1 = Address::generate(&env);
         let signer2 = Address::generate(&env);
-        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone()]);
-        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
 
-        // Deposit exactly 1_000_000
-        mint_asset(&env, &asset, &signer1, 1_000_000);
-        client.deposit(&signer1, &1_000_000);
+        client.set_strict_self_withdrawal(&admin, &true);
+        assert!(client.get_config().strict_self_withdrawal);
 
+        let memo = String::from_str(&env, "self");
+        let self_tx = client.propose_withdrawal(&signer1, &signer1, &asset, &100, &memo, &0);
+        assert!(client.is_self_directed(&self_tx));
+        client.approve(&signer2, &self_tx);
+        let result = client.try_execute(&signer1, &self_tx);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.approve(&signer3, &self_tx);
+        client.execute(&signer1, &self_tx);
+
+        // Withdrawals to anyone else still need only the threshold
         let recipient = Address::generate(&env);
+        let normal_tx = client.propose_withdrawal(&signer1, &recipient, &asset, &100, &memo, &0);
+        assert!(!client.is_self_directed(&normal_tx));
+        client.approve(&signer2, &normal_tx);
+        client.execute(&signer1, &normal_tx);
+        assert_eq!(client.get_balance(&asset), 800);
+    }
 
-        // First proposal for the full balance — should succeed
-        let _tx1 = client.propose_withdrawal(
-            &signer1,
-            &recipient,
-            &1_000_000,
-            &String::from_str(&env, "first"),
-        );
+    #[test]
+    fn test_strict_self_directed_schedule_needs_every_signer() {
+        let (env, admin, _contract_id, client) = setup_contract();
 
-        // Second proposal for any amount — must be rejected because all funds
-        // are already reserved by the first pending proposal.
-        let result = client.try_propose_withdrawal(
-            &signer2,
-            &recipient,
-            &1,
-            &String::from_str(&env, "second"),
-        );
-        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let signer3 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone(), signer2.clone(), signer3.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+        client.set_strict_self_withdrawal(&admin, &true);
+
+        let memo = String::from_str(&env, "salary");
+        let self_schedule =
+            client.propose_recurring(&signer1, &signer1, &asset, &100, &memo, &60, &2);
+        assert!(client.is_self_directed_schedule(&self_schedule));
+        client.approve_recurring(&signer2, &self_schedule);
+        let result = client.try_execute_recurring(&signer1, &self_schedule);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.approve_recurring(&signer3, &self_schedule);
+        client.execute_recurring(&signer1, &self_schedule);
+
+        // Schedules paying anyone else still need only the threshold
+        let vendor = Address::generate(&env);
+        let normal_schedule =
+            client.propose_recurring(&signer1, &vendor, &asset, &100, &memo, &60, &2);
+        assert!(!client.is_self_directed_schedule(&normal_schedule));
+        client.approve_recurring(&signer2, &normal_schedule);
+        client.execute_recurring(&signer1, &normal_schedule);
+        assert_eq!(client.get_balance(&asset), 800);
     }
 
     #[test]
@@ -1239,6 +5867,7 @@ mod test {
             &env,
             symbol_short!("treasury").into_val(&env),
             symbol_short!("init").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1261,7 +5890,7 @@ mod test {
 
         let depositor = Address::generate(&env);
         mint_asset(&env, &asset, &depositor, 1_000_000);
-        client.deposit(&depositor, &1_000_000);
+        client.deposit(&depositor, &asset, &1_000_000);
 
         let events = env.events().all();
         let event = events.get(events.len() - 1).unwrap();
@@ -1270,11 +5899,13 @@ mod test {
             &env,
             symbol_short!("treasury").into_val(&env),
             symbol_short!("deposit").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
             &env,
             depositor.into_val(&env),
+            asset.into_val(&env),
             1_000_000_i128.into_val(&env),
             1_000_000_i128.into_val(&env),
         ];
@@ -1292,14 +5923,16 @@ mod test {
         let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
 
         mint_asset(&env, &asset, &signer1, 5_000_000);
-        client.deposit(&signer1, &5_000_000);
+        client.deposit(&signer1, &asset, &5_000_000);
 
         let recipient = Address::generate(&env);
         let tx_id = client.propose_withdrawal(
             &signer1,
             &recipient,
+            &asset,
             &1_000_000,
             &String::from_str(&env, "rent"),
+            &0,
         );
 
         let events = env.events().all();
@@ -1309,6 +5942,7 @@ mod test {
             &env,
             symbol_short!("treasury").into_val(&env),
             symbol_short!("propose").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1316,6 +5950,7 @@ mod test {
             tx_id.into_val(&env),
             signer1.into_val(&env),
             recipient.into_val(&env),
+            asset.into_val(&env),
             1_000_000_i128.into_val(&env),
         ];
         let actual_data: Vec<Val> = Vec::try_from_val(&env, &event.2).unwrap();
@@ -1332,24 +5967,28 @@ mod test {
         let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
 
         mint_asset(&env, &asset, &signer1, 5_000_000);
-        client.deposit(&signer1, &5_000_000);
+        client.deposit(&signer1, &asset, &5_000_000);
         let recipient = Address::generate(&env);
         let tx_id = client.propose_withdrawal(
             &signer1,
             &recipient,
+            &asset,
             &1_000_000,
             &String::from_str(&env, "rent"),
+            &0,
         );
 
         let approval_count = client.approve(&signer2, &tx_id);
 
+        // The approval is followed by the threshold `ready` event
         let events = env.events().all();
-        let event = events.get(events.len() - 1).unwrap();
+        let event = events.get(events.len() - 2).unwrap();
         assert_eq!(event.0, contract_id);
         let expected_topics: Vec<Val> = vec![
             &env,
             symbol_short!("treasury").into_val(&env),
             symbol_short!("approve").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
@@ -1372,13 +6011,15 @@ mod test {
         let asset = initialize_treasury(&client, &env, &admin, 2, &signers);
 
         mint_asset(&env, &asset, &signer1, 5_000_000);
-        client.deposit(&signer1, &5_000_000);
+        client.deposit(&signer1, &asset, &5_000_000);
         let recipient = Address::generate(&env);
         let tx_id = client.propose_withdrawal(
             &signer1,
             &recipient,
+            &asset,
             &1_000_000,
             &String::from_str(&env, "rent"),
+            &0,
         );
         client.approve(&signer2, &tx_id);
         client.execute(&signer1, &tx_id);
@@ -1390,12 +6031,14 @@ mod test {
             &env,
             symbol_short!("treasury").into_val(&env),
             symbol_short!("execute").into_val(&env),
+            EVENT_VERSION.into_val(&env),
         ];
         assert_eq!(event.1, expected_topics);
         let expected_data: Vec<Val> = vec![
             &env,
             tx_id.into_val(&env),
             recipient.into_val(&env),
+            asset.into_val(&env),
             1_000_000_i128.into_val(&env),
             4_000_000_i128.into_val(&env),
             true.into_val(&env),
@@ -1518,6 +6161,7 @@ mod test {
                 &env,
                 symbol_short!("treasury").into_val(&env),
                 symbol_short!("dep_tok").into_val(&env),
+                EVENT_VERSION.into_val(&env),
             ];
             assert_eq!(event.1, expected_topics);
 
@@ -1684,4 +6328,62 @@ mod test {
         let result = client.try_set_threshold(&admin, &1);
         assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
+
+    #[test]
+    fn test_release_funds_by_governance() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let governance = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        // Nothing can be released until a governance contract is set
+        let result = client.try_release_funds(&governance, &recipient, &100);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.set_governance(&admin, &governance);
+        client.release_funds(&governance, &recipient, &400);
+
+        assert_eq!(client.get_balance(&asset), 600);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&recipient), 400);
+
+        let result = client.try_release_funds(&signer1, &recipient, &100);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_release_funds_respects_reserved_balance() {
+        let (env, admin, _contract_id, client) = setup_contract();
+
+        let signer1 = Address::generate(&env);
+        let signers = Vec::from_array(&env, [signer1.clone()]);
+        let asset = initialize_treasury(&client, &env, &admin, 1, &signers);
+        mint_asset(&env, &asset, &signer1, 1_000);
+        client.deposit(&signer1, &asset, &1_000);
+
+        let governance = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        client.set_governance(&admin, &governance);
+        client.propose_withdrawal(
+            &signer1,
+            &recipient,
+            &asset,
+            &700,
+            &String::from_str(&env, "pending"),
+            &0,
+        );
+
+        let result = client.try_release_funds(&governance, &recipient, &400);
+        assert_eq!(result, Err(Ok(Error::InsufficientFunds)));
+        let result = client.try_release_funds(&governance, &recipient, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        client.release_funds(&governance, &recipient, &300);
+        assert_eq!(client.get_balance(&asset), 700);
+    }
 }